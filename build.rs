@@ -0,0 +1,88 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Build script, providing the Git commit of the build as the `GIT_COMMIT` environment variable,
+//! and the list of the dependencies with their licenses as `$OUT_DIR/dependencies.rs`, which is
+//! included by the About window.
+
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+
+    let dependencies = match dependencies() {
+        Some(value) => value,
+        None => {
+            println!("cargo:warning=Failed to obtain the dependencies from `cargo metadata`.");
+            Vec::<(String, String, String)>::new()
+        }
+    };
+    let mut generated = String::from("pub static DEPENDENCIES: &[Dependency] = &[\n");
+    for (name, version, license) in dependencies {
+        generated.push_str(&format!(
+            "    Dependency {{ name: {:?}, version: {:?}, license: {:?} }},\n",
+            name, version, license
+        ));
+    }
+    generated.push_str("];\n");
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("dependencies.rs"), generated).unwrap();
+    println!("cargo:rerun-if-changed=Cargo.lock");
+}
+
+/// The name, version and license of the resolved dependencies of the build's target and features,
+/// sorted by name. `None` if `cargo metadata` failed.
+fn dependencies() -> Option<Vec<(String, String, String)>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let target = env::var("TARGET").ok()?;
+    let features = env::vars()
+        .filter_map(|(key, _)| {
+            let feature = key.strip_prefix("CARGO_FEATURE_")?.to_lowercase();
+            (feature != "default").then_some(feature)
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    let mut command = Command::new(cargo);
+    command.args([
+        "metadata",
+        "--format-version",
+        "1",
+        "--offline",
+        "--locked",
+        "--filter-platform",
+        target.as_str(),
+    ]);
+    if !features.is_empty() {
+        command.args(["--features", features.as_str()]);
+    }
+    let output = command.output().ok().filter(|output| output.status.success())?;
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let root = metadata["resolve"]["root"].as_str()?;
+    let resolved = metadata["resolve"]["nodes"]
+        .as_array()?
+        .iter()
+        .filter_map(|node| node["id"].as_str())
+        .filter(|id| *id != root)
+        .collect::<Vec<&str>>();
+    let mut dependencies = metadata["packages"]
+        .as_array()?
+        .iter()
+        .filter(|package| package["id"].as_str().is_some_and(|id| resolved.contains(&id)))
+        .map(|package| {
+            let text = |key: &str| package[key].as_str().unwrap_or("").to_string();
+            (text("name"), text("version"), text("license"))
+        })
+        .collect::<Vec<(String, String, String)>>();
+    dependencies.sort();
+    Some(dependencies)
+}
@@ -16,7 +16,19 @@ pub mod session;
 pub use session::Session;
 pub mod constants;
 pub mod enums;
-pub use enums::{WindowType, StringGroup};
+pub use enums::{LastWindowPolicy, StringGroup, WindowType};
 //pub mod data;
 pub mod clap;
 pub mod log;
+pub mod accelerator;
+pub mod timer;
+pub mod toast;
+pub mod tray;
+pub mod updates;
+#[cfg(feature = "single_instance")]
+pub mod single_instance;
+#[cfg(feature = "dev_tools")]
+pub mod dev_tools;
+pub mod theme;
+pub mod font;
+pub mod monitors;
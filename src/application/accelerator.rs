@@ -0,0 +1,312 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The keyboard accelerators of the application.
+//!
+//! Add an entry to `ACCELERATORS` for a new accelerator, and when needed an `Action` variant with
+//! its match branch in the `Message::Accelerator` handler of `application/application.rs`. The
+//! command palette lists the actions of the menu bar and of the registered accelerators, which
+//! have a label in `command_palette::label()`.
+//!
+//! The `Accelerators` registry is initialised with `ACCELERATORS`, followed by the user overrides
+//! of `Settings::accelerator_overrides`. Accelerators can be registered and unregistered at runtime
+//! with `State::register_accelerator()` and `State::unregister_accelerator()`, such as context
+//! specific accelerators.
+
+use iced::keyboard::{Key, Modifiers};
+use serde::{Deserialize, Serialize};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The actions that can be triggered by the accelerators and the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Action {
+    Preferences,    // Open the Preferences window.
+    Exit,           // Save settings and exit.
+    Close,          // Close the window, the same as the window decoration's close button.
+    New,            // Open a new Main window.
+    About,          // Open the About window.
+    LogViewer,      // Open the LogViewer window.
+    CommandPalette, // Open the CommandPalette window.
+    Undo,           // Revert the most recent change of the window.
+    Redo,           // Apply again the most recently reverted change of the window.
+    ZoomIn,         // Increase the scale of the user interface.
+    ZoomOut,        // Decrease the scale of the user interface.
+    ZoomReset,      // Restore the default scale of the user interface.
+}
+
+/// An accelerator, the character key with the modifiers, and its action.
+#[derive(Debug)]
+pub struct Accelerator {
+    pub character: &'static str,
+    pub modifiers: Modifiers,
+    pub action: Action,
+}
+
+/// The accelerator table.
+///
+/// `Modifiers::COMMAND` is the Command key on macOS and the Control key on other platforms.
+pub static ACCELERATORS: [Accelerator; 10] = [
+    Accelerator {
+        character: ",",
+        modifiers: Modifiers::COMMAND,
+        action: Action::Preferences,
+    },
+    Accelerator {
+        character: "q",
+        modifiers: Modifiers::COMMAND,
+        action: Action::Exit,
+    },
+    Accelerator {
+        character: "w",
+        modifiers: Modifiers::COMMAND,
+        action: Action::Close,
+    },
+    Accelerator {
+        character: "p",
+        modifiers: Modifiers::COMMAND.union(Modifiers::SHIFT),
+        action: Action::CommandPalette,
+    },
+    Accelerator {
+        character: "z",
+        modifiers: Modifiers::COMMAND,
+        action: Action::Undo,
+    },
+    Accelerator {
+        character: "y",
+        modifiers: Modifiers::COMMAND,
+        action: Action::Redo,
+    },
+    // The plus key is shifted on some keyboard layouts, thus the unshifted `=` is also accepted.
+    Accelerator {
+        character: "=",
+        modifiers: Modifiers::COMMAND,
+        action: Action::ZoomIn,
+    },
+    Accelerator {
+        character: "+",
+        modifiers: Modifiers::COMMAND.union(Modifiers::SHIFT),
+        action: Action::ZoomIn,
+    },
+    Accelerator {
+        character: "-",
+        modifiers: Modifiers::COMMAND,
+        action: Action::ZoomOut,
+    },
+    Accelerator {
+        character: "0",
+        modifiers: Modifiers::COMMAND,
+        action: Action::ZoomReset,
+    },
+];
+
+/// A key combination, the character key with the modifiers. The character is stored in lower case,
+/// as the accelerators ignore the case of the character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCombination {
+    pub character: String,
+    pub modifiers: Modifiers,
+}
+
+impl KeyCombination {
+    pub fn new(character: &str, modifiers: Modifiers) -> Self {
+        KeyCombination {
+            character: character.to_lowercase(),
+            modifiers,
+        }
+    }
+
+    /// The key combination of the text, such as `"ctrl+shift+n"`, where the modifiers are `ctrl`,
+    /// `shift`, `alt`, `logo`, and `command` for the platform's command key. `None` for an unknown
+    /// modifier, or a missing character.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (modifiers_text, character) = match text.rsplit_once('+') {
+            // The plus key itself, such as `"ctrl++"`.
+            Some((modifiers_text, "")) if modifiers_text.ends_with('+') => {
+                (modifiers_text.trim_end_matches('+'), "+")
+            }
+            Some((modifiers_text, character)) => (modifiers_text, character),
+            None => ("", text),
+        };
+        if character.is_empty() {
+            return None;
+        }
+        let mut modifiers = Modifiers::empty();
+        for modifier in modifiers_text.split('+').filter(|modifier| !modifier.is_empty()) {
+            modifiers |= match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CTRL,
+                "shift" => Modifiers::SHIFT,
+                "alt" => Modifiers::ALT,
+                "logo" => Modifiers::LOGO,
+                "command" => Modifiers::COMMAND,
+                _ => return None,
+            };
+        }
+        Some(KeyCombination::new(character, modifiers))
+    }
+
+    /// The key combination of the pressed key with its modifiers, `None` for a non-character key.
+    pub fn from_key(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let Key::Character(character) = key.as_ref() else {
+            return None;
+        };
+        Some(KeyCombination::new(character, modifiers))
+    }
+}
+
+/// The registered accelerators, initialised with `ACCELERATORS`. A key combination has at most
+/// one action, thus the last registration of a key combination wins.
+#[derive(Debug)]
+pub struct Accelerators {
+    entries: Vec<(KeyCombination, Action)>,
+}
+
+/// A user override of an accelerator, as stored in `Settings::accelerator_overrides`. The key
+/// combination is parsed by `KeyCombination::parse()`, and a missing action unregisters the key
+/// combination.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AcceleratorOverride {
+    pub keys: String,
+    #[serde(default)]
+    pub action: Option<Action>,
+}
+
+impl Accelerators {
+    pub fn new() -> Self {
+        Accelerators {
+            entries: ACCELERATORS
+                .iter()
+                .map(|accelerator| {
+                    (
+                        KeyCombination::new(accelerator.character, accelerator.modifiers),
+                        accelerator.action,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// The registry of `ACCELERATORS`, followed by the user overrides in order. Invalid key
+    /// combinations are logged and ignored.
+    pub fn with_overrides(overrides: &[AcceleratorOverride]) -> Self {
+        let mut accelerators = Accelerators::new();
+        for accelerator_override in overrides {
+            let Some(combination) = KeyCombination::parse(&accelerator_override.keys) else {
+                warn!("Ignoring the invalid accelerator ‘{}’.", accelerator_override.keys);
+                continue;
+            };
+            match accelerator_override.action {
+                Some(action) => {
+                    accelerators.register(combination, action);
+                }
+                None => {
+                    accelerators.unregister(&combination);
+                }
+            }
+        }
+        accelerators
+    }
+
+    /// Register the action for the key combination, returning the replaced action, if any.
+    pub fn register(&mut self, combination: KeyCombination, action: Action) -> Option<Action> {
+        let replaced = self.unregister(&combination);
+        if let Some(replaced) = replaced {
+            if replaced != action {
+                warn!(
+                    "The accelerator {:?} now triggers {:?} instead of {:?}.",
+                    combination, action, replaced
+                );
+            }
+        }
+        self.entries.push((combination, action));
+        replaced
+    }
+
+    /// Unregister the key combination, returning its action, if any.
+    pub fn unregister(&mut self, combination: &KeyCombination) -> Option<Action> {
+        let index = self.entries.iter().position(|(entry, _)| entry == combination)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// The actions of the registered accelerators, in registration order.
+    pub fn actions(&self) -> impl Iterator<Item = Action> + '_ {
+        self.entries.iter().map(|(_, action)| *action)
+    }
+
+    /// Find the action of the pressed key with its modifiers, if it is an accelerator.
+    pub fn find(&self, key: &Key, modifiers: Modifiers) -> Option<Action> {
+        let combination = KeyCombination::from_key(key, modifiers)?;
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == combination)
+            .map(|(_, action)| *action)
+    }
+}
+
+impl Default for Accelerators {
+    fn default() -> Self {
+        Accelerators::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_combination_maps_to_action() {
+        let mut accelerators = Accelerators::new();
+        let key = Key::Character("K".into());
+        assert_eq!(accelerators.find(&key, Modifiers::COMMAND), None);
+        accelerators.register(KeyCombination::new("k", Modifiers::COMMAND), Action::About);
+        assert_eq!(accelerators.find(&key, Modifiers::COMMAND), Some(Action::About));
+        assert_eq!(accelerators.find(&key, Modifiers::SHIFT), None);
+    }
+
+    #[test]
+    fn last_registration_wins() {
+        let mut accelerators = Accelerators::new();
+        let combination = KeyCombination::new("w", Modifiers::COMMAND);
+        assert_eq!(accelerators.register(combination, Action::Exit), Some(Action::Close));
+        let key = Key::Character("w".into());
+        assert_eq!(accelerators.find(&key, Modifiers::COMMAND), Some(Action::Exit));
+    }
+
+    #[test]
+    fn parse_key_combination() {
+        assert_eq!(
+            KeyCombination::parse("ctrl+Shift+N"),
+            Some(KeyCombination::new("n", Modifiers::CTRL | Modifiers::SHIFT))
+        );
+        assert_eq!(
+            KeyCombination::parse("command++"),
+            Some(KeyCombination::new("+", Modifiers::COMMAND))
+        );
+        assert_eq!(KeyCombination::parse("f"), Some(KeyCombination::new("f", Modifiers::empty())));
+        assert_eq!(KeyCombination::parse("hyper+x"), None);
+        assert_eq!(KeyCombination::parse("ctrl+"), None);
+    }
+
+    #[test]
+    fn overrides_register_and_unregister() {
+        let accelerators = Accelerators::with_overrides(&[
+            AcceleratorOverride {
+                keys: "command+n".to_string(),
+                action: Some(Action::New),
+            },
+            AcceleratorOverride {
+                keys: "command+q".to_string(),
+                action: None,
+            },
+            AcceleratorOverride {
+                keys: "unknown+x".to_string(),
+                action: Some(Action::About),
+            },
+        ]);
+        let new = Key::Character("n".into());
+        let exit = Key::Character("q".into());
+        assert_eq!(accelerators.find(&new, Modifiers::COMMAND), Some(Action::New));
+        assert_eq!(accelerators.find(&exit, Modifiers::COMMAND), None);
+    }
+}
@@ -3,11 +3,23 @@
 
 use crate::{
     application::{
+        accelerator::{Accelerators, Action, KeyCombination},
+        constants,
         clap::Clap,
         error::ApplicationError,
         environment::Environment,
-        log::{new_logger, update_logger, LogLevel,},
-        session::Session,
+        font,
+        monitors,
+        log::{
+            is_valid_pattern, new_log_buffer, new_logger, panic_message, set_panic_hook,
+            update_logger, LogLevel,
+        },
+        session::{clamp_ui_scale, OpenWindowRecord, Session},
+        theme::{system_mode_changes, with_accent, ThemePreference},
+        timer::{after, ticks},
+        toast::{self, Toast, ToastLevel},
+        tray::TrayAction,
+        LastWindowPolicy,
         StringGroup,
         WindowType,
     },
@@ -15,27 +27,54 @@ use crate::{
         error::CoreError,
         localisation::{Localisation, StringCache},
         state::Manager,
+        traits::{SaveDataTrait, UndoableTrait},
     },
     localisation,
     widget::event_control,
     window::{
+        about,
+        command_palette,
         default,
         confirm_exit,
         fatal_error,
+        information,
+        log_viewer,
         main,
         preferences,
+        recoverable_error,
         unsaved_data,
+        unsaved_data_batch,
     },
 };
 use clap::Parser;
-use core::panic;
+use rfd::AsyncFileDialog;
+use std::{collections::VecDeque, path::PathBuf, time::Duration};
+#[cfg(feature = "single_instance")]
+use crate::application::single_instance::{self, instance_requests};
+#[cfg(feature = "dev_tools")]
+use crate::application::dev_tools::l10n_changes;
+#[cfg(feature = "updates")]
+use crate::application::updates::{self, UpdateStatus};
+#[cfg(feature = "updates")]
+use i18n::utility::PlaceholderValue;
+#[cfg(feature = "updates")]
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
 use iced::{
-    event::{self, Event}, window, Element, Length, Point, Size, Subscription, Task,
+    event::{self, Event}, keyboard, mouse, window, Element, Length, Point, Rectangle, Size,
+    Subscription, Task, Theme,
 };
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
 //
 // ----- The application supported messages
 //
@@ -48,21 +87,65 @@ pub enum Message {
     CloseRequested(window::Id),
     Resized(window::Id, Size),
     Moved(window::Id, Point),
+    ResizedMaximized(window::Id, Size, bool), // Resized, with the window's maximised state.
+    MovedMaximized(window::Id, Point, bool), // Moved, with the window's maximised state.
+    SystemThemeChanged(dark_light::Mode), // The operating system's theme mode has changed.
+    Accelerator(window::Id, Action), // Accelerator key pressed in the focused window.
+    ArrowKey(window::Id, keyboard::key::Named), // Arrow key not captured by the focused window.
+    KeyPressed(window::Id, keyboard::Key, keyboard::Modifiers), // Key not captured by the focused window.
+    Undo(window::Id), // Revert the most recent change of the window.
+    Redo(window::Id), // Apply again the most recently reverted change of the window.
+    NavigateBack(window::Id), // Mouse back button pressed in the window.
+    NavigateForward(window::Id), // Mouse forward button pressed in the window.
+    FileHovered(window::Id), // Files are being dragged over the window.
+    FilesHoveredLeft(window::Id), // Files are no longer being dragged over the window.
+    FileDropped(window::Id, PathBuf),
+    Focused(window::Id), // The window has gained focus.
+    Unfocused(window::Id), // The window has lost focus.
+    MonitorsDetected(Vec<Rectangle>), // The monitor areas, detected off the UI thread.
+    #[cfg(feature = "single_instance")]
+    InstanceRequest(Vec<String>), // Command line arguments forwarded by a later launch.
+    Tray(TrayAction), // Action selected in the tray icon's menu, requires the `tray` feature.
+    #[cfg(feature = "dev_tools")]
+    L10nReloaded, // The `l10n` directory has changed, when using `--watch-l10n`.
+    AutosaveTick, // Save the unsaved data of all the main windows.
 
     // Generic application messages
     Initialise, // Continue with initialising once application state instance exists.
     WindowOpened(window::Id), // Indicates window has opened. Optional action be done
     WindowClosed(window::Id), // Remove the state of closed window Id.
     ThreadClosed(usize), // Remove the thread, now that windows are closed.
+    SizeLocked(window::Id, Size), // The current size of the window, locked by `set_resizable()`.
     Exit,  // Save settings and exit.
+    SaveAndExit, // Save the unsaved data of all the windows, then save settings and exit.
     Terminate,         // Terminates application without any saving.
     Close(window::Id), // Generic window close, nothing else is done.
+    DismissToast(usize), // Remove the toast having the identifier.
+    Notify { summary: String, body: String }, // Raise a desktop notification of localised strings.
+    #[cfg(feature = "updates")]
+    CheckForUpdates, // Fetch the release manifest, off the UI thread.
+    #[cfg(feature = "updates")]
+    UpdateChecked(Result<UpdateStatus, String>), // The result of checking for updates.
+    Retry(window::Id, Box<Message>), // Dispatch again, a failure redisplays RecoverableError.
+    OpenFileDialog(window::Id, bool), // Display the file open dialogue for the window, `true` for read-only.
+    SaveFileDialog(window::Id), // Display the file save dialogue for the window.
+    FileOpened(window::Id, Option<PathBuf>, bool), // Selected file, `None` if the dialogue was cancelled.
+    FileSaved(window::Id, Option<PathBuf>), // Selected file, `None` if the dialogue was cancelled.
+    ExportSettings(window::Id, Option<PathBuf>), // Selected file, `None` if the dialogue was cancelled.
+    ImportSettings(window::Id, Option<PathBuf>), // Selected file, `None` if the dialogue was cancelled.
+    RestoreDefaults(window::Id), // Confirmed in the restore defaults dialogue.
 
     // Application window specific messages
     UnsavedData(window::Id, unsaved_data::Message),
+    Information(window::Id, information::Message),
+    FatalError(window::Id, fatal_error::Message),
+    RecoverableError(window::Id, recoverable_error::Message),
+    CommandPalette(window::Id, command_palette::Message),
+    UnsavedDataBatch(window::Id, unsaved_data_batch::Message),
     Default(window::Id, default::Message),
     Main(window::Id, main::Message),
     Preferences(window::Id, preferences::Message),
+    LogViewer(window::Id, log_viewer::Message),
 }
 
 //
@@ -96,6 +179,23 @@ pub struct State {
 
     // Indicates if application is running for the first time.
     first_use: bool,
+
+    // The toasts displayed on top of the focused window, oldest first.
+    toasts: VecDeque<Toast>,
+    next_toast: usize,
+
+    // The last window to have gained focus.
+    focused: Option<window::Id>,
+
+    // What happens once the last window thread has been closed.
+    pub on_last_window_closed: LastWindowPolicy,
+
+    // The registered accelerators, consulted for the keys not captured by the focused window.
+    accelerators: Accelerators,
+
+    // The system tray icon, removed when dropped.
+    #[cfg(feature = "tray")]
+    tray: Option<tray_icon::TrayIcon>,
 }
 
 impl State {
@@ -107,18 +207,78 @@ impl State {
         }
     }
 
+    /// Initialise the application state for driving it headlessly, such as in tests, without
+    /// starting `iced`. The default settings are used, with the session file in the temporary
+    /// directory, the `l10n` directory of the crate, and logging turned off. Feed
+    /// `Message::Initialise` to `try_update()` to display the first window.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn new_for_test() -> State {
+        match State::try_new_for_test() {
+            Err(error) => panic!("Test state initialisation error: {}", error),
+            Ok(value) => value,
+        }
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn try_new_for_test() -> Result<State, ApplicationError> {
+        use crate::application::log::LogBuffer;
+        use std::{ffi::OsString, path::Path, sync::OnceLock};
+
+        // The logger can only be initialised once for the process, thus it is shared.
+        static LOGGER: OnceLock<(log4rs::Handle, LogBuffer)> = OnceLock::new();
+        let (logger, log_buffer) = LOGGER
+            .get_or_init(|| {
+                let log_buffer = new_log_buffer();
+                (new_logger(LogLevel::Off, None, &log_buffer), log_buffer)
+            })
+            .clone();
+        let mut config = std::env::temp_dir().join(format!("{}_test", constants::PACKAGE_NAME));
+        config.set_extension("ron");
+        let clap = Clap::parse_from([
+            OsString::from(constants::PACKAGE_NAME),
+            OsString::from("--defaults"),
+            OsString::from("--config"),
+            config.into_os_string(),
+        ]);
+        let environment = Environment::try_new(logger, log_buffer, clap)?;
+        let session = Session::default();
+        let localisation = Localisation::try_new_with_path(
+            Path::new(env!("CARGO_MANIFEST_DIR")),
+            &session.settings.ui.language,
+        )?;
+        Ok(State {
+            initialised: false,
+            session,
+            environment,
+            localisation,
+            string_cache: StringCache::new(),
+            manager: Manager::try_new()?,
+            first_use: false,
+            toasts: VecDeque::<Toast>::new(),
+            next_toast: 0,
+            focused: None,
+            on_last_window_closed: constants::LAST_WINDOW_POLICY,
+            accelerators: Accelerators::new(),
+            #[cfg(feature = "tray")]
+            tray: None,
+        })
+    }
+
     /// The actual implementation of creating the state.
     fn try_new() -> Result<(State, Task<Message>), ApplicationError> {
         // Use clap for task line options. See clap.rs for various task options.
         let clap = Clap::parse();
 
-        // Initialise logging to console.
-        // For now just log to stdout.
+        // Initialise logging to console, and to the log file if specified on the command line.
         let log_level = match clap.log_level {
             None => LogLevel::Default,
             Some(value) => value,
         };
-        let mut logger = new_logger(log_level);
+        let log_buffer = new_log_buffer();
+        let logger = new_logger(log_level, clap.log_file.as_deref(), &log_buffer);
+        set_panic_hook();
+        let mut environment = Environment::try_new(logger, log_buffer, clap)?;
+        let clap = &environment.clap;
 
         // Initialise the session, if available from previous saved session.
         #[allow(unused_mut)]
@@ -126,7 +286,7 @@ impl State {
         let mut first_use = false;
         if !clap.defaults {
             info!("Using saved settings.");
-            match Session::try_restore() {
+            match Session::try_restore(&environment.config_file) {
                 Err(_error) => {
                     warn!("Restore state error: `{:?}`", _error);
                     first_use = true
@@ -135,18 +295,65 @@ impl State {
             }
         }
 
+        // An invalid log format is replaced by the default log format.
+        if !is_valid_pattern(session.settings.log_levels.log_format.as_str()) {
+            warn!(
+                "Invalid log format ‘{}’, using the default log format.",
+                session.settings.log_levels.log_format
+            );
+            session.settings.log_levels.log_format = constants::DEFAULT_LOG_FORMAT.to_string();
+        }
+
         // Update logger to all the log categories
         if clap.log_level.is_none() {
-            update_logger(&mut logger, &session.settings.log_levels);
+            let mut log_levels = session.settings.log_levels.clone();
+            if clap.log_file.is_some() {
+                log_levels.log_to_file = clap.log_file.clone();
+            }
+            update_logger(&mut environment.logger, &log_levels, &environment.log_buffer);
         }
 
-        let environment = Environment::try_new(logger, clap)?;
-        let localisation =
+        let mut localisation =
             Localisation::try_new(&environment, &session.settings.ui.language)?;
+
+        // Correct the setting, when the language is no longer available and a fallback was used.
+        // The setting is kept when only the embedded strings are available.
+        let language = localisation.default_language();
+        if localisation.missing_database().is_none()
+            && language.as_str() != session.settings.ui.language
+        {
+            session.settings.ui.language = language.as_str().to_string();
+        }
+
+        // On first use, prefer the operating system's locale when it is an available language.
+        if first_use && localisation.missing_database().is_none() {
+            if let Some(locale) = sys_locale::get_locale() {
+                match localisation.match_language(locale.as_str()) {
+                    Some(tag) => {
+                        info!(
+                            "Using the operating system's locale `{}` as `{}`.",
+                            locale,
+                            tag.as_str()
+                        );
+                        session.settings.ui.language = tag.as_str().to_string();
+                        localisation.change_default_language(tag)?;
+                    }
+                    None => debug!("Operating system's locale `{}` is not available.", locale),
+                }
+            }
+        }
         let string_cache = StringCache::new();
         debug!("Localisation initialised.");
-        let manager = Manager::try_new()?;
+        #[cfg(feature = "tray")]
+        let tray = crate::application::tray::new(&localisation, session.settings.accent);
+        let mut manager = Manager::try_new()?;
+        manager.set_monitors(monitors::detect());
         debug!("State manager initialised.");
+        let accelerators = Accelerators::with_overrides(&session.settings.accelerator_overrides);
+
+        // The fonts are loaded before the first window is displayed.
+        let tasks =
+            font::load_fonts(&session.settings.fonts).chain(Task::done(Message::Initialise));
         Ok((
             State {
                 initialised: false,
@@ -156,47 +363,154 @@ impl State {
                 string_cache,
                 manager,
                 first_use,
+                toasts: VecDeque::<Toast>::new(),
+                next_toast: 0,
+                focused: None,
+                on_last_window_closed: constants::LAST_WINDOW_POLICY,
+                accelerators,
+                #[cfg(feature = "tray")]
+                tray,
             },
-            Task::done(Message::Initialise),
+            tasks,
         ))
     }
 
+    /// Register the action for the key combination, replacing the action of an already registered
+    /// key combination. Window states can register context specific accelerators when focused.
+    pub fn register_accelerator(&mut self, combination: KeyCombination, action: Action) {
+        self.accelerators.register(combination, action);
+    }
+
+    /// Unregister the key combination, returning its action, if any.
+    pub fn unregister_accelerator(&mut self, combination: &KeyCombination) -> Option<Action> {
+        self.accelerators.unregister(combination)
+    }
+
+    /// The registered accelerators.
+    pub fn accelerators(&self) -> &Accelerators {
+        &self.accelerators
+    }
+
+    /// Apply the replaced settings, such as after importing or restoring the defaults: the
+    /// accelerators are rebuilt from the overrides, and the font files are loaded. The default
+    /// font applies on the next start.
+    pub fn apply_settings(&mut self) -> Task<Message> {
+        self.accelerators =
+            Accelerators::with_overrides(&self.session.settings.accelerator_overrides);
+        font::load_fonts(&self.session.settings.fonts)
+    }
+
     /// Indicates if the application has started for the first time
     pub fn first_use(&self) -> bool {
         self.first_use
     }
 
+    /// The Id of the last focused window, if any.
+    pub fn focused(&self) -> Option<window::Id> {
+        self.focused
+    }
+
     //
     // ------ Update methods
     //
 
-    /// To capture the `iced` window events.
+    /// To capture the `iced` window events, the accelerator keys and the mouse back and forward
+    /// buttons not captured by widgets, the operating system's theme changes when following the
+    /// system theme, the autosave timer when enabled, the expiry of information windows and
+    /// toasts, and the command line arguments forwarded by later launches when the
+    /// `single_instance` feature is enabled.
     pub fn subscription(&self) -> Subscription<Message> {
-        event::listen_with(
-            |event, _status, id| {
+        let events = event::listen_with(
+            |event, status, id| {
                 match event {
                     Event::Window(event) => match event {
                         window::Event::CloseRequested => Some(Message::CloseRequested(id)),
                         window::Event::Resized(size) => Some(Message::Resized(id, size)),
                         window::Event::Moved(point) => Some(Message::Moved(id, point)),
+                        window::Event::FileHovered(_) => Some(Message::FileHovered(id)),
+                        window::Event::FilesHoveredLeft => Some(Message::FilesHoveredLeft(id)),
+                        window::Event::FileDropped(path) => Some(Message::FileDropped(id, path)),
+                        window::Event::Focused => Some(Message::Focused(id)),
+                        window::Event::Unfocused => Some(Message::Unfocused(id)),
                         _ => None
                     }
+                    Event::Mouse(mouse::Event::ButtonPressed(button))
+                        if status == event::Status::Ignored =>
+                    {
+                        match button {
+                            mouse::Button::Back => Some(Message::NavigateBack(id)),
+                            mouse::Button::Forward => Some(Message::NavigateForward(id)),
+                            _ => None,
+                        }
+                    }
+                    Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                        if status == event::Status::Ignored =>
+                    {
+                        match key {
+                            keyboard::Key::Named(
+                                named @ (keyboard::key::Named::ArrowUp
+                                | keyboard::key::Named::ArrowDown)
+                            ) => Some(Message::ArrowKey(id, named)),
+                            _ => Some(Message::KeyPressed(id, key, modifiers)),
+                        }
+                    }
                     _ => None
                 }
             }
-        )
+        );
+        let mut subscriptions = vec![events, information::subscription(self)];
+        for toast in self.toasts.iter() {
+            subscriptions.push(
+                Subscription::run_with_id(
+                    ("toast", toast.id),
+                    after(constants::TOAST_TTL, toast.id),
+                )
+                .map(Message::DismissToast)
+            );
+        }
+        if self.session.settings.theme == ThemePreference::System {
+            subscriptions.push(
+                Subscription::run(system_mode_changes).map(Message::SystemThemeChanged)
+            );
+        }
+        if let Some(seconds) = self.session.settings.autosave_secs.filter(|seconds| *seconds > 0) {
+            subscriptions.push(
+                Subscription::run_with_id(seconds, ticks(Duration::from_secs(seconds as u64)))
+                    .map(|_| Message::AutosaveTick)
+            );
+        }
+        #[cfg(feature = "single_instance")]
+        subscriptions.push(Subscription::run(instance_requests).map(Message::InstanceRequest));
+        #[cfg(feature = "tray")]
+        subscriptions.push(Subscription::run(crate::application::tray::tray_events).map(Message::Tray));
+        #[cfg(feature = "dev_tools")]
+        if self.environment.clap.watch_l10n {
+            let path = self.environment.application_path.join("l10n");
+            subscriptions.push(
+                Subscription::run_with_id("l10n", l10n_changes(path)).map(|_| Message::L10nReloaded)
+            );
+        }
+        Subscription::batch(subscriptions)
     }
 
     /// The entry point for the `iced` update functionality.
+    ///
+    /// A panic during the update is caught and displayed in the FatalError window, as the
+    /// application state may now be inconsistent. The panic hook has already logged the panic.
     pub fn update(&mut self, message: Message) -> Task<Message> {
-        match self.try_update(message) {
-            Err(error) => fatal_error::display(self, error),
-            Ok(value) => value,
+        match panic::catch_unwind(AssertUnwindSafe(|| self.try_update(message))) {
+            Err(payload) => fatal_error::display(
+                self,
+                ApplicationError::Panic(panic_message(payload.as_ref(), None)),
+            ),
+            Ok(Err(error)) => fatal_error::display(self, error),
+            Ok(Ok(value)) => value,
         }
     }
 
-    /// The actual implementation of updating the state.
-    fn try_update(
+    /// The actual implementation of updating the state. Also used directly for driving the state
+    /// headlessly, see `State::new_for_test()`.
+    pub fn try_update(
         &mut self,
         message: Message,
     ) -> Result<Task<Message>, ApplicationError> {
@@ -217,7 +531,11 @@ impl State {
                     match state.window_type() {
                         WindowType::Default => {
                             debug!("Default window's decoration button was pressed.");
-                            tasks = self.close_thread(id)?
+                            if self.hides_to_tray() {
+                                tasks = window::change_mode(id, window::Mode::Hidden)
+                            } else {
+                                tasks = self.close_thread(id)?
+                            }
                         }
                         WindowType::Main => {
                             debug!("Main window's decoration button was pressed.");
@@ -225,20 +543,239 @@ impl State {
                         }
                         WindowType::FatalError => tasks = iced::exit(), // Session is not saved.
                         WindowType::Preferences => tasks = preferences::cancel_and_close(self, id)?,
+                        WindowType::Information => tasks = information::close(self, id)?,
 
                         // Generic window close
                         _ => tasks = self.manager.close_window(id)?,
                     }
                 }
             }
-            Message::Resized(id, size) => tasks = self.resized(&id, size)?,
-            Message::Moved(id, point) => tasks = self.moved(&id, point)?,
+            Message::Resized(id, size) => {
+                tasks = match self.manager.locked_size(&id) {
+                    Some(locked) if locked != size => window::resize(id, locked),
+                    Some(_) => Task::none(),
+                    None => window::get_maximized(id)
+                        .map(move |maximized| Message::ResizedMaximized(id, size, maximized)),
+                }
+            }
+            Message::Moved(id, point) => {
+                tasks = window::get_maximized(id)
+                    .map(move |maximized| Message::MovedMaximized(id, point, maximized))
+            }
+            Message::ResizedMaximized(id, size, maximized) => {
+                // The window may have closed while the maximised state was being queried.
+                if self.manager.state(&id).is_some() {
+                    tasks = self.resized(&id, size, maximized)?
+                }
+            }
+            Message::MovedMaximized(id, point, maximized) => {
+                // The window may have closed while the maximised state was being queried.
+                if self.manager.state(&id).is_some() {
+                    tasks = self.moved(&id, point, maximized)?
+                }
+            }
+
+            Message::KeyPressed(id, key, modifiers) => {
+                if let Some(action) = self.accelerators.find(&key, modifiers) {
+                    tasks = Task::done(Message::Accelerator(id, action))
+                }
+            }
+            Message::Accelerator(id, action) => {
+                // Keyboard events are for the focused window, which is ignored when disabled.
+                let Some(state) = self.manager.state(&id) else {
+                    return Ok(tasks);
+                };
+                if !self.manager.is_enabled(&id).unwrap() {
+                    return Ok(tasks);
+                }
+                debug!("Accelerator {:?} for window {:?}.", action, id);
+                match action {
+                    Action::Preferences => match state.window_type() {
+                        // Only windows with the menu bar.
+                        WindowType::Default | WindowType::Main => {
+                            tasks = preferences::display(self, id)?
+                        }
+                        _ => {}
+                    },
+                    // Same as the Exit menu item, thus the unsaved data can be saved first.
+                    Action::Exit => tasks = confirm_exit::display(self, id)?,
+                    Action::Close => tasks = Task::done(Message::CloseRequested(id)),
+                    Action::New => match state.window_type() {
+                        WindowType::Default | WindowType::Main => {
+                            tasks = self.open_thread(WindowType::Main, None, false)?
+                        }
+                        _ => {}
+                    },
+                    Action::About => match state.window_type() {
+                        WindowType::Default | WindowType::Main => {
+                            tasks = about::display(self, id)?
+                        }
+                        _ => {}
+                    },
+                    Action::LogViewer => match state.window_type() {
+                        WindowType::Default | WindowType::Main => {
+                            tasks = log_viewer::display(self, id)?
+                        }
+                        _ => {}
+                    },
+                    Action::Undo => tasks = Task::done(Message::Undo(id)),
+                    Action::Redo => tasks = Task::done(Message::Redo(id)),
+                    // The scale applies to all the windows.
+                    Action::ZoomIn => {
+                        let scale = self.session.settings.ui_scale + constants::UI_SCALE_STEP;
+                        self.session.settings.set_ui_scale(scale);
+                    }
+                    Action::ZoomOut => {
+                        let scale = self.session.settings.ui_scale - constants::UI_SCALE_STEP;
+                        self.session.settings.set_ui_scale(scale);
+                    }
+                    Action::ZoomReset => self.session.settings.set_ui_scale(1.0),
+                    Action::CommandPalette => match state.window_type() {
+                        WindowType::Default | WindowType::Main => {
+                            tasks = command_palette::display(self, id)?
+                        }
+                        _ => {}
+                    },
+                }
+            }
+            Message::Undo(id) | Message::Redo(id) => {
+                let Some(state) = self.manager.state_mut(&id) else {
+                    return Ok(tasks);
+                };
+                // Only the window types having an undo history.
+                let undoable: Option<&mut dyn UndoableTrait> = match state.window_type() {
+                    WindowType::Main => state
+                        .as_any_mut()
+                        .downcast_mut::<main::State>()
+                        .map(|actual| actual as &mut dyn UndoableTrait),
+                    _ => None,
+                };
+                if let Some(undoable) = undoable {
+                    match message {
+                        Message::Undo(_) => undoable.undo(),
+                        _ => undoable.redo(),
+                    }
+                }
+            }
+            Message::NavigateBack(id) | Message::NavigateForward(id) => {
+                // Only the focused window navigates, which is ignored when disabled.
+                if self.focused != Some(id) || !self.manager.is_enabled(&id).unwrap_or(false) {
+                    return Ok(tasks);
+                }
+                let Some(state) = self.manager.state_mut(&id) else {
+                    return Ok(tasks);
+                };
+                if let Some(navigable) = state.as_navigable() {
+                    match message {
+                        Message::NavigateBack(_) => navigable.navigate_back(),
+                        _ => navigable.navigate_forward(),
+                    }
+                }
+            }
+            Message::ArrowKey(id, named) => {
+                // Only the command palette navigates using the arrow keys.
+                if self.manager.state(&id).is_some_and(|state| {
+                    state.window_type() == WindowType::CommandPalette
+                }) {
+                    let inner_message = match named {
+                        keyboard::key::Named::ArrowUp => command_palette::Message::Previous,
+                        _ => command_palette::Message::Next,
+                    };
+                    tasks = command_palette::try_update(
+                        self,
+                        Message::CommandPalette(id, inner_message),
+                    )?
+                }
+            }
+            Message::FileHovered(id) | Message::FilesHoveredLeft(id) => {
+                if let Some(state) = self.manager.state_mut(&id) {
+                    state.set_file_hovered(matches!(message, Message::FileHovered(_)));
+                }
+            }
+            Message::FileDropped(id, path) => {
+                if let Some(state) = self.manager.state_mut(&id) {
+                    state.set_file_hovered(false);
+                }
+                let path = path.to_string_lossy().to_string();
+                match main::find_path(self, path.as_str()) {
+                    // Already opened, thus just focus the window.
+                    Some(main_id) => tasks = main::focus_path(self, main_id, path.as_str()),
+                    None => tasks = self.open_thread(WindowType::Main, Some(path), false)?,
+                }
+            }
+            Message::Focused(id) => {
+                self.focused = Some(id);
+
+                if let Some(state) = self.manager.state_mut(&id) {
+                    if let Err(error) = state.on_focus() {
+                        self.push_toast(&error, ToastLevel::Warning);
+                    }
+                }
+
+                // Monitors may have been connected or disconnected while the application was in
+                // the background. Querying the X11 server is slow, thus is done off the UI thread,
+                // and the cached monitors are used until then.
+                tasks = Task::perform(async { monitors::detect() }, Message::MonitorsDetected);
+            }
+            Message::MonitorsDetected(monitors) => self.manager.set_monitors(monitors),
+            // The last focused window is kept, as the focus may have left the application.
+            Message::Unfocused(id) => {
+                if let Some(state) = self.manager.state_mut(&id) {
+                    state.on_blur();
+                }
+            }
+            Message::AutosaveTick => {
+                if let Err(error) = self.autosave() {
+                    self.push_toast(&error, ToastLevel::Error);
+                }
+            }
+            Message::SystemThemeChanged(mode) => {
+                debug!("System theme mode changed to {:?}.", mode);
+                self.environment.system_mode = mode;
+            }
+            Message::Tray(action) => {
+                debug!("Tray action {:?}.", action);
+                match action {
+                    TrayAction::Show => {
+                        // No windows remain when hidden by `LastWindowPolicy::Hide`.
+                        if self.manager.thread_count() == 0 {
+                            tasks = default::display(self)?;
+                        }
+                        for id in self.manager.thread_list() {
+                            tasks = tasks
+                                .chain(window::change_mode(id, window::Mode::Windowed))
+                                .chain(window::gain_focus(id));
+                        }
+                    }
+                    TrayAction::Preferences => {
+                        if let Some(id) = self.manager.thread_list().first().copied() {
+                            tasks = window::change_mode(id, window::Mode::Windowed)
+                                .chain(preferences::display(self, id)?);
+                        }
+                    }
+                    TrayAction::Quit => tasks = self.exit(),
+                }
+            }
+            #[cfg(feature = "single_instance")]
+            Message::InstanceRequest(arguments) => {
+                debug!("Instance request: {:?}", arguments);
+                match Clap::try_parse_from(arguments) {
+                    Ok(clap) => tasks = self.open_thread(WindowType::Main, clap.path, false)?,
+                    Err(error) => warn!("Invalid instance request arguments: {}", error),
+                }
+            }
 
             // Generic application messages
             Message::Initialise => {
                 debug!("Reached 2nd part of initialise.");
                 // Display the window, now that application state exists.
                 if !self.initialised {
+                    // Only the FatalError window can be displayed using the embedded strings.
+                    if let Some(path) = self.localisation.missing_database() {
+                        let error = CoreError::LocalisationMissing(path.to_path_buf());
+                        self.initialised = true;
+                        return Ok(fatal_error::display(self, ApplicationError::Core(error)));
+                    }
                     if self.first_use {
                         trace!("First use");
                         if !self.string_cache.exists(&StringGroup::Preferences) {
@@ -260,31 +797,146 @@ impl State {
                         tasks = self.manager.try_create_thread(&mut self.session, Box::new(state))?;
                         debug!("Opening Preferences window.");
                     } else {
-                        tasks = default::display(self)?;
-                        debug!("Opening Default window.");
+                        if self.session.settings.restore_session {
+                            tasks = self.try_restore_windows()?;
+                        }
+                        if self.manager.thread_count() == 0 {
+                            tasks = default::display(self)?;
+                            debug!("Opening Default window.");
+                        }
                     }
                     self.initialised = true;
                 }
                 println!("Initialise has completed."); // Keep both these line
                 info!("Initialise has completed."); // Keep both these line
             },
+            Message::SizeLocked(id, size) => self.manager.lock_size(&id, size),
             Message::WindowOpened(_id) => {} // Post actions can be place here for the opened window (Id provided)
             Message::WindowClosed(id) => self.manager.window_closed(id)?,
             Message::ThreadClosed(id) => {
                 self.manager.thread_closed(id)?;
                 if self.manager.thread_count() == 0 {
-                    tasks = default::display(self)?;
+                    tasks = match self.on_last_window_closed {
+                        LastWindowPolicy::SpawnDefault => default::display(self)?,
+                        LastWindowPolicy::Hide if self.has_tray() => {
+                            debug!("Last window closed, running without windows.");
+                            Task::none()
+                        }
+
+                        // Without the tray icon, there is no way to show a window nor to quit.
+                        LastWindowPolicy::Exit | LastWindowPolicy::Hide => self.exit(),
+                    };
+                } else {
+                    main::update_threads(self)?;
                 }
             }
             Message::Exit => tasks = self.exit(),
-            Message::Terminate => tasks = iced::exit(),
+            Message::SaveAndExit => {
+                self.save_all()?;
+                tasks = self.exit()
+            }
+            Message::Terminate => {
+                #[cfg(feature = "single_instance")]
+                single_instance::release();
+                tasks = iced::exit()
+            }
             Message::Close(id) => tasks = self.manager.close_window(id)?,
+            Message::Notify { summary, body } => self.notify(summary.as_str(), body.as_str()),
+            #[cfg(feature = "updates")]
+            Message::CheckForUpdates => tasks = Task::perform(updates::check(), Message::UpdateChecked),
+            #[cfg(feature = "updates")]
+            Message::UpdateChecked(result) => self.update_checked(result),
+            #[cfg(feature = "dev_tools")]
+            Message::L10nReloaded => self.reload_localisation(),
             Message::UnsavedData(_, _) => tasks = unsaved_data::try_update(self, message)?,
+            Message::Information(_, _) => tasks = information::try_update(self, message)?,
+            Message::FatalError(_, _) => tasks = fatal_error::try_update(self, message)?,
+            Message::RecoverableError(_, _) => {
+                tasks = recoverable_error::try_update(self, message)?
+            }
+            Message::CommandPalette(_, _) => {
+                tasks = command_palette::try_update(self, message)?
+            }
+            Message::Retry(parent, retry) => match self.try_update(*retry.clone()) {
+                Ok(value) => tasks = value,
+                Err(error) => tasks = recoverable_error::display(self, parent, error, *retry)?,
+            },
+            Message::UnsavedDataBatch(_, _) => {
+                tasks = unsaved_data_batch::try_update(self, message)?
+            }
+            Message::OpenFileDialog(id, read_only) => {
+                tasks = Task::perform(
+                    AsyncFileDialog::new().pick_file(),
+                    move |handle| {
+                        let path = handle.map(|handle| handle.path().to_path_buf());
+                        Message::FileOpened(id, path, read_only)
+                    },
+                )
+            }
+            Message::SaveFileDialog(id) => {
+                let mut dialog = AsyncFileDialog::new();
+                if let Some(state) = self.manager.state(&id) {
+                    if let Some(actual) = state.as_any().downcast_ref::<main::State>() {
+                        dialog = dialog.set_file_name(actual.name());
+                    }
+                }
+                tasks = Task::perform(
+                    dialog.save_file(),
+                    move |handle| {
+                        Message::FileSaved(id, handle.map(|handle| handle.path().to_path_buf()))
+                    },
+                )
+            }
+            Message::ExportSettings(_, path) => {
+                if let Some(path) = path {
+                    match preferences::try_export(self, path) {
+                        Ok(value) => tasks = value,
+                        Err(error) => self.push_toast(&error, ToastLevel::Error),
+                    }
+                }
+            }
+            Message::RestoreDefaults(id) => tasks = preferences::try_restore_defaults(self, id)?,
+            Message::ImportSettings(id, path) => {
+                // A newer settings version is rejected by the migrations.
+                if let Some(path) = path {
+                    match preferences::try_import(self, id, path) {
+                        Ok(value) => tasks = value,
+                        Err(error) => self.push_toast(&error, ToastLevel::Error),
+                    }
+                }
+            }
+            Message::FileOpened(_, path, read_only) => {
+                // A cancelled dialogue changes nothing.
+                if let Some(path) = path {
+                    tasks = self.open_thread(
+                        WindowType::Main,
+                        Some(path.to_string_lossy().to_string()),
+                        read_only,
+                    )?
+                }
+            }
+            Message::FileSaved(id, path) => {
+                if let Some(path) = path {
+                    match main::try_save_as(self, id, path.clone()) {
+                        Ok(value) => tasks = value,
+                        // The window may have been closed while the dialogue was displayed.
+                        Err(error) if !self.manager.contains(&id) => {
+                            self.push_toast(&error, ToastLevel::Error)
+                        }
+                        Err(error) => {
+                            let retry = Message::FileSaved(id, Some(path));
+                            tasks = recoverable_error::display(self, id, error, retry)?
+                        }
+                    }
+                }
+            }
+            Message::DismissToast(toast_id) => self.toasts.retain(|toast| toast.id != toast_id),
 
             // Application window specific messages
             Message::Default(_, _) => tasks = default::try_update(self, message)?,
             Message::Main(_, _) => tasks = main::try_update(self, message)?,
             Message::Preferences(_, _) => tasks = preferences::try_update(self, message)?,
+            Message::LogViewer(_, _) => tasks = log_viewer::try_update(self, message)?,
         }
         Ok(tasks)
     }
@@ -293,11 +945,25 @@ impl State {
     // ----- Window geometry methods
     //
 
+    /// Lock or unlock the resizing of the window, such as during a long operation. While locked,
+    /// the window is resized back to its size at the time of locking.
+    pub fn set_resizable(
+        &mut self,
+        id: window::Id,
+        resizable: bool,
+    ) -> Result<Task<Message>, ApplicationError> {
+        Ok(self.manager.set_resizable(id, resizable)?)
+    }
+
     /// Window was resized.
+    ///
+    /// While the window is maximised the floating size is left untouched, so that the window
+    /// can be restored to it.
     fn resized(
         &mut self,
         id: &window::Id,
         size: Size,
+        maximized: bool,
     ) -> Result<Task<Message>, CoreError> {
         let Some(state) = self.manager.state(&id) else {
             return Err(CoreError::WindowIdNotFound(
@@ -311,15 +977,22 @@ impl State {
                 "session.windows".to_string(),
             ));
         };
-        data.size = (size.width, size.height);
+        data.maximized = maximized;
+        if !maximized {
+            data.size = (size.width, size.height);
+        }
         Ok(Task::none())
     }
 
     /// Window was moved.
+    ///
+    /// While the window is maximised the floating position is left untouched, so that the window
+    /// can be restored to it.
     fn moved(
         &mut self,
         id: &window::Id,
         position: Point,
+        maximized: bool,
     ) -> Result<Task<Message>, CoreError> {
         let Some(state) = self.manager.state(&id) else {
             return Err(CoreError::WindowIdNotFound(
@@ -333,7 +1006,10 @@ impl State {
                 "session.windows".to_string(),
             ));
         };
-        data.position = Some((position.x, position.y));
+        data.maximized = maximized;
+        if !maximized {
+            data.position = Some((position.x, position.y));
+        }
         Ok(Task::none())
     }
 
@@ -351,11 +1027,129 @@ impl State {
     pub fn view(&self, id: window::Id) -> Element<Message> {
         let state = self.manager.state(&id).expect(format!("view(): Failed to get state for window id {:?}", id).as_str());
         let content = state.view(id, &self.localisation, &self.string_cache);
-        event_control::Container::new(content, self.manager.is_enabled(&id).unwrap())
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(0)
-            .into()
+        let content: Element<Message> =
+            event_control::Container::new(content, self.manager.is_enabled(&id).unwrap())
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(0)
+                .disabled_cursor(mouse::Interaction::NotAllowed)
+                .border_scale(self.session.settings.theme.border_scale())
+                .into();
+
+        // Toasts are displayed on the focused window, or on all windows until a window has focus.
+        if self.toasts.is_empty() || self.focused.is_some_and(|focused| focused != id) {
+            return content;
+        }
+        toast::view(content, &self.toasts)
+    }
+
+    /// Display a toast for a recoverable error, which is removed after `TOAST_TTL`, or when
+    /// dismissed. Only the last `TOAST_LIMIT` toasts are kept.
+    pub fn push_toast(&mut self, error: &ApplicationError, level: ToastLevel) {
+        warn!("Toast: {}", error);
+        let message = match self.localisation.format_error_with_defaults(error) {
+            Ok(value) => value.0,
+            Err(_) => error.to_string().into(),
+        };
+        self.push_toast_message(message, level);
+    }
+
+    /// Display a toast of an already localised message.
+    pub fn push_toast_message(&mut self, message: RefCount<String>, level: ToastLevel) {
+        self.toasts.push_back(Toast {
+            id: self.next_toast,
+            level,
+            message,
+        });
+        self.next_toast += 1;
+        while self.toasts.len() > constants::TOAST_LIMIT {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Display the result of checking for updates as a toast. A failure is not fatal, as the
+    /// application is still usable.
+    #[cfg(feature = "updates")]
+    fn update_checked(&mut self, result: Result<UpdateStatus, String>) {
+        let localised = match result {
+            Err(error) => {
+                self.push_toast(&CoreError::UpdateCheckFailed(error).into(), ToastLevel::Warning);
+                return;
+            }
+            Ok(UpdateStatus::Available(version)) => {
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("version".to_string(), PlaceholderValue::String(version));
+                self.localisation
+                    .format_with_defaults("application", "update_available", &values)
+            }
+            Ok(UpdateStatus::UpToDate) => {
+                self.localisation.literal_with_defaults("application", "up_to_date")
+            }
+        };
+        match localised {
+            Ok(value) => self.push_toast_message(value.0, ToastLevel::Information),
+            Err(error) => self.push_toast(&error.into(), ToastLevel::Warning),
+        }
+    }
+
+    /// Reload the localisation database, and update the localised strings of all the windows. A
+    /// failure, such as a malformed database while being edited, is only logged, keeping the
+    /// previous strings.
+    #[cfg(feature = "dev_tools")]
+    fn reload_localisation(&mut self) {
+        info!("Reloading the localisation database.");
+        if let Err(error) = self
+            .localisation
+            .try_reload_provider(&self.environment.application_path)
+        {
+            error!("Failed to reload the localisation database, keeping the previous strings: {}", error);
+            return;
+        }
+        if let Err(error) = self.string_cache.try_update(&self.localisation) {
+            error!("Failed to update the localised strings: {}", error);
+            return;
+        }
+        let localisation = &self.localisation;
+        if let Err(error) = self
+            .manager
+            .for_each_state_mut(|_, state| state.try_localise(localisation))
+        {
+            error!("Failed to update the localised window strings: {}", error);
+        }
+    }
+
+    /// Raise a desktop notification, where the summary and body are already localised. Without the
+    /// `notifications` feature, or when the platform's notification service is unavailable, only a
+    /// warning is logged.
+    pub fn notify(&self, summary: &str, body: &str) {
+        #[cfg(feature = "notifications")]
+        {
+            let result = notify_rust::Notification::new()
+                .appname(constants::APPLICATION_NAME)
+                .summary(summary)
+                .body(body)
+                .show();
+            if let Err(error) = result {
+                warn!("Failed to raise the notification ‘{}’: {}", summary, error);
+            }
+        }
+        #[cfg(not(feature = "notifications"))]
+        warn!("Notifications are not enabled, ‘{}’: {}", summary, body);
+    }
+
+    /// The scale of the windows, being the `ui_scale` setting, which scales the text sizes and the
+    /// paddings.
+    pub fn scale_factor(&self, _id: window::Id) -> f64 {
+        clamp_ui_scale(self.session.settings.ui_scale) as f64
+    }
+
+    /// The theme of the windows, as set in the preferences.
+    pub fn theme(&self, _id: window::Id) -> Theme {
+        let settings = &self.session.settings;
+        with_accent(
+            settings.theme.to_theme(self.environment.system_mode),
+            settings.accent,
+        )
     }
 
     //
@@ -363,17 +1157,24 @@ impl State {
     //
 
     /// Opens a new main window thread, for the specified window type.
+    ///
+    /// The optional path is the data to be opened in the main window, which is read-only when
+    /// `read_only` is `true`.
     pub fn open_thread(
         &mut self,
         window_type: WindowType,
+        path: Option<String>,
+        read_only: bool,
     ) -> Result<Task<Message>, ApplicationError> {
         debug!("Opening. Threads: {:?}", self.manager.thread_count());
-        let tasks = if self.manager.thread_count() > 1 {
+        // Without any thread, such as with the `LastWindowPolicy::Hide` policy, a new Main thread
+        // is created.
+        let tasks = if self.manager.thread_count() != 1 {
             match window_type {
                 // If there are additional main window types, add them.
                 WindowType::Main => {
                     trace!("open_thread: try to display Main");
-                    let (tasks, _) = main::display(self)?;
+                    let (tasks, _) = main::display(self, path, read_only)?;
                     tasks
                 }
                 _ => {
@@ -385,7 +1186,7 @@ impl State {
             // Only 1 thread, which means current window can be Default window,
             // which would need to be closed on successful opening of a main
             // window.
-            let id = self.manager.thread_list().pop().unwrap();
+            let id = self.manager.thread_list()[0];
             let Some(state) = self.manager.state(&id) else {
                 return Err(ApplicationError::Core(CoreError::WindowIdNotFound(
                     id,
@@ -399,7 +1200,7 @@ impl State {
                     	// If there are additional main window types, add them.
                         WindowType::Main => {
                             trace!("open_thread: try to display Main");
-                            let (mut tasks, success) = main::display(self)?;
+                            let (mut tasks, success) = main::display(self, path, read_only)?;
                             if success {
                                 // Have new Main window, close the Default window
                                 tasks = tasks.chain(self.manager.close_thread(id)?);
@@ -414,7 +1215,7 @@ impl State {
                     match window_type {
 	                    // If there are additional main window types, add them.
                         WindowType::Main => {
-                            let (tasks, _) = main::display(self)?;
+                            let (tasks, _) = main::display(self, path, read_only)?;
                             tasks
                         }
                         _ => Task::none(),
@@ -462,16 +1263,168 @@ impl State {
     pub fn exit(
         &mut self,
     ) -> Task<Message> {
-        let _ = self.session.save();
+        self.record_windows();
+        let _ = self.session.save(&self.environment.config_file);
+        #[cfg(feature = "tray")]
+        self.tray.take(); // Dropping the icon removes it from the tray.
+        #[cfg(feature = "single_instance")]
+        single_instance::release();
         iced::exit()
     }
 
+    /// Indicates whether the tray icon exists, for showing the windows and quitting while no
+    /// window is displayed.
+    fn has_tray(&self) -> bool {
+        #[cfg(feature = "tray")]
+        return self.tray.is_some();
+        #[cfg(not(feature = "tray"))]
+        false
+    }
+
+    /// Indicates whether closing the Default window hides it to the tray, rather than exiting.
+    fn hides_to_tray(&self) -> bool {
+        #[cfg(feature = "tray")]
+        return self.session.settings.minimize_to_tray && self.tray.is_some();
+        #[cfg(not(feature = "tray"))]
+        false
+    }
+
+    /// Record the open window threads in the session, to be reopened on startup when the
+    /// `restore_session` setting is enabled. The Default window is not recorded, as it is
+    /// displayed when no window is restored.
+    fn record_windows(&mut self) {
+        let mut records = Vec::<OpenWindowRecord>::new();
+        for id in self.manager.thread_list() {
+            let Some(state) = self.manager.state(&id) else {
+                continue;
+            };
+            // A record for each tab, which are reopened as tabs in the `DocumentMode::Tabs` mode.
+            if let Some(actual) = state.as_any().downcast_ref::<main::State>() {
+                for path in actual.paths() {
+                    records.push(OpenWindowRecord {
+                        window_type: WindowType::Main,
+                        path: Some(path),
+                    });
+                }
+            }
+        }
+        self.session.open_windows = records;
+    }
+
+    /// Reopen the window threads recorded in the session. Records having a path that no longer
+    /// exists are skipped.
+    fn try_restore_windows(&mut self) -> Result<Task<Message>, ApplicationError> {
+        let mut tasks = Task::none();
+        for record in self.session.open_windows.clone() {
+            if let Some(path) = record.path.as_ref() {
+                if !PathBuf::from(path).exists() {
+                    warn!(
+                        "Not restoring the {:?} window, as ‘{}’ no longer exists.",
+                        record.window_type,
+                        path
+                    );
+                    continue;
+                }
+            }
+            match record.window_type {
+                WindowType::Main => {
+                    debug!("Restoring Main window for {:?}.", record.path);
+                    let (task, _) = main::display(self, record.path, false)?;
+                    tasks = tasks.chain(task);
+                }
+                _ => warn!("Restoring the {:?} window is not supported.", record.window_type),
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// Save the unsaved data of the main windows.
+    ///
+    /// Main windows displaying the UnsavedData dialogue, or listed in the UnsavedDataBatch
+    /// dialogue, are skipped, as the user is deciding what to do with the unsaved data.
+    pub fn autosave(&mut self) -> Result<(), ApplicationError> {
+        let mut deciding = self
+            .manager
+            .ids_of_type(WindowType::UnsavedData)
+            .iter()
+            .filter_map(|id| self.manager.parent(id))
+            .collect::<Vec<window::Id>>();
+        for id in self.manager.ids_of_type(WindowType::UnsavedDataBatch) {
+            if let Some(actual) = self
+                .manager
+                .state(&id)
+                .and_then(|state| state.as_any().downcast_ref::<unsaved_data_batch::State>())
+            {
+                deciding.extend(actual.windows());
+            }
+        }
+        for id in self.manager.thread_list() {
+            if deciding.contains(&id) {
+                continue;
+            }
+            let Some(state) = self.manager.state_mut(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            if let Some(actual) = state.as_any_mut().downcast_mut::<main::State>() {
+                if actual.is_unsaved() {
+                    debug!("Autosaving {}.", actual.name());
+                    actual.try_save()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Indicates whether any main window has unsaved data.
+    pub fn has_unsaved(&self) -> bool {
+        self.manager.thread_list().iter().any(|id| {
+            self.manager.state(id).is_some_and(|state| {
+                state
+                    .as_any()
+                    .downcast_ref::<main::State>()
+                    .is_some_and(|actual| actual.is_unsaved())
+            })
+        })
+    }
+
+    /// Save the unsaved data of all the main windows, such as before exiting.
+    pub fn save_all(&mut self) -> Result<(), ApplicationError> {
+        for id in self.manager.thread_list() {
+            let Some(state) = self.manager.state_mut(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            if let Some(actual) = state.as_any_mut().downcast_mut::<main::State>() {
+                if actual.is_unsaved() {
+                    debug!("Saving {}.", actual.name());
+                    actual.try_save()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Attempt to close all threads.
     ///
-    /// Any window that has unsaved data will produce a dialogue for that window.
+    /// When multiple windows have unsaved data, a single dialogue lists all those windows, and no
+    /// thread is closed until the user has decided. Otherwise the window that has unsaved data
+    /// will produce a dialogue for that window.
     pub fn close_all(
         &mut self,
     ) -> Result<Task<Message>, ApplicationError> {
+        let mut unsaved = Vec::<(window::Id, String)>::new();
+        for id in self.manager.thread_list() {
+            let Some(state) = self.manager.state(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            if let Some(actual) = state.as_any().downcast_ref::<main::State>() {
+                if actual.is_unsaved() {
+                    unsaved.push((id, actual.name().to_string()));
+                }
+            }
+        }
+        if unsaved.len() > 1 {
+            return unsaved_data_batch::display(self, unsaved);
+        }
         let mut tasks = Task::none();
         for id in self.manager.thread_list() {
             let Some(state) = self.manager.state(&id) else {
@@ -485,3 +1438,559 @@ impl State {
         Ok(tasks)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::{accelerator::AcceleratorOverride, session::{Settings, WindowData}},
+        core::{
+            testing::TempDir,
+            traits::{AnyWindowTrait, NavigableTrait, WindowTrait},
+        },
+        window::default::{menu_bar, Message as DefaultMessage},
+    };
+    use std::any::Any;
+
+    /// Update the state with the message, discarding the returned tasks, which require the runtime.
+    fn update(state: &mut State, message: Message) {
+        let _ = state.try_update(message).unwrap();
+    }
+
+    /// The initialised state, with the Default window displayed.
+    fn initialised() -> (State, window::Id) {
+        let mut state = State::new_for_test();
+        update(&mut state, Message::Initialise);
+        let id = state.manager.first_of_type(WindowType::Default).unwrap();
+        (state, id)
+    }
+
+    /// Select the menu item of the Default window.
+    fn menu(state: &mut State, id: window::Id, message: menu_bar::Message) {
+        update(state, Message::Default(id, DefaultMessage::MenuBar(message)));
+    }
+
+    #[test]
+    fn initialise_displays_default_window() {
+        let mut state = State::new_for_test();
+        assert_eq!(state.manager.thread_count(), 0);
+        update(&mut state, Message::Initialise);
+        assert_eq!(state.manager.thread_count(), 1);
+        assert!(state.manager.first_of_type(WindowType::Default).is_some());
+    }
+
+    #[test]
+    fn preferences_opens_from_default_window() {
+        let (mut state, id) = initialised();
+        menu(&mut state, id, menu_bar::Message::Preferences);
+
+        // The Preferences window belongs to the thread of the Default window.
+        assert_eq!(state.manager.thread_count(), 1);
+        let preferences = state.manager.first_of_type(WindowType::Preferences).unwrap();
+        assert_eq!(state.manager.parent(&preferences), Some(id));
+        assert_eq!(state.manager.is_enabled(&id), Some(false));
+    }
+
+    #[test]
+    fn new_document_is_not_recent() {
+        let (mut state, id) = initialised();
+        menu(&mut state, id, menu_bar::Message::New(WindowType::Main));
+        assert_eq!(state.manager.count_by_type(WindowType::Main), 1);
+        assert!(state.session.settings.recent.is_empty());
+    }
+
+    #[test]
+    fn preferences_routes_tab_messages() {
+        let (mut state, id) = initialised();
+        menu(&mut state, id, menu_bar::Message::Preferences);
+        let preferences_id = state.manager.first_of_type(WindowType::Preferences).unwrap();
+        update(
+            &mut state,
+            preferences::tab_message(
+                preferences_id,
+                preferences::backup::TAB_ID,
+                preferences::backup::Message::IncludeWindows(true),
+            ),
+        );
+        update(
+            &mut state,
+            Message::Preferences(
+                preferences_id,
+                preferences::Message::TabSelected(preferences::theme::TAB_ID),
+            ),
+        );
+        let preferences_state = state.manager.state(&preferences_id).unwrap();
+        let actual = preferences_state.as_any().downcast_ref::<preferences::State>().unwrap();
+        assert!(actual.include_windows());
+        assert_eq!(state.session.settings.last_preferences_tab.as_deref(), Some("theme"));
+    }
+
+    #[test]
+    fn notify_message_of_localised_strings() {
+        let (mut state, _id) = initialised();
+        let localise = |identifier: &str| {
+            state
+                .localisation
+                .literal_with_defaults("application", identifier)
+                .unwrap()
+                .0
+                .to_string()
+        };
+        let summary = localise("export_settings");
+        let body = localise("import_settings");
+        assert!(!summary.is_empty());
+        assert!(!body.is_empty());
+
+        // Without the platform's notification service, only a warning is logged.
+        update(&mut state, Message::Notify { summary, body });
+    }
+
+    /// Restore the default settings from the Preferences window, optionally including the window
+    /// geometries.
+    fn restore_defaults(state: &mut State, id: window::Id, include_windows: bool) {
+        menu(state, id, menu_bar::Message::Preferences);
+        let preferences_id = state.manager.first_of_type(WindowType::Preferences).unwrap();
+        update(
+            state,
+            preferences::tab_message(
+                preferences_id,
+                preferences::backup::TAB_ID,
+                preferences::backup::Message::IncludeWindows(include_windows),
+            ),
+        );
+        update(
+            state,
+            preferences::tab_message(
+                preferences_id,
+                preferences::backup::TAB_ID,
+                preferences::backup::Message::RestoreDefaults,
+            ),
+        );
+        let confirm_id = state.manager.first_of_type(WindowType::RestoreDefaults).unwrap();
+        update(state, Message::RestoreDefaults(confirm_id));
+    }
+
+    /// Change the settings from the defaults, and store a window geometry.
+    fn change_settings(state: &mut State) {
+        state.session.settings.ui_scale = 1.5;
+        state.session.push_recent("document");
+        state.session.windows.insert(
+            WindowType::Main,
+            WindowData {
+                size: (640f32, 480f32),
+                position: Some((10f32, 20f32)),
+                maximized: false,
+            },
+        );
+    }
+
+    fn is_default(settings: &Settings) -> bool {
+        ron::to_string(settings).unwrap() == ron::to_string(&Settings::default()).unwrap()
+    }
+
+    #[test]
+    fn restore_defaults_keeps_window_geometries() {
+        let (mut state, id) = initialised();
+        change_settings(&mut state);
+        assert!(!is_default(&state.session.settings));
+        restore_defaults(&mut state, id, false);
+        assert!(is_default(&state.session.settings));
+        assert!(state.session.windows.contains_key(&WindowType::Main));
+    }
+
+    #[test]
+    fn restore_defaults_including_window_geometries() {
+        let (mut state, id) = initialised();
+        change_settings(&mut state);
+        restore_defaults(&mut state, id, true);
+        assert!(is_default(&state.session.settings));
+        assert!(!state.session.windows.contains_key(&WindowType::Main));
+    }
+
+    /// Close the last window thread, being the Default window, with the policy. Returns the state,
+    /// and whether the session was saved to the test's own configuration file.
+    fn close_last_window(policy: LastWindowPolicy) -> (State, bool) {
+        let (mut state, id) = initialised();
+        let directory = TempDir::new(&format!("policy_{:?}", policy));
+        let config_file = directory.path().join("session.ron");
+        state.environment.config_file = config_file.clone();
+        state.on_last_window_closed = policy;
+        update(&mut state, Message::WindowClosed(id));
+        update(&mut state, Message::ThreadClosed(0));
+        let saved = config_file.is_file();
+        (state, saved)
+    }
+
+    #[test]
+    fn last_window_closed_spawns_default() {
+        assert_eq!(State::new_for_test().on_last_window_closed, LastWindowPolicy::SpawnDefault);
+        let (state, saved) = close_last_window(LastWindowPolicy::SpawnDefault);
+        assert_eq!(state.manager.thread_count(), 1);
+        assert!(state.manager.first_of_type(WindowType::Default).is_some());
+        assert!(!saved);
+    }
+
+    #[test]
+    fn last_window_closed_exits() {
+        let (state, saved) = close_last_window(LastWindowPolicy::Exit);
+        assert_eq!(state.manager.thread_count(), 0);
+        assert!(saved);
+    }
+
+    #[test]
+    fn last_window_closed_hides() {
+        let (mut state, saved) = close_last_window(LastWindowPolicy::Hide);
+        assert_eq!(state.manager.thread_count(), 0);
+
+        // Without the tray icon, hiding is exiting.
+        assert_eq!(saved, !state.has_tray());
+
+        // Opening a file while no window is displayed creates a Main thread.
+        let _ = state
+            .open_thread(WindowType::Main, Some("hidden.txt".to_string()), false)
+            .unwrap();
+        assert_eq!(state.manager.thread_count(), 1);
+        assert!(main::find_path(&state, "hidden.txt").is_some());
+    }
+
+    #[test]
+    fn resizable_flag_is_recorded() {
+        let (mut state, id) = initialised();
+        let size = Size::new(640f32, 480f32);
+        assert_eq!(state.manager.locked_size(&id), None);
+
+        // Locking queries the window's size, which is then recorded.
+        let _ = state.set_resizable(id, false).unwrap();
+        update(&mut state, Message::SizeLocked(id, size));
+        assert_eq!(state.manager.locked_size(&id), Some(size));
+        update(&mut state, Message::Resized(id, Size::new(100f32, 100f32)));
+        assert_eq!(state.manager.locked_size(&id), Some(size));
+
+        // Once unlocked, the resized window's size is stored again.
+        let _ = state.set_resizable(id, true).unwrap();
+        assert_eq!(state.manager.locked_size(&id), None);
+        update(&mut state, Message::ResizedMaximized(id, Size::new(700f32, 500f32), false));
+        assert_eq!(state.session.windows[&WindowType::Default].size, (700f32, 500f32));
+        assert!(state.set_resizable(window::Id::unique(), false).is_err());
+    }
+
+
+    #[test]
+    fn confirm_exit_routes_unsaved_data() {
+        let (mut state, id) = initialised();
+        let directory = TempDir::new("save_and_exit");
+        state.environment.config_file = directory.path().join("session.ron");
+        menu(&mut state, id, menu_bar::Message::New(WindowType::Main));
+        let main = state.manager.first_of_type(WindowType::Main).unwrap();
+        assert!(!state.has_unsaved());
+        update(&mut state, Message::Main(main, main::Message::Toggle));
+        assert!(state.has_unsaved());
+
+        // The dialogue offers saving, thus isn't reusable.
+        let _ = confirm_exit::display(&mut state, main).unwrap();
+        let dialogue = state.manager.first_of_type(WindowType::ConfirmExit).unwrap();
+        assert!(!state.manager.state(&dialogue).unwrap().is_reusable());
+
+        // Exiting without saving keeps the unsaved data, while saving before exiting saves it.
+        update(&mut state, Message::Exit);
+        assert!(state.has_unsaved());
+        update(&mut state, Message::SaveAndExit);
+        assert!(!state.has_unsaved());
+    }
+
+
+    /// A window state having a navigation history, counting the back and forward navigations,
+    /// and the gained and lost focus.
+    struct TestWindow {
+        navigated: (usize, usize),
+        focus: (usize, usize),
+    }
+
+    impl TestWindow {
+        fn new() -> Box<Self> {
+            Box::new(TestWindow {
+                navigated: (0, 0),
+                focus: (0, 0),
+            })
+        }
+    }
+
+    impl AnyWindowTrait for TestWindow {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    impl WindowTrait for TestWindow {
+        fn title<'a>(&'a self, string_cache: &'a StringCache) -> &'a String {
+            string_cache.title(&StringGroup::Default)
+        }
+
+        fn view<'a>(
+            &'a self,
+            _id: window::Id,
+            _localisation: &Localisation,
+            _string_cache: &'a StringCache,
+        ) -> Element<'a, Message> {
+            iced::widget::text("").into()
+        }
+
+        fn window_type(&self) -> WindowType {
+            WindowType::Main
+        }
+
+        fn as_navigable(&mut self) -> Option<&mut dyn NavigableTrait> {
+            Some(self)
+        }
+
+        fn on_focus(&mut self) -> Result<(), ApplicationError> {
+            self.focus.0 += 1;
+            Ok(())
+        }
+
+        fn on_blur(&mut self) {
+            self.focus.1 += 1;
+        }
+    }
+
+    impl NavigableTrait for TestWindow {
+        fn navigate_back(&mut self) {
+            self.navigated.0 += 1;
+        }
+
+        fn navigate_forward(&mut self) {
+            self.navigated.1 += 1;
+        }
+    }
+
+    #[test]
+    fn navigation_is_dispatched_to_focused_navigable_window() {
+        let (mut state, default) = initialised();
+        let _ = state
+            .manager
+            .try_create_thread(&mut state.session, TestWindow::new())
+            .unwrap();
+        let id = state.manager.first_of_type(WindowType::Main).unwrap();
+        let navigated = |state: &State| {
+            let state = state.manager.state(&id).unwrap();
+            state.as_any().downcast_ref::<TestWindow>().unwrap().navigated
+        };
+
+        // Ignored when the window isn't focused, and by windows without a navigation history.
+        update(&mut state, Message::NavigateBack(id));
+        assert_eq!(navigated(&state), (0, 0));
+        update(&mut state, Message::Focused(default));
+        update(&mut state, Message::NavigateBack(default));
+        update(&mut state, Message::NavigateBack(id));
+        assert_eq!(navigated(&state), (0, 0));
+
+        update(&mut state, Message::Focused(id));
+        update(&mut state, Message::NavigateBack(id));
+        update(&mut state, Message::NavigateForward(id));
+        update(&mut state, Message::NavigateForward(id));
+        assert_eq!(navigated(&state), (1, 2));
+
+        // Ignored when disabled by a child window.
+        let _ = confirm_exit::display(&mut state, id).unwrap();
+        assert_eq!(state.manager.is_enabled(&id), Some(false));
+        update(&mut state, Message::NavigateBack(id));
+        assert_eq!(navigated(&state), (1, 2));
+    }
+
+
+    #[test]
+    fn opening_same_path_twice_focuses() {
+        let (mut state, _) = initialised();
+        let path = "already_opened.txt".to_string();
+        let (_, opened) = main::display(&mut state, Some(path.clone()), false).unwrap();
+        assert!(opened);
+        let id = state.manager.first_of_type(WindowType::Main).unwrap();
+        assert!(state.toasts.is_empty());
+
+        // The opened window is focused instead, and the user is informed.
+        let (_, opened) = main::display(&mut state, Some(path.clone()), false).unwrap();
+        assert!(opened);
+        assert_eq!(state.manager.ids_of_type(WindowType::Main), vec![id]);
+        assert_eq!(state.toasts.len(), 1);
+        let toast = state.toasts.back().unwrap();
+        assert_eq!(toast.level, ToastLevel::Information);
+        assert!(toast.message.contains(path.as_str()), "{}", toast.message);
+    }
+
+
+    #[test]
+    fn read_only_main_closes_without_unsaved_dialogue() {
+        let (mut state, _) = initialised();
+        for (path, read_only) in [("read_only.txt", true), ("writable.txt", false)] {
+            let _ = main::display(&mut state, Some(path.to_string()), read_only).unwrap();
+            let id = main::find_path(&state, path).unwrap();
+            update(&mut state, Message::Main(id, main::Message::Toggle));
+            let _ = main::try_to_close(&mut state, id).unwrap();
+            assert_eq!(
+                state.manager.first_of_type(WindowType::UnsavedData).is_some(),
+                !read_only,
+                "{}",
+                path
+            );
+        }
+    }
+
+
+    #[test]
+    fn focus_messages_reach_their_window() {
+        let (mut state, _) = initialised();
+        let mut ids = Vec::<window::Id>::new();
+        for _ in 0..2 {
+            let _ = state.manager.try_create_thread(&mut state.session, TestWindow::new()).unwrap();
+            let opened = state.manager.ids_of_type(WindowType::Main);
+            ids.push(*opened.iter().find(|id| !ids.contains(id)).unwrap());
+        }
+        let focus = |state: &State, id: &window::Id| {
+            let state = state.manager.state(id).unwrap();
+            state.as_any().downcast_ref::<TestWindow>().unwrap().focus
+        };
+        update(&mut state, Message::Focused(ids[0]));
+        assert_eq!(state.focused(), Some(ids[0]));
+        assert_eq!((focus(&state, &ids[0]), focus(&state, &ids[1])), ((1, 0), (0, 0)));
+        update(&mut state, Message::Unfocused(ids[0]));
+        update(&mut state, Message::Focused(ids[1]));
+        assert_eq!(state.focused(), Some(ids[1]));
+        assert_eq!((focus(&state, &ids[0]), focus(&state, &ids[1])), ((1, 1), (1, 0)));
+
+        // The last focused window is kept when the focus leaves the application.
+        update(&mut state, Message::Unfocused(ids[1]));
+        assert_eq!(state.focused(), Some(ids[1]));
+        assert_eq!(focus(&state, &ids[1]), (1, 1));
+    }
+
+
+    #[test]
+    fn snapshots_of_visible_state() {
+        let (mut state, id) = initialised();
+        let _ = main::display(&mut state, Some("snapshot.txt".to_string()), false).unwrap();
+        let main = main::find_path(&state, "snapshot.txt").unwrap();
+        update(&mut state, Message::Main(main, main::Message::Toggle));
+        let snapshot = |state: &State, id| state.manager.debug_snapshot(&id, &state.string_cache);
+        let default = snapshot(&state, id).unwrap();
+        assert_eq!(default.window_type, WindowType::Default);
+        assert!(default.enabled && !default.is_unsaved);
+        let snapshot_main = snapshot(&state, main).unwrap();
+        assert_eq!(snapshot_main.window_type, WindowType::Main);
+        assert!(snapshot_main.enabled && snapshot_main.is_unsaved);
+        assert!(snapshot_main.title.starts_with(constants::UNSAVED_MARKER));
+
+        // The opened Preferences window disables the Main window.
+        let preferences = main::Message::MenuBar(main::menu_bar::Message::Preferences);
+        update(&mut state, Message::Main(main, preferences));
+        assert!(!snapshot(&state, main).unwrap().enabled);
+        let id = state.manager.first_of_type(WindowType::Preferences).unwrap();
+        let snapshot_preferences = snapshot(&state, id).unwrap();
+        assert!(snapshot_preferences.enabled && !snapshot_preferences.is_first_use);
+        let title = state.string_cache.title(&StringGroup::Preferences);
+        assert_eq!(&snapshot_preferences.title, title);
+        assert!(ron::to_string(&snapshot_preferences).is_ok());
+    }
+
+
+    #[test]
+    fn exit_accelerator_confirms_unsaved_data() {
+        let (mut state, _) = initialised();
+        let _ = main::display(&mut state, Some("exit.txt".to_string()), false).unwrap();
+        let main = main::find_path(&state, "exit.txt").unwrap();
+        update(&mut state, Message::Main(main, main::Message::Toggle));
+        update(&mut state, Message::Accelerator(main, Action::Exit));
+
+        // The confirm exit dialogue offering to save is displayed, instead of exiting.
+        let dialogue = state.manager.first_of_type(WindowType::ConfirmExit).unwrap();
+        assert!(!state.manager.state(&dialogue).unwrap().is_reusable());
+        assert!(state.has_unsaved());
+    }
+
+
+    #[test]
+    fn autosave_skips_windows_of_unsaved_data_batch() {
+        let (mut state, _) = initialised();
+        for path in ["batch1.txt", "batch2.txt"] {
+            let _ = main::display(&mut state, Some(path.to_string()), false).unwrap();
+            let main = main::find_path(&state, path).unwrap();
+            update(&mut state, Message::Main(main, main::Message::Toggle));
+        }
+        let _ = state.close_all().unwrap();
+        assert!(state.manager.first_of_type(WindowType::UnsavedDataBatch).is_some());
+
+        // The user is deciding, thus the unsaved data is left alone.
+        state.autosave().unwrap();
+        assert!(state.has_unsaved());
+    }
+
+
+    #[test]
+    fn monitors_detected_are_cached() {
+        let (mut state, default) = initialised();
+        let monitors = vec![Rectangle::new(Point::new(0f32, 0f32), Size::new(1920f32, 1080f32))];
+        update(&mut state, Message::MonitorsDetected(monitors.clone()));
+        assert_eq!(state.manager.monitors(), monitors.as_slice());
+
+        // Gaining focus detects the monitors off the UI thread, the cache is kept until then.
+        update(&mut state, Message::Focused(default));
+        assert_eq!(state.manager.monitors(), monitors.as_slice());
+    }
+
+
+    /// Unregister the Exit accelerator using an override of the settings.
+    fn override_exit(settings: &mut Settings) {
+        settings.accelerator_overrides = vec![AcceleratorOverride {
+            keys: "command+q".to_string(),
+            action: None,
+        }];
+    }
+
+    fn exit_accelerator(state: &State) -> Option<Action> {
+        state
+            .accelerators()
+            .find(&keyboard::Key::Character("q".into()), keyboard::Modifiers::COMMAND)
+    }
+
+    #[test]
+    fn restore_defaults_rebuilds_accelerators() {
+        let (mut state, id) = initialised();
+        override_exit(&mut state.session.settings);
+        let _ = state.apply_settings();
+        assert_eq!(exit_accelerator(&state), None);
+        restore_defaults(&mut state, id, false);
+        assert_eq!(exit_accelerator(&state), Some(Action::Exit));
+    }
+
+    #[test]
+    fn import_settings_rebuilds_accelerators() {
+        let (mut state, id) = initialised();
+        let directory = TempDir::new("import_accelerators");
+        let path = directory.path().join("session.ron");
+        let mut session = Session::default();
+        override_exit(&mut session.settings);
+        session.export_to(&path).unwrap();
+        menu(&mut state, id, menu_bar::Message::Preferences);
+        let preferences_id = state.manager.first_of_type(WindowType::Preferences).unwrap();
+        update(&mut state, Message::ImportSettings(preferences_id, Some(path)));
+        assert_eq!(exit_accelerator(&state), None);
+    }
+
+
+    #[test]
+    fn saving_closed_window_displays_toast() {
+        let (mut state, _) = initialised();
+        let directory = TempDir::new("save_closed");
+        let closed = window::Id::unique();
+        assert!(!state.manager.contains(&closed));
+        update(&mut state, Message::FileSaved(closed, Some(directory.path().join("closed.txt"))));
+
+        // Without the window, there is no parent for the RecoverableError dialogue.
+        assert!(state.manager.first_of_type(WindowType::RecoverableError).is_none());
+        assert_eq!(state.toasts.len(), 1);
+        assert_eq!(state.toasts.back().unwrap().level, ToastLevel::Error);
+    }
+}
@@ -2,14 +2,32 @@
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
 use clap::{Parser, builder::TypedValueParser as _};
-use crate::application::log::LogLevel;
+use crate::{
+    application::{
+        constants::{APPLICATION_NAME, GIT_COMMIT, VERSION},
+        environment::application_path,
+        log::LogLevel,
+        Session,
+        StringGroup,
+    },
+    core::localisation::Localisation,
+    localisation::try_strings,
+};
+use std::path::PathBuf;
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
 /// Specify the various command line options.
 #[derive(Parser, Debug, Clone)]
-#[command( author, version, about, long_about = None )]
+#[command( author, about, long_about = None, disable_version_flag = true )]
 pub struct Clap {
     /// To override the log levels that are stored in the session.
     #[arg(
@@ -23,9 +41,34 @@ pub struct Clap {
     )]
     pub log_level: Option<LogLevel>,
 
+    /// To also write the log to the specified file, overriding the file stored in the session.
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+
+    /// Use the specified session file instead of the one in the configuration directory.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Print the version, build and localisation details, then exit.
+    #[arg(short = 'V', long)]
+    pub version: bool,
+
+    /// Check that the strings of every string group resolve for every available language, then
+    /// exit. Exits with a failure code if any string group fails.
+    #[arg(long)]
+    pub check_localisation: bool,
+
+    /// Reload the localisation database when the files of the `l10n` directory change.
+    #[cfg(feature = "dev_tools")]
+    #[arg(long)]
+    pub watch_l10n: bool,
+
     /// Use the application defaults. Reset session data.
     #[arg(short, long)]
     pub defaults: bool,
+
+    /// The path to open in a main window, when forwarded to the running instance.
+    pub path: Option<String>,
     
     /*
     /// Name of the person to greet
@@ -37,3 +80,73 @@ pub struct Clap {
     count: u8,
     */
 }
+
+/// Print the version details for the `--version` option. The localisation details are skipped
+/// with a warning, if the localisation database can't be opened.
+pub fn print_version() {
+    println!("{} {} (commit {})", APPLICATION_NAME, VERSION, GIT_COMMIT);
+    let localisation = match application_path().and_then(|path| {
+        Localisation::try_new_with_path(&path, &Session::default().settings.ui.language)
+    }) {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("Warning: failed to open the localisation database: {}", error);
+            return;
+        }
+    };
+    match localisation.repository_details() {
+        Ok(details) => {
+            println!("Localisation:");
+            println!("  Components: {}", details.components.join(", "));
+            println!("  Strings: {}", details.total_strings);
+            if let Some(default) = &details.default {
+                println!("  Default language: {}", default.as_str());
+            }
+            if !details.contributors.is_empty() {
+                println!("  Contributors: {}", details.contributors.join(", "));
+            }
+        }
+        Err(error) => eprintln!("Warning: failed to read the localisation details: {}", error),
+    }
+    let languages = localisation
+        .language_completion()
+        .iter()
+        .map(|(tag, _)| tag.as_str().to_string())
+        .collect::<Vec<String>>();
+    println!("Supported languages: {}", languages.join(", "));
+}
+
+/// Check the localisation for the `--check-localisation` option, by constructing the strings of
+/// every group in `StringGroup::ALL` for every available language. Each error is printed, and
+/// `false` is returned if there were any errors.
+pub fn check_localisation() -> bool {
+    let mut localisation = match application_path().and_then(|path| {
+        Localisation::try_new_with_path(&path, &Session::default().settings.ui.language)
+    }) {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("Failed to open the localisation database: {}", error);
+            return false;
+        }
+    };
+    let mut failures = 0usize;
+    for (tag, _) in localisation.language_completion() {
+        if let Err(error) = localisation.change_default_language(RefCount::clone(&tag)) {
+            eprintln!("{}: {}", tag.as_str(), error);
+            failures += 1;
+            continue;
+        }
+        for string_group in StringGroup::all().iter() {
+            if let Err(error) = try_strings(string_group, &localisation) {
+                eprintln!("{} {}: {}", tag.as_str(), string_group.as_str(), error);
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        eprintln!("Localisation check failed with {} errors.", failures);
+        return false;
+    }
+    println!("Localisation check passed.");
+    true
+}
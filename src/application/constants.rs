@@ -1,8 +1,10 @@
 // This file is part of `iced_af` crate. For the terms of use, please see the file
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
+use crate::application::LastWindowPolicy;
 use log::LevelFilter;
 use phf::phf_map;
+use std::time::Duration;
 
 //
 //
@@ -13,6 +15,7 @@ use phf::phf_map;
 // Derived from the build environment:
 pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_COMMIT: &str = env!("GIT_COMMIT"); // Provided by `build.rs`.
 
 //
 //
@@ -26,9 +29,38 @@ pub const APPLICATION_NAME: &str = "Iced Application Framework";
 pub const APPLICATION_NAME_SHORT: &str = "Iced AF";
 pub const APPLICATION_ABBREVIATION: &str = "iced_af";
 pub const AUTHORS: &str = env!("CARGO_PKG_AUTHORS"); // Change this if more detailed is required.
+pub const UPDATE_MANIFEST_URL: &str = "https://example.com/iced_af/release.json"; // Change to the JSON release manifest, `{ "version": "x.y.z" }`, for the `updates` feature.
 
 // Default settings values
+pub const SETTINGS_VERSION: u32 = 1; // Increment when adding a migration to `session.rs`.
 pub const DEFAULT_LOG_LEVEL_FILTER: LevelFilter = LevelFilter::Error; // This is the default log level of entire application.
+pub const LOG_FILE_SIZE_LIMIT: u64 = 1_048_576; // Size in bytes before the log file is rolled over.
+pub const LOG_FILE_COUNT: u32 = 5; // Number of rolled over log files to keep.
+pub const LOG_BUFFER_CAPACITY: usize = 1000; // Number of log records kept for the Log Viewer window.
+pub const DEFAULT_LOG_FORMAT: &str = "{d(%Y-%m-%d %H:%M:%S)} {l:<5} {t} - {m}{n}"; // `log4rs` pattern of the console and the log file.
+pub const TOAST_TTL: Duration = Duration::from_secs(5); // Duration a toast is displayed.
+pub const TOAST_LIMIT: usize = 5; // Maximum number of toasts displayed.
+pub const RECENT_LIMIT: usize = 10; // Maximum number of entries in the recent list.
+pub const UNDO_DEPTH: usize = 100; // Maximum number of changes kept in the undo stack of a window.
+pub const UNSAVED_MARKER: &str = "• "; // Prepended to the title of a window with unsaved data.
+pub const CASCADE_OFFSET: f32 = 30.0; // Horizontal and vertical offset of cascaded windows.
+pub const UI_SCALE_MIN: f32 = 0.5; // Smallest scale of the user interface.
+pub const UI_SCALE_MAX: f32 = 3.0; // Largest scale of the user interface.
+pub const UI_SCALE_STEP: f32 = 0.1; // Change of the scale for each zoom in or out.
+pub const HIGH_CONTRAST_BORDER_SCALE: f32 = 2.0; // Border width multiplier of the high contrast theme.
+pub const REUSABLE_LIMIT: usize = 4; // Maximum number of cached reusable states, including Default.
+pub const DEFAULT_ACCENT: [f32; 3] = [0x5E as f32 / 255.0, 0x7C as f32 / 255.0, 0xE2 as f32 / 255.0]; // The primary colour of `iced`'s light and dark themes.
+pub const ACCENT_PRESETS: [[f32; 3]; 6] = [ // sRGB presets displayed in the Preferences window.
+    DEFAULT_ACCENT,
+    [0.55, 0.36, 0.96], // Purple
+    [0.13, 0.60, 0.65], // Teal
+    [0.18, 0.62, 0.37], // Green
+    [0.93, 0.55, 0.16], // Orange
+    [0.85, 0.26, 0.28], // Red
+];
+pub const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(5); // Interval for detecting system theme changes.
+pub const L10N_RELOAD_DELAY: Duration = Duration::from_millis(500); // Waiting for the l10n writes to settle.
+pub const LAST_WINDOW_POLICY: LastWindowPolicy = LastWindowPolicy::SpawnDefault; // Initial `State::on_last_window_closed`.
 
 // Tabs constants
 pub const TAB_HEADER_SIZE: u16 = 32;
@@ -38,7 +70,10 @@ pub const TAB_PADDING: u16 = 16;
 /// thus `&str` are used instead. Ensure the `&str` matches the
 /// `WindowType::as_str()`.
 ///
-/// Every `WindowType` variant, there must an entry present.
+/// Every `WindowType` variant, there must an entry present, which is validated by
+/// `Manager::try_new()` on startup.
+/// The `max_instances` limits the number of opened windows of the window type, see
+/// `Manager::try_create_thread()` and `Manager::try_create_window()`.
 /// Add additional windows to Main windows and Application windows below.
 pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
     // Core windows
@@ -49,6 +84,7 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
     },
     "ConfirmExit" => WindowDefaultsData {
         size: (300f32, 120f32),
@@ -57,6 +93,7 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
     },
     "FatalError" => WindowDefaultsData {
         size: (500f32, 200f32),
@@ -65,6 +102,34 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
+    },
+    "RecoverableError" => WindowDefaultsData {
+        size: (500f32, 200f32),
+        size_max: (800f32, 400f32),
+        size_min: (300f32, 150f32),
+        resizable: true,
+        minimise: false,
+        maximise: false,
+        max_instances: None,
+    },
+    "CommandPalette" => WindowDefaultsData {
+        size: (400f32, 300f32),
+        size_max: (800f32, 600f32),
+        size_min: (300f32, 200f32),
+        resizable: true,
+        minimise: false,
+        maximise: false,
+        max_instances: None,
+    },
+    "LogViewer" => WindowDefaultsData {
+        size: (700f32, 400f32),
+        size_max: (1920f32, 1080f32),
+        size_min: (400f32, 250f32),
+        resizable: true,
+        minimise: true,
+        maximise: true,
+        max_instances: None,
     },
     "Information" => WindowDefaultsData {
         size: (600f32, 200f32),
@@ -73,6 +138,7 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
     },
     "Preferences" => WindowDefaultsData {
         size: (500f32, 300f32),
@@ -81,6 +147,16 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
+    },
+    "RestoreDefaults" => WindowDefaultsData {
+        size: (400f32, 120f32),
+        size_max: (400f32, 120f32),
+        size_min: (400f32, 120f32),
+        resizable: false,
+        minimise: false,
+        maximise: false,
+        max_instances: None,
     },
     "UnsavedData" => WindowDefaultsData {
         size: (450f32, 120f32),
@@ -89,6 +165,16 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: false,
         minimise: false,
         maximise: false,
+        max_instances: None,
+    },
+    "UnsavedDataBatch" => WindowDefaultsData {
+        size: (450f32, 250f32),
+        size_max: (700f32, 600f32),
+        size_min: (300f32, 200f32),
+        resizable: true,
+        minimise: false,
+        maximise: false,
+        max_instances: None,
     },
 
     // Main windows
@@ -99,6 +185,7 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: true,
         minimise: true,
         maximise: true,
+        max_instances: None,
     },
     "Main" => WindowDefaultsData {
         size: (500f32, 250f32),
@@ -107,6 +194,7 @@ pub static WINDOW_DEFAULT_DATA: phf::Map<&str, WindowDefaultsData> = phf_map! {
         resizable: true,
         minimise: true,
         maximise: true,
+        max_instances: None,
     },
 
     // Application windows
@@ -122,4 +210,5 @@ pub struct WindowDefaultsData {
     pub resizable: bool,
     pub minimise: bool,
     pub maximise: bool,
+    pub max_instances: Option<usize>, // The most opened windows of the type, `None` is unlimited.
 }
@@ -0,0 +1,53 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Development tools, only available with the `dev_tools` feature.
+//!
+//! The `--watch-l10n` command line option watches the `l10n` directory, so translators see their
+//! edits of the localisation database without restarting the application.
+
+use crate::application::constants;
+use iced::futures::{channel::mpsc, Stream};
+use notify::{RecursiveMode, Watcher};
+use std::{path::PathBuf, sync::mpsc as std_mpsc, thread};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The stream of changes to the files of the `l10n` directory. A burst of file events, such as
+/// the several writes of a single database save, is delivered as a single change once no further
+/// events have arrived for `L10N_RELOAD_DELAY`. The thread ends once the subscription has been
+/// dropped.
+pub fn l10n_changes(path: PathBuf) -> impl Stream<Item = ()> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        let (events_sender, events) = std_mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(events_sender) {
+            Ok(value) => value,
+            Err(error) => {
+                error!("Failed to create the l10n watcher: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = watcher.watch(&path, RecursiveMode::NonRecursive) {
+            error!("Failed to watch ‘{}’: {}", path.display(), error);
+            return;
+        }
+        info!("Watching ‘{}’ for localisation changes.", path.display());
+        while let Ok(event) = events.recv() {
+            match event {
+                Ok(event) if event.kind.is_access() => continue,
+                Ok(_) => {}
+                Err(error) => {
+                    warn!("l10n watcher error: {}", error);
+                    continue;
+                }
+            }
+            while events.recv_timeout(constants::L10N_RELOAD_DELAY).is_ok() {}
+            if sender.unbounded_send(()).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
@@ -3,7 +3,7 @@
 
 //! The available type of windows in the application.
 //!
-//! Remember to also add to the src/application/constants.rs file.
+//! Remember to also add to `ALL_WINDOW_TYPES`, and to the src/application/constants.rs file.
 
 use serde::{Deserialize, Serialize};
 
@@ -18,10 +18,15 @@ pub enum WindowType {
     // Core windows
     ConfirmExit,
     FatalError,
+    RecoverableError,
     Information,
     Preferences,
     About,
     UnsavedData,
+    UnsavedDataBatch,
+    LogViewer,
+    CommandPalette,
+    RestoreDefaults,
 
     // Main windows
     Main,
@@ -30,6 +35,12 @@ pub enum WindowType {
 }
 
 impl WindowType {
+    /// All the window types, such as for validating `WINDOW_DEFAULT_DATA` on startup. New window
+    /// types must be added here.
+    pub fn all() -> &'static [WindowType] {
+        &ALL_WINDOW_TYPES
+    }
+
     /// Returns the window type name.
     pub fn as_str(&self) -> &str {
         match self {
@@ -38,10 +49,15 @@ impl WindowType {
             // Core windows
             WindowType::ConfirmExit => "ConfirmExit",
             WindowType::FatalError => "FatalError",
+            WindowType::RecoverableError => "RecoverableError",
             WindowType::Information => "Information",
             WindowType::Preferences => "Preferences",
             WindowType::About => "About",
             WindowType::UnsavedData => "UnsavedData",
+            WindowType::UnsavedDataBatch => "UnsavedDataBatch",
+            WindowType::LogViewer => "LogViewer",
+            WindowType::CommandPalette => "CommandPalette",
+            WindowType::RestoreDefaults => "RestoreDefaults",
 
             // Main windows
             WindowType::Main => "Main",
@@ -51,6 +67,41 @@ impl WindowType {
     }
 }
 
+impl std::str::FromStr for WindowType {
+    type Err = String;
+
+    /// The inverse of `WindowType::as_str()`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        WindowType::all()
+            .iter()
+            .find(|window_type| window_type.as_str() == string)
+            .cloned()
+            .ok_or_else(|| format!("Unknown window type: {string}"))
+    }
+}
+
+static ALL_WINDOW_TYPES: [WindowType; 13] = [
+    WindowType::Default,
+
+    // Core windows
+    WindowType::ConfirmExit,
+    WindowType::FatalError,
+    WindowType::RecoverableError,
+    WindowType::Information,
+    WindowType::Preferences,
+    WindowType::About,
+    WindowType::UnsavedData,
+    WindowType::UnsavedDataBatch,
+    WindowType::LogViewer,
+    WindowType::CommandPalette,
+    WindowType::RestoreDefaults,
+
+    // Main windows
+    WindowType::Main,
+
+    // Application windows
+];
+
 /// The available string groups of the application.
 /// 
 /// Note: there is no need for a 1 to 1 match with window types, as string
@@ -60,10 +111,13 @@ pub enum StringGroup {
     // Core windows
     ConfirmExit,
     FatalError,
+    RecoverableError,
     Information,
     Preferences,
     About,
     UnsavedData,
+    LogViewer,
+    CommandPalette,
 
     // Main windows
     MainCommon,
@@ -74,16 +128,25 @@ pub enum StringGroup {
 }
 
 impl StringGroup {
+    /// All the string groups, such as for the `--check-localisation` command line option. New
+    /// groups must be added here, and to `localisation::try_strings()`.
+    pub fn all() -> &'static [StringGroup] {
+        &ALL_STRING_GROUPS
+    }
+
     /// Returns the string group name.
     pub fn as_str(&self) -> &str {
         match self {
             // Core windows
             StringGroup::ConfirmExit => "ConfirmExit",
             StringGroup::FatalError => "FatalError",
+            StringGroup::RecoverableError => "RecoverableError",
             StringGroup::Information => "Information",
             StringGroup::Preferences => "Preferences",
             StringGroup::About => "About",
             StringGroup::UnsavedData => "UnsavedData",
+            StringGroup::LogViewer => "LogViewer",
+            StringGroup::CommandPalette => "CommandPalette",
 
             // Main windows
             StringGroup::MainCommon => "MainCommon",
@@ -94,3 +157,98 @@ impl StringGroup {
         }
     }
 }
+
+static ALL_STRING_GROUPS: [StringGroup; 12] = [
+    // Core windows
+    StringGroup::ConfirmExit,
+    StringGroup::FatalError,
+    StringGroup::RecoverableError,
+    StringGroup::Information,
+    StringGroup::Preferences,
+    StringGroup::About,
+    StringGroup::UnsavedData,
+    StringGroup::LogViewer,
+    StringGroup::CommandPalette,
+
+    // Main windows
+    StringGroup::MainCommon,
+    StringGroup::Default,
+    StringGroup::Main,
+
+    // Application windows
+];
+
+/// What happens once the last window thread has been closed, other than by exiting.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LastWindowPolicy {
+    SpawnDefault, // Display the Default window.
+    Exit,         // Save the settings and exit.
+    Hide,         // Keep running without windows, such as for the tray icon's Show action.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{application::constants::WINDOW_DEFAULT_DATA, core::state::Manager};
+
+    /// The position of the window type within `WindowType::all()`. The match is exhaustive, thus
+    /// a new variant fails to compile until it is added here, to `all()` and to
+    /// `WINDOW_DEFAULT_DATA`.
+    fn position(window_type: &WindowType) -> usize {
+        match window_type {
+            WindowType::Default => 0,
+
+            // Core windows
+            WindowType::ConfirmExit => 1,
+            WindowType::FatalError => 2,
+            WindowType::RecoverableError => 3,
+            WindowType::Information => 4,
+            WindowType::Preferences => 5,
+            WindowType::About => 6,
+            WindowType::UnsavedData => 7,
+            WindowType::UnsavedDataBatch => 8,
+            WindowType::LogViewer => 9,
+            WindowType::CommandPalette => 10,
+            WindowType::RestoreDefaults => 11,
+
+            // Main windows
+            WindowType::Main => 12,
+
+            // Application windows
+        }
+    }
+
+    const WINDOW_TYPES: usize = 13;
+
+    #[test]
+    fn all_window_types_have_default_data() {
+        let all = WindowType::all();
+        assert_eq!(all.len(), WINDOW_TYPES);
+        for (index, window_type) in all.iter().enumerate() {
+            assert_eq!(position(window_type), index, "{}", window_type.as_str());
+            assert!(
+                WINDOW_DEFAULT_DATA.contains_key(window_type.as_str()),
+                "{}",
+                window_type.as_str()
+            );
+        }
+
+        // No stale entries of removed or renamed window types.
+        for key in WINDOW_DEFAULT_DATA.keys() {
+            assert!(all.iter().any(|window_type| window_type.as_str() == *key), "{}", key);
+        }
+        assert!(Manager::try_new().is_ok());
+    }
+
+
+    #[test]
+    fn window_type_string_round_trip() {
+        use std::str::FromStr;
+
+        for window_type in WindowType::all() {
+            assert_eq!(WindowType::from_str(window_type.as_str()).as_ref(), Ok(window_type));
+        }
+        assert!(WindowType::from_str("Unknown").is_err());
+        assert!(WindowType::from_str("").is_err());
+    }
+}
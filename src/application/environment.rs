@@ -2,10 +2,16 @@
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
 use crate::{
-    application::{ApplicationError, clap::Clap},
+    application::{
+        ApplicationError, clap::Clap, constants, log::LogBuffer, theme::detect_system_mode,
+    },
     core::error::CoreError,
 };
-use std::{env, path::PathBuf};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use dark_light::Mode;
 use log4rs::Handle as LoggerHandler;
 
 #[allow(unused_imports)]
@@ -17,23 +23,127 @@ use log::{debug, error, info, trace, warn};
 pub struct Environment {
     pub application_path: PathBuf,
     pub logger: LoggerHandler,
+    pub log_buffer: LogBuffer, // The most recent log records.
+    pub system_mode: Mode, // The operating system's theme mode.
+    pub config_file: PathBuf, // The session file, overridden by the `--config` option.
+    config_dir: PathBuf,
+    data_dir: PathBuf,
     pub clap: Clap,
 }
 
 impl Environment {
-    /// Creates the environment struct.
-    pub fn try_new(logger: LoggerHandler, clap: Clap) -> Result<Environment, ApplicationError> {
-        let application_path = match env::current_exe() {
-            Err(error) => return Err(ApplicationError::Core(CoreError::Io(error.to_string()))),
-            Ok(value) => match value.parent() {
-                None => return Err(ApplicationError::Core(CoreError::ApplicationPath)),
-                Some(value) => value.to_owned(),
-            }
+    /// Creates the environment struct. The configuration and data directories are created if
+    /// missing.
+    pub fn try_new(
+        logger: LoggerHandler,
+        log_buffer: LogBuffer,
+        clap: Clap,
+    ) -> Result<Environment, ApplicationError> {
+        let application_path = application_path()?;
+        let config_file = config_file(&clap)?;
+        let config_dir = match config_file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => env::current_dir().map_err(CoreError::from)?,
+        };
+        let data_dir = match dirs::data_dir() {
+            None => return Err(CoreError::DataDirNotFound)?,
+            Some(value) => value.join(constants::VENDOR),
         };
+        create_dirs(&[&config_dir, &data_dir])?;
         Ok(Environment {
             application_path,
+            config_file,
+            config_dir,
+            data_dir,
             logger,
+            log_buffer,
+            system_mode: detect_system_mode(),
             clap,
         })
     }
+
+    /// The directory of the session file, being the vendor directory within the operating
+    /// system's configuration directory, or the directory of the `--config` file.
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// The directory for the application's data files, being the vendor directory within the
+    /// operating system's data directory.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+}
+
+/// The vendor directory within the operating system's configuration directory, containing the
+/// session file and the default log file.
+pub fn default_config_dir() -> Result<PathBuf, CoreError> {
+    match dirs::config_dir() {
+        None => Err(CoreError::ConfigDirNotFound),
+        Some(value) => Ok(value.join(constants::VENDOR)),
+    }
+}
+
+/// The session file, being the `--config` file when specified, else the file within
+/// `default_config_dir()`.
+pub fn config_file(clap: &Clap) -> Result<PathBuf, CoreError> {
+    match &clap.config {
+        Some(path_file) => Ok(path_file.clone()),
+        None => {
+            let mut path_file = default_config_dir()?.join(constants::PACKAGE_NAME);
+            path_file.set_extension("ron");
+            Ok(path_file)
+        }
+    }
+}
+
+/// Create the directories that are missing, including their missing parent directories.
+fn create_dirs(paths: &[&Path]) -> Result<(), CoreError> {
+    for path in paths {
+        if !path.is_dir() {
+            fs::create_dir_all(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// The directory containing the application's executable.
+pub fn application_path() -> Result<PathBuf, CoreError> {
+    match env::current_exe() {
+        Err(error) => Err(CoreError::Io(error.to_string())),
+        Ok(value) => match value.parent() {
+            None => Err(CoreError::ApplicationPath),
+            Some(value) => Ok(value.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TempDir;
+    use clap::Parser;
+
+    #[test]
+    fn missing_directories_are_created() {
+        let temp_dir = TempDir::new("environment");
+        let directory = temp_dir.path();
+        let path = directory.join("profiles").join("profile.ron");
+        let clap = Clap::parse_from([
+            constants::PACKAGE_NAME,
+            "--config",
+            path.to_str().unwrap(),
+        ]);
+        let config_dir = config_file(&clap).unwrap().parent().unwrap().to_path_buf();
+        let data_dir = directory.join("data");
+        assert!(!config_dir.exists());
+        create_dirs(&[&config_dir, &data_dir]).unwrap();
+        assert!(config_dir.is_dir());
+        assert!(data_dir.is_dir());
+
+        // Existing directories are kept.
+        fs::write(data_dir.join("file"), "").unwrap();
+        create_dirs(&[&config_dir, &data_dir]).unwrap();
+        assert!(data_dir.join("file").is_file());
+    }
 }
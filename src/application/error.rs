@@ -25,6 +25,8 @@ pub enum ApplicationError {
     Core(CoreError),
     DatabaseAlreadyOpen,
     InvalidSchema(String),
+    Panic(String), // The formatted panic message and location.
+    ModifiedExternally(String), // The names of the files modified by another application.
 
     // Copied from CoreError as these are common error in application.
     Sqlite3(RefCount<Sqlite3Error>),
@@ -103,6 +105,33 @@ impl LocalisationTrait for ApplicationError {
                     values: Some(values),
                 }
             }
+            ApplicationError::ModifiedExternally(ref names) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "names".to_string(),
+                    PlaceholderValue::String(names.to_string()),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "modified_externally".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("ModifiedExternally".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
             ApplicationError::Sqlite3(ref error) => {
                 // Currently no localisation is available for this error type: Sqlite3Error.
                 let mut values = HashMap::<String, PlaceholderValue>::new();
@@ -139,6 +168,33 @@ impl LocalisationTrait for ApplicationError {
                     values: Some(values),
                 }
             }
+            ApplicationError::Panic(ref message) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::String(message.to_string()),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "panic".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("Panic".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
         }
     }
 }
@@ -155,8 +211,17 @@ impl Display for ApplicationError {
                 "The Sqlite3 file schema is invalid for the database ‘{}’.",
                 name
             ),
+            ApplicationError::ModifiedExternally(ref names) => write!(
+                formatter,
+                "Modified by another application: {}",
+                names
+            ),
             ApplicationError::Sqlite3(ref error) => Display::fmt(&error, formatter),
             ApplicationError::Io(ref error) => Display::fmt(&error, formatter),
+            ApplicationError::Panic(ref message) => write!(
+                formatter,
+                "The application panicked: {}", message
+            ),
         }
     }
 }
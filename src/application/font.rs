@@ -0,0 +1,122 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The application-wide font configuration: the font files of the `fonts` setting, and the
+//! default font of the `font_family` setting, else the preferred font of the language's script.
+//!
+//! `iced` fixes the default font when the daemon is started, thus changes to the `font_family`
+//! setting or the language apply on the next start.
+
+use crate::{
+    application::{clap::Clap, environment, session::Settings, Message, Session},
+    core::localisation::preferred_font,
+};
+use iced::{font, Font, Task};
+use std::{fs, path::PathBuf};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// Read the font files, and queue them for loading by `iced`. An unreadable file is logged and
+/// skipped, as the default font is still available.
+pub fn load_fonts(paths: &[PathBuf]) -> Task<Message> {
+    Task::batch(read_fonts(paths).into_iter().map(|(path, bytes)| {
+        font::load(bytes)
+            .map(move |result| {
+                match result {
+                    Ok(()) => debug!("Loaded the font ‘{}’.", path.display()),
+                    Err(error) => {
+                        warn!("Failed to load the font ‘{}’: {:?}", path.display(), error)
+                    }
+                }
+            })
+            .discard()
+    }))
+}
+
+/// The contents of the readable font files.
+pub fn read_fonts(paths: &[PathBuf]) -> Vec<(PathBuf, Vec<u8>)> {
+    paths
+        .iter()
+        .filter_map(|path| match fs::read(path) {
+            Ok(bytes) => Some((path.clone(), bytes)),
+            Err(error) => {
+                warn!("Failed to read the font ‘{}’: {}", path.display(), error);
+                None
+            }
+        })
+        .collect()
+}
+
+/// The default font of the settings: the `font_family` setting, else the preferred font of the
+/// language's script, else `iced`'s default font.
+///
+/// Note: The family name is leaked, as `iced` requires a static name. Only call once at startup.
+pub fn default_font(settings: &Settings) -> Font {
+    match &settings.font_family {
+        Some(family) if !family.is_empty() => {
+            Font::with_name(Box::leak(family.clone().into_boxed_str()))
+        }
+        _ => match preferred_font(settings.ui.language.as_str()) {
+            Some(family) => Font::with_name(family),
+            None => Font::DEFAULT,
+        },
+    }
+}
+
+/// The default font of the saved settings, for starting the daemon before the application state
+/// exists. `iced`'s default font is used for the `--defaults` option, or when the settings are
+/// unavailable.
+pub fn startup_font(clap: &Clap) -> Font {
+    if clap.defaults {
+        return Font::DEFAULT;
+    }
+    let session = environment::config_file(clap)
+        .ok()
+        .and_then(|config_file| Session::try_restore(&config_file).ok())
+        .unwrap_or_default();
+    default_font(&session.settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TempDir;
+
+    #[test]
+    fn configured_fonts_are_read_for_loading() {
+        let temp_dir = TempDir::new("fonts");
+        let directory = temp_dir.path();
+        let first = directory.join("first.ttf");
+        let second = directory.join("second.otf");
+        fs::write(&first, [0u8, 1, 0, 0]).unwrap();
+        fs::write(&second, b"OTTO").unwrap();
+
+        // The unreadable font is skipped, keeping the order of the setting.
+        let paths = vec![second.clone(), directory.join("missing.ttf"), first.clone()];
+        assert_eq!(
+            read_fonts(&paths),
+            vec![(second, b"OTTO".to_vec()), (first, vec![0u8, 1, 0, 0])]
+        );
+        assert!(read_fonts(&[]).is_empty());
+    }
+
+    #[test]
+    fn default_font_of_settings() {
+        let mut settings = Settings::default();
+        settings.ui.language = "en-ZA".to_string();
+        assert_eq!(default_font(&settings), Font::DEFAULT);
+
+        // The preferred font of the language's script, by the script subtag else the language.
+        settings.ui.language = "zh-Hant-TW".to_string();
+        assert_eq!(default_font(&settings), Font::with_name("Noto Sans CJK TC"));
+        settings.ui.language = "ar".to_string();
+        assert_eq!(default_font(&settings), Font::with_name("Noto Sans Arabic"));
+
+        // The `font_family` setting, unless empty.
+        settings.font_family = Some(String::new());
+        assert_eq!(default_font(&settings), Font::with_name("Noto Sans Arabic"));
+        settings.font_family = Some("Test Sans".to_string());
+        assert_eq!(default_font(&settings), Font::with_name("Test Sans"));
+    }
+}
@@ -1,26 +1,100 @@
 // This file is part of `iced_af` crate. For the terms of use, please see the file
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
-//! TODO: figure out how to eliminate duplicate entries.
+//! The application logger, configured with `log4rs`. The targets of `LOG_TARGETS` and of the
+//! `LogLevels::targets` overrides have their own logger, which is not additive, thus their records
+//! are only written once by the appenders, rather than also by the root logger.
 
 use crate::application::{
     constants,
+    environment::default_config_dir,
     session::LogLevels,
 };
 use core::fmt::{Display, Formatter, Result as FormatterResult};
-use log::LevelFilter;
+use log::{Level, LevelFilter, Log, Metadata, Record};
 use log4rs::{
-    append::console::ConsoleAppender,
-    config::{Appender, Logger, Root},
+    append::{
+        console::ConsoleAppender,
+        rolling_file::{
+            policy::compound::{
+                roll::fixed_window::FixedWindowRoller, trigger::size::SizeTrigger, CompoundPolicy,
+            },
+            RollingFileAppender,
+        },
+    },
+    config::{runtime::ConfigBuilder, Appender, Logger, Root},
+    encode::{pattern::PatternEncoder, writer::simple::SimpleWriter, Encode},
     Config, Handle,
 };
+use std::{
+    any::Any,
+    collections::VecDeque,
+    panic::{self, Location},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 use serde::{Deserialize, Serialize};
 
+/// The groups of log targets, each group has its own log level in `LogLevels`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LogGroup {
+    Application,
+    Iced,
+    I18n,
+}
+
+/// The log targets having a logger, and the group providing their log level. Add or remove the
+/// targets here as the dependencies change. Targets not listed use the `other` log level, unless
+/// overridden by `LogLevels::targets`.
+pub const LOG_TARGETS: [(&str, LogGroup); 17] = [
+    // the application itself, the target being the crate name
+    (constants::PACKAGE_NAME, LogGroup::Application),
+    // iced components (iced depends on many crates)
+    ("iced_wgpu", LogGroup::Iced),
+    ("wgpu_core", LogGroup::Iced),
+    ("iced_graphics", LogGroup::Iced),
+    ("cosmic_text", LogGroup::Iced),
+    ("calloop", LogGroup::Iced),
+    ("naga", LogGroup::Iced),
+    ("fontdb", LogGroup::Iced),
+    ("wgpu_hal", LogGroup::Iced),
+    ("winit", LogGroup::Iced),
+    ("iced_winit", LogGroup::Iced),
+    // i18n components (i18n has a few crates)
+    ("i18n", LogGroup::I18n),
+    ("i18n_lexer", LogGroup::I18n),
+    ("i18n_localiser", LogGroup::I18n),
+    ("i18n_provider", LogGroup::I18n),
+    ("i18n_provider_sqlite3", LogGroup::I18n),
+    ("i18n_utility", LogGroup::I18n),
+];
+
+/// The loggers to be configured, being the `LOG_TARGETS` with the log level of their group,
+/// followed by the targets of `LogLevels::targets` not in `LOG_TARGETS`. The override of a target
+/// replaces its group's log level.
+pub fn loggers(log_levels: &LogLevels) -> Vec<(&str, LogLevel)> {
+    let mut loggers = LOG_TARGETS
+        .iter()
+        .map(|(name, group)| match log_levels.targets.get(*name) {
+            Some(level) => (*name, log_levels.resolve(*level)),
+            None => (*name, log_levels.group(*group)),
+        })
+        .collect::<Vec<(&str, LogLevel)>>();
+    for (name, level) in log_levels.targets.iter() {
+        if !LOG_TARGETS.iter().any(|(target, _)| target == name) {
+            loggers.push((name.as_str(), log_levels.resolve(*level)));
+        }
+    }
+    loggers
+}
+
 /// Create a new application logger
-pub fn new_logger(default: LogLevel) -> Handle {
+///
+/// If `log_to_file` is provided, the log is also written to the file.
+pub fn new_logger(default: LogLevel, log_to_file: Option<&Path>, log_buffer: &LogBuffer) -> Handle {
     let default = if default == LogLevel::Default {
         // Invalid variant, silently change to LogLevel::Error
         LogLevel::Error
@@ -28,13 +102,17 @@ pub fn new_logger(default: LogLevel) -> Handle {
         default
     };
     println!("Initialise: Log level set to ‘{}’", default); // Keep this line
-    let stdout = ConsoleAppender::builder().build();
+    let (builder, appenders) = add_appenders(
+        Config::builder(),
+        log_to_file,
+        log_buffer,
+        constants::DEFAULT_LOG_FORMAT,
+    );
     log4rs::init_config(
-        Config::builder()
-            .appender(Appender::builder().build("stdout", Box::new(stdout)))
+        builder
             .build(
                 Root::builder()
-                    .appender("stdout")
+                    .appenders(appenders)
                     .build(default.to_level_filter()),
             )
             .unwrap(),
@@ -43,138 +121,258 @@ pub fn new_logger(default: LogLevel) -> Handle {
 }
 
 /// Update the log levels of the logger.
-pub fn update_logger(handle: &mut Handle, log_levels: &LogLevels) {
-    let application = match log_levels.application {
-        LogLevel::Default => log_levels.default,
-        _ => log_levels.application,
-    };
-    let other = match log_levels.other {
-        LogLevel::Default => log_levels.default,
-        _ => log_levels.other,
-    };
-    let iced = match log_levels.iced {
-        LogLevel::Default => log_levels.default,
-        _ => log_levels.iced,
+///
+/// The file appender is only present when `log_levels.log_to_file` is set, thus it is removed when
+/// the setting has been cleared.
+pub fn update_logger(handle: &mut Handle, log_levels: &LogLevels, log_buffer: &LogBuffer) {
+    handle.set_config(config(log_levels, log_buffer));
+    println!("Log levels has been updated.");
+    if !is_valid_pattern(&log_levels.log_format) {
+        warn!("Invalid log format ‘{}’, using the default log format.", log_levels.log_format);
+    }
+}
+
+/// The logger configuration of the log levels, having a logger for each of `loggers()`.
+fn config(log_levels: &LogLevels, log_buffer: &LogBuffer) -> Config {
+    let other = log_levels.resolve(log_levels.other);
+    let (mut builder, appenders) = add_appenders(
+        Config::builder(),
+        log_levels.log_to_file.as_deref(),
+        log_buffer,
+        log_levels.log_format.as_str(),
+    );
+    for (name, level) in loggers(log_levels) {
+        builder = builder.logger(
+            Logger::builder()
+                .appenders(appenders.clone())
+                .additive(false)
+                .build(name, level.to_level_filter()),
+        );
+    }
+    builder
+        .build(
+            Root::builder()
+                .appenders(appenders)
+                .build(other.to_level_filter()),
+        )
+        .unwrap()
+}
+
+/// Indicates whether the `log4rs` pattern is valid. As `PatternEncoder` embeds the errors of the
+/// pattern in the encoded records, a record is encoded and checked for errors.
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    let mut writer = SimpleWriter(Vec::<u8>::new());
+    PatternEncoder::new(pattern)
+        .encode(&mut writer, &Record::builder().args(format_args!("")).build())
+        .is_ok()
+        && !String::from_utf8_lossy(&writer.0).contains("{ERROR:")
+}
+
+/// The encoder of the pattern, using `DEFAULT_LOG_FORMAT` for an invalid pattern.
+///
+/// The logger is not available while being configured, thus an invalid pattern is logged by
+/// `update_logger()` once the logger is reconfigured.
+fn encoder(pattern: &str) -> Box<PatternEncoder> {
+    if is_valid_pattern(pattern) {
+        return Box::new(PatternEncoder::new(pattern));
+    }
+    Box::new(PatternEncoder::new(constants::DEFAULT_LOG_FORMAT))
+}
+
+/// Add the stdout, log buffer, and the optional file appenders to the configuration, returning
+/// the names of the added appenders. The stdout and the file appenders use the log format.
+fn add_appenders(
+    mut builder: ConfigBuilder,
+    log_to_file: Option<&Path>,
+    log_buffer: &LogBuffer,
+    log_format: &str,
+) -> (ConfigBuilder, Vec<&'static str>) {
+    let stdout = ConsoleAppender::builder().encoder(encoder(log_format)).build();
+    let buffer = BufferAppender::new(log_buffer);
+    let mut appenders = vec!["stdout", "buffer"];
+    builder = builder
+        .appender(Appender::builder().build("stdout", Box::new(stdout)))
+        .appender(Appender::builder().build("buffer", Box::new(buffer)));
+    if let Some(file) = log_to_file.and_then(|path| file_appender(path, log_format)) {
+        builder = builder.appender(Appender::builder().build("file", Box::new(file)));
+        appenders.push("file");
+    }
+    (builder, appenders)
+}
+
+/// Install the panic hook, which logs the panic message and location as an error before calling
+/// the previous hook. Thus panics are also written to the log buffer and the log file.
+///
+/// Install the hook once the logger has been created.
+pub fn set_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        error!("{}", panic_message(info.payload(), info.location()));
+        previous(info);
+    }));
+}
+
+/// Format the panic payload and location of a panic.
+///
+/// Payloads that are not strings, are indicated as a non-string payload.
+pub fn panic_message(payload: &(dyn Any + Send), location: Option<&Location>) -> String {
+    let payload = match payload.downcast_ref::<&str>() {
+        Some(value) => value,
+        None => match payload.downcast_ref::<String>() {
+            Some(value) => value.as_str(),
+            None => "<non-string payload>",
+        },
     };
-    let i18n = match log_levels.i18n {
-        LogLevel::Default => log_levels.default,
-        _ => log_levels.i18n,
+    match location {
+        None => format!("‘{}’", payload),
+        Some(location) => format!(
+            "‘{}’ at ‘{}:{}:{}’",
+            payload,
+            location.file(),
+            location.line(),
+            location.column()
+        ),
+    }
+}
+
+/// The default log file, located in the same directory as the configuration file.
+pub fn default_log_file() -> Option<PathBuf> {
+    let mut path_file = default_config_dir().ok()?.join(constants::PACKAGE_NAME);
+    path_file.set_extension("log");
+    Some(path_file)
+}
+
+/// Create the rolling file appender, which rolls the log file over once it reaches
+/// `LOG_FILE_SIZE_LIMIT`, keeping the last `LOG_FILE_COUNT` rolled over files.
+///
+/// The logger is not available while being configured, thus failures are printed to stderr.
+fn file_appender(path: &Path, log_format: &str) -> Option<RollingFileAppender> {
+    let pattern = format!("{}.{{}}", path.display());
+    let roller = match FixedWindowRoller::builder().build(&pattern, constants::LOG_FILE_COUNT) {
+        Err(error) => {
+            eprintln!("Failed to create the log file roller: {}", error);
+            return None;
+        }
+        Ok(value) => value,
     };
-    let stdout = ConsoleAppender::builder().build();
-    handle.set_config(
-        Config::builder()
-            .appender(Appender::builder().build("stdout", Box::new(stdout)))
-            // the application itself
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("iced_af_rizzen_yazston", application.to_level_filter()),
-            )
-            // iced components (iced depends on many crates)
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("iced_wgpu", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("wgpu_core", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("iced_graphics", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("cosmic_text", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("calloop", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("naga", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("fontdb", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("wgpu_hal", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("winit", iced.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("iced_winit", iced.to_level_filter()),
-            )
-            // i18n components (i18n has a few crates)
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n", i18n.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n_lexer", i18n.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n_localiser", i18n.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n_provider", i18n.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n_provider_sqlite3", i18n.to_level_filter()),
-            )
-            .logger(
-                Logger::builder()
-                    .appender("stdout")
-                    .build("i18n_utility", i18n.to_level_filter()),
-            )
-            .build(
-                Root::builder()
-                    .appender("stdout")
-                    .build(other.to_level_filter()),
-            )
-            .unwrap(),
+    let policy = CompoundPolicy::new(
+        Box::new(SizeTrigger::new(constants::LOG_FILE_SIZE_LIMIT)),
+        Box::new(roller),
     );
-    println!("Log levels has been updated.");
+    match RollingFileAppender::builder()
+        .encoder(encoder(log_format))
+        .build(path, Box::new(policy))
+    {
+        Err(error) => {
+            eprintln!("Failed to open the log file ‘{}’: {}", path.display(), error);
+            None
+        }
+        Ok(value) => Some(value),
+    }
+}
+
+/// A formatted log record of the log buffer.
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    pub level: Level,
+    pub line: String,
+}
+
+/// The shared buffer of the most recent log records, displayed by the Log Viewer window.
+///
+/// The appenders may be called from any thread, thus `Arc` and `Mutex` are always used,
+/// regardless of the `sync` feature.
+pub type LogBuffer = Arc<Mutex<VecDeque<LogRecord>>>;
+
+/// Create an empty log buffer.
+pub fn new_log_buffer() -> LogBuffer {
+    Arc::new(Mutex::new(VecDeque::with_capacity(constants::LOG_BUFFER_CAPACITY)))
+}
+
+/// The appender that pushes the formatted log records into the log buffer, dropping the oldest
+/// records once the buffer holds `LOG_BUFFER_CAPACITY` records.
+///
+/// Note: Never log within the appender, as the buffer is locked.
+#[derive(Debug)]
+pub struct BufferAppender {
+    buffer: LogBuffer,
+}
+
+impl BufferAppender {
+    pub fn new(buffer: &LogBuffer) -> Self {
+        BufferAppender {
+            buffer: Arc::clone(buffer),
+        }
+    }
+}
+
+impl Log for BufferAppender {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let line = format!(
+            "{} {} {} - {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        while buffer.len() >= constants::LOG_BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(LogRecord {
+            level: record.level(),
+            line,
+        });
+    }
+
+    fn flush(&self) {}
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash, Deserialize, Serialize)]
+/// The log levels, ordered by verbosity after `Default`, thus `Info < Debug < Trace`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash, Deserialize, Serialize)]
 pub enum LogLevel {
     #[default]
     Default,
     Off,
     Error,
     Warn,
-    Debug,
     Info,
+    Debug,
     Trace,
 }
 
 impl LogLevel {
+    /// Resolve `Default` to the specified default log level, which when also `Default` resolves
+    /// to `DEFAULT_LOG_LEVEL_FILTER`. Used by both the logger and the Preferences window, thus
+    /// the displayed log level is the configured log level.
+    pub fn resolve(self, default: LogLevel) -> LogLevel {
+        match (self, default) {
+            (LogLevel::Default, LogLevel::Default) => {
+                LogLevel::from_level_filter(constants::DEFAULT_LOG_LEVEL_FILTER)
+            }
+            (LogLevel::Default, _) => default,
+            _ => self,
+        }
+    }
+
+    /// Convert [`log::LevelFilter`] to `LogLevel`.
+    pub fn from_level_filter(filter: LevelFilter) -> LogLevel {
+        match filter {
+            LevelFilter::Off => LogLevel::Off,
+            LevelFilter::Error => LogLevel::Error,
+            LevelFilter::Warn => LogLevel::Warn,
+            LevelFilter::Info => LogLevel::Info,
+            LevelFilter::Debug => LogLevel::Debug,
+            LevelFilter::Trace => LogLevel::Trace,
+        }
+    }
+
     /// Convert `LogLevel` to [`log::LevelFilter`].
     pub fn to_level_filter(&self) -> LevelFilter {
         match self {
@@ -221,3 +419,160 @@ impl std::str::FromStr for LogLevel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TempDir;
+
+    #[test]
+    fn panic_message_formats_payload_and_location() {
+        let location = Location::caller();
+        let message = panic_message(&"boom", Some(location));
+        assert_eq!(
+            message,
+            format!("‘boom’ at ‘{}:{}:{}’", location.file(), location.line(), location.column())
+        );
+        assert_eq!(panic_message(&String::from("boom"), None), "‘boom’");
+        assert_eq!(panic_message(&42, None), "‘<non-string payload>’");
+    }
+
+    #[test]
+    fn config_has_logger_per_target() {
+        let mut log_levels = LogLevels {
+            iced: LogLevel::Warn,
+            ..Default::default()
+        };
+        log_levels.targets.insert("naga".to_string(), LogLevel::Trace);
+        log_levels.targets.insert("custom_crate".to_string(), LogLevel::Debug);
+        let config = config(&log_levels, &new_log_buffer());
+        let configured = config
+            .loggers()
+            .iter()
+            .map(|logger| (logger.name(), logger.level()))
+            .collect::<Vec<(&str, LevelFilter)>>();
+        assert_eq!(configured.len(), LOG_TARGETS.len() + 1);
+        for (target, _) in LOG_TARGETS.iter() {
+            assert!(configured.iter().any(|(name, _)| name == target), "{}", target);
+        }
+        assert!(configured.contains(&(constants::PACKAGE_NAME, LevelFilter::Info)));
+        assert!(configured.contains(&("iced_wgpu", LevelFilter::Warn)));
+        assert!(configured.contains(&("naga", LevelFilter::Trace)));
+        assert!(configured.contains(&("custom_crate", LevelFilter::Debug)));
+    }
+
+    /// Log the record of the target and level with the logger of the configuration.
+    fn log_with(config: Config, target: &str, level: Level, message: &str) {
+        let logger = log4rs::Logger::new(config);
+        logger.log(
+            &Record::builder()
+                .target(target)
+                .level(level)
+                .args(format_args!("{}", message))
+                .build(),
+        );
+        logger.flush();
+    }
+
+    #[test]
+    fn validate_log_format() {
+        assert!(is_valid_pattern(constants::DEFAULT_LOG_FORMAT));
+        assert!(is_valid_pattern("[{l}] {t}: {m}{n}"));
+        assert!(!is_valid_pattern("{d(%Y"));
+        assert!(!is_valid_pattern("{unknown}"));
+    }
+
+    #[test]
+    fn logger_uses_custom_log_format() {
+        let directory = TempDir::new("log");
+        let path = directory.path().join("custom.log");
+        let mut log_levels = LogLevels {
+            log_to_file: Some(path.clone()),
+            log_format: "[{l}] {t}: {m}{n}".to_string(),
+            ..Default::default()
+        };
+        log_with(
+            config(&log_levels, &new_log_buffer()),
+            constants::PACKAGE_NAME,
+            Level::Info,
+            "custom",
+        );
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, format!("[INFO] {}: custom\n", constants::PACKAGE_NAME));
+
+        // An invalid log format uses the default log format.
+        let _ = std::fs::remove_file(&path);
+        log_levels.log_format = "{unknown}".to_string();
+        log_with(
+            config(&log_levels, &new_log_buffer()),
+            constants::PACKAGE_NAME,
+            Level::Info,
+            "default",
+        );
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.ends_with(&format!("INFO  {} - default\n", constants::PACKAGE_NAME)));
+    }
+
+    #[test]
+    fn record_emitted_once() {
+        let log_levels = LogLevels {
+            log_format: "{t}: {m}".to_string(),
+            ..Default::default()
+        };
+        for target in ["iced_wgpu", "uncovered_crate"] {
+            let log_buffer = new_log_buffer();
+            log_with(config(&log_levels, &log_buffer), target, Level::Error, "once");
+            let buffer = log_buffer.lock().unwrap();
+            assert_eq!(buffer.len(), 1, "{}", target);
+            assert!(buffer[0].line.contains("once"));
+        }
+    }
+
+    #[test]
+    fn log_levels_ordered_by_verbosity() {
+        assert!(LogLevel::Off < LogLevel::Error);
+        assert!(LogLevel::Error < LogLevel::Warn);
+        assert!(LogLevel::Warn < LogLevel::Info);
+        assert!(LogLevel::Info < LogLevel::Debug);
+        assert!(LogLevel::Debug < LogLevel::Trace);
+
+        // The same order as the level filters, apart from `Default`.
+        let levels = [
+            LogLevel::Off,
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ];
+        for pair in levels.windows(2) {
+            assert!(pair[0].to_level_filter() < pair[1].to_level_filter());
+        }
+        for level in levels {
+            assert_eq!(LogLevel::from_level_filter(level.to_level_filter()), level);
+        }
+    }
+
+    #[test]
+    fn resolve_default_log_level() {
+        assert_eq!(LogLevel::Debug.resolve(LogLevel::Warn), LogLevel::Debug);
+        assert_eq!(LogLevel::Default.resolve(LogLevel::Warn), LogLevel::Warn);
+        assert_eq!(
+            LogLevel::Default.resolve(LogLevel::Default),
+            LogLevel::from_level_filter(constants::DEFAULT_LOG_LEVEL_FILTER)
+        );
+
+        // The logger's levels are those displayed as resolved in the Preferences window.
+        let log_levels = LogLevels {
+            default: LogLevel::Warn,
+            iced: LogLevel::Default,
+            i18n: LogLevel::Trace,
+            ..Default::default()
+        };
+        assert_eq!(log_levels.group(LogGroup::Iced), LogLevel::Warn);
+        assert_eq!(log_levels.group(LogGroup::I18n), LogLevel::Trace);
+        let loggers = loggers(&log_levels);
+        assert!(loggers.contains(&("winit", LogLevel::Warn)));
+        assert!(loggers.contains(&("i18n_lexer", LogLevel::Trace)));
+    }
+}
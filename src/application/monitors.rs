@@ -0,0 +1,71 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The detection of the monitor areas, for placing restored windows on a connected monitor.
+//!
+//! `iced` does not provide a means to enumerate the monitors, thus on Linux the RandR extension of
+//! the X11 server is queried, which includes XWayland. On the other platforms, or without an X11
+//! server, no monitors are detected, and the stored window positions are used as is.
+
+use iced::Rectangle;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The areas of the connected monitors, in logical coordinates. Empty when the monitors could not
+/// be detected.
+pub fn detect() -> Vec<Rectangle> {
+    #[cfg(target_os = "linux")]
+    match x11::try_detect() {
+        Ok(monitors) => {
+            trace!("Detected monitors: {:?}", monitors);
+            return monitors;
+        }
+        Err(error) => debug!("Failed to detect the monitors: {}", error),
+    }
+    Vec::<Rectangle>::new()
+}
+
+#[cfg(target_os = "linux")]
+mod x11 {
+    use iced::{Point, Rectangle, Size};
+    use std::{env, error::Error};
+    use x11rb::{connection::Connection, protocol::randr, resource_manager};
+
+    /// Query the active monitors of the RandR extension, converting them to logical coordinates
+    /// using the same scale factor as `winit`.
+    pub fn try_detect() -> Result<Vec<Rectangle>, Box<dyn Error>> {
+        let (connection, screen) = x11rb::connect(None)?;
+        let root = connection.setup().roots[screen].root;
+        let reply = randr::get_monitors(&connection, root, true)?.reply()?;
+        let scale = scale_factor(&connection);
+        Ok(reply
+            .monitors
+            .iter()
+            .map(|monitor| {
+                Rectangle::new(
+                    Point::new(monitor.x as f32 / scale, monitor.y as f32 / scale),
+                    Size::new(monitor.width as f32 / scale, monitor.height as f32 / scale),
+                )
+            })
+            .collect())
+    }
+
+    /// The `WINIT_X11_SCALE_FACTOR` environment variable, else the `Xft.dpi` resource relative to
+    /// 96 DPI, else `1.0`.
+    fn scale_factor(connection: &impl Connection) -> f32 {
+        if let Some(scale) = env::var("WINIT_X11_SCALE_FACTOR")
+            .ok()
+            .and_then(|value| value.parse::<f32>().ok())
+            .filter(|scale| *scale > 0f32)
+        {
+            return scale;
+        }
+        resource_manager::new_from_default(connection)
+            .ok()
+            .and_then(|database| database.get_value::<u32>("Xft.dpi", "").ok().flatten())
+            .filter(|dpi| *dpi > 0)
+            .map(|dpi| dpi as f32 / 96f32)
+            .unwrap_or(1f32)
+    }
+}
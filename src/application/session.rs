@@ -6,17 +6,31 @@
 //! Add data as needed.
 
 use crate::{
-    application::{constants, log::LogLevel, WindowType},
+    application::{
+        accelerator::AcceleratorOverride,
+        constants,
+        log::{default_log_file, LogGroup, LogLevel},
+        theme::ThemePreference,
+        WindowType,
+    },
     core::error::CoreError,
 };
+use i18n::utility::LanguageTag;
 use std::{
     collections::{BTreeMap, VecDeque},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
-use dirs::config_dir;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
@@ -25,68 +39,329 @@ use log::{debug, error, info, trace, warn};
 /// Add additional settings to the various structs.
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Session {
+    #[serde(default)]
+    pub version: u32, // Settings version, sessions prior to versioning are version 0.
     pub settings: Settings,
     pub history: VecDeque<PathBuf>, // Hold last 10 opened databases.
     pub windows: BTreeMap<WindowType, WindowData>,
+    #[serde(default)]
+    pub open_windows: Vec<OpenWindowRecord>, // The window threads open at exit, oldest first.
+    #[serde(skip)]
+    modified: Option<SystemTime>, // Modification time of the configuration file when last read or written.
 }
 
 impl Session {
-    pub fn save(&self) -> Result<(), CoreError> {
-        let path_config = match config_dir() {
-            None => return Err(CoreError::ConfigDirNotFound),
-            Some(value) => value,
-        };
-        let path_vendor = path_config.join(constants::VENDOR);
-        if !path_vendor.exists() {
-            fs::create_dir(path_vendor.clone())?;
+    /// Save the session to the configuration file, `Environment::config_file`.
+    ///
+    /// The session is first written to a sibling temporary file, which is then renamed over the
+    /// configuration file. The rename is atomic on the same filesystem, thus the configuration
+    /// file is never left partially written if the application is terminated during the save.
+    ///
+    /// When the configuration file has been modified since it was restored or last saved, such as
+    /// by another instance or by the user, the last writer would win silently. Instead, the
+    /// modified file is first copied to a `.conflict` backup, and a warning is logged.
+    pub fn save(&mut self, path_file: &Path) -> Result<(), CoreError> {
+        if let Some(parent) = path_file.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        if let (Some(modified), Some(on_disk)) = (self.modified, modified_time(path_file)) {
+            if on_disk > modified {
+                let path_conflict = conflict_path(path_file);
+                warn!(
+                    "‘{}’ was modified externally, keeping it as ‘{}’ before overwriting.",
+                    path_file.display(),
+                    path_conflict.display()
+                );
+                fs::copy(path_file, path_conflict)?;
+            }
         }
-        let mut path_file = path_vendor.join(constants::PACKAGE_NAME);
-        path_file.set_extension("ron");
         let contents = ron::to_string(&self)?;
-        fs::write(path_file, contents)?;
+        let path_temp = temporary_path(path_file);
+        fs::write(&path_temp, contents)?;
+        fs::rename(path_temp, path_file)?;
+        self.modified = modified_time(path_file);
         Ok(())
     }
 
-    pub fn try_restore() -> Result<Session, CoreError> {
-        let path_config = match config_dir() {
-            None => return Err(CoreError::ConfigDirNotFound),
-            Some(value) => value,
-        };
-        let path_vendor = path_config.join(constants::VENDOR);
-        if !path_vendor.exists() {
-            return Err(CoreError::NoVendorDir(path_vendor));
-        }
-        if !path_vendor.is_dir() {
-            return Err(CoreError::NoVendorDir(path_vendor));
+    /// Restore the session from the configuration file, `Environment::config_file`.
+    ///
+    /// If the configuration file fails to be parsed, the temporary file of an incomplete save
+    /// is tried before giving up. Sessions of older settings versions are migrated, and the
+    /// upgraded session is saved.
+    pub fn try_restore(path_file: &Path) -> Result<Session, CoreError> {
+        let mut session = Session::try_read(path_file)?;
+        session.modified = modified_time(path_file);
+        if migrate(&mut session)? {
+            if let Err(error) = session.save(path_file) {
+                warn!("Failed to save the migrated session: {}", error);
+            }
         }
-        let mut path_file = path_vendor.join(constants::PACKAGE_NAME);
-        path_file.set_extension("ron");
+        Ok(session)
+    }
+
+    /// Export the session to the file, such as for moving the settings to another computer. The
+    /// settings version is included, for the migrations when imported.
+    pub fn export_to(&self, path: &Path) -> Result<(), CoreError> {
+        let contents = ron::ser::to_string_pretty(&self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Import the session from the file, running the same migrations as `try_restore()`. A session
+    /// of a newer settings version is rejected.
+    pub fn import_from(path: &Path) -> Result<Session, CoreError> {
+        let string = fs::read_to_string(path)?;
+        let mut session: Session = ron::from_str(string.as_str())?;
+        migrate(&mut session)?;
+        Ok(session)
+    }
+
+    /// Add the path to the front of the recent list, removing any previous entry of the path, and
+    /// limiting the list to `RECENT_LIMIT` entries.
+    pub fn push_recent(&mut self, path: &str) {
+        let recent = &mut self.settings.recent;
+        recent.retain(|entry| entry != path);
+        recent.insert(0, path.to_string());
+        recent.truncate(constants::RECENT_LIMIT);
+    }
+
+    /// Read the session from the configuration file as is.
+    fn try_read(path_file: &Path) -> Result<Session, CoreError> {
         if !path_file.exists() {
-            return Err(CoreError::NoConfigFile(path_file));
+            return Err(CoreError::NoConfigFile(path_file.to_path_buf()));
         }
         if !path_file.is_file() {
-            return Err(CoreError::NoConfigFile(path_file));
+            return Err(CoreError::NoConfigFile(path_file.to_path_buf()));
         }
         let string = fs::read_to_string(path_file)?;
-        Ok(ron::from_str(string.as_str())?)
+        match ron::from_str(string.as_str()) {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                let path_temp = temporary_path(path_file);
+                if !path_temp.is_file() {
+                    return Err(error.into());
+                }
+                warn!(
+                    "Failed to parse ‘{}’, trying ‘{}’: {}",
+                    path_file.display(),
+                    path_temp.display(),
+                    error
+                );
+                let string = fs::read_to_string(path_temp)?;
+                Ok(ron::from_str(string.as_str())?)
+            }
+        }
+    }
+}
+
+/// The ordered settings migrations, where the migration at index `n` upgrades the session from
+/// version `n` to version `n + 1`.
+///
+/// Add a migration for every increment of `SETTINGS_VERSION`. New fields should have a
+/// `#[serde(default)]` attribute, and renamed fields a `#[serde(alias = "…")]` attribute, so
+/// that older sessions can still be deserialised before being migrated.
+static MIGRATIONS: [fn(&mut Session); constants::SETTINGS_VERSION as usize] = [
+    // 0 → 1: Language tags are stored in the hyphenated form used by the localisation database.
+    |session| session.settings.ui.language = session.settings.ui.language.replace('_', "-"),
+];
+
+/// Run the migrations from the session's version up to the current `SETTINGS_VERSION`.
+///
+/// Returns `true` if the session was migrated. A session of a newer version is rejected, rather
+/// than silently downgraded.
+pub fn migrate(session: &mut Session) -> Result<bool, CoreError> {
+    if session.version > constants::SETTINGS_VERSION {
+        return Err(CoreError::SettingsVersionUnsupported(session.version));
+    }
+    let migrated = session.version < constants::SETTINGS_VERSION;
+    while session.version < constants::SETTINGS_VERSION {
+        debug!("Migrating session from version {}.", session.version);
+        MIGRATIONS[session.version as usize](session);
+        session.version += 1;
     }
+    Ok(migrated)
+}
+
+/// The backup of the configuration file, when it was modified externally before being saved.
+fn conflict_path(path_file: &Path) -> PathBuf {
+    let mut path_conflict = path_file.to_path_buf();
+    path_conflict.set_extension("ron.conflict");
+    path_conflict
+}
+
+/// The modification time of the file, `None` if not available.
+pub fn modified_time(path_file: &Path) -> Option<SystemTime> {
+    fs::metadata(path_file).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// The temporary file used for writing the configuration file, located in the same directory.
+fn temporary_path(path_file: &Path) -> PathBuf {
+    let mut path_temp = path_file.to_path_buf();
+    path_temp.set_extension("ron.tmp");
+    path_temp
 }
 
 impl Default for Session {
     fn default() -> Self {
         let windows = BTreeMap::<WindowType, WindowData>::new();
         Session {
+            version: constants::SETTINGS_VERSION,
             settings: Settings::default(),
             history: VecDeque::<PathBuf>::new(),
             windows,
+            open_windows: Vec::<OpenWindowRecord>::new(),
+            modified: None,
         }
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Default)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct Settings {
     pub ui: Ui,
     pub log_levels: LogLevels,
+    #[serde(default)]
+    pub theme: ThemePreference,
+    #[serde(default = "default_accent")]
+    pub accent: [f32; 3], // sRGB accent colour, used as the primary colour of the theme.
+    #[serde(default)]
+    pub recent: Vec<String>, // Recently opened paths, most recent first.
+    #[serde(default)]
+    pub autosave_secs: Option<u32>, // Autosave interval in seconds, `None` disables autosave.
+    #[serde(default)]
+    pub restore_session: bool, // Reopen the windows of `Session::open_windows` on startup.
+    #[serde(default)]
+    pub minimize_to_tray: bool, // Closing the Default window hides it, requires the `tray` feature.
+    #[serde(default)]
+    pub cascade_new_windows: bool, // New window threads are offset from the opened windows of the same type.
+    #[serde(default)]
+    pub last_preferences_tab: Option<String>, // The last selected tab of the Preferences window.
+    #[serde(default)]
+    pub document_mode: DocumentMode, // Open the documents in separate windows, or as tabs.
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32, // Scale of the text sizes and paddings of all the windows, see `set_ui_scale()`.
+    #[serde(default)]
+    pub fonts: Vec<PathBuf>, // Font files loaded on startup, such as for CJK or Arabic scripts.
+    #[serde(default)]
+    pub font_family: Option<String>, // The default font, else the preferred font of the language.
+    #[serde(default)]
+    pub accelerator_overrides: Vec<AcceleratorOverride>, // Applied to `ACCELERATORS` on startup.
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            ui: Ui::default(),
+            log_levels: LogLevels::default(),
+            theme: ThemePreference::default(),
+            accent: constants::DEFAULT_ACCENT,
+            recent: Vec::<String>::new(),
+            autosave_secs: None,
+            restore_session: false,
+            minimize_to_tray: false,
+            cascade_new_windows: false,
+            last_preferences_tab: None,
+            document_mode: DocumentMode::default(),
+            ui_scale: default_ui_scale(),
+            fonts: Vec::<PathBuf>::new(),
+            font_family: None,
+            accelerator_overrides: Vec::<AcceleratorOverride>::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Set the scale of the user interface, clamped to `UI_SCALE_MIN..=UI_SCALE_MAX`. The scale is
+    /// rounded to hundredths to avoid accumulating rounding errors of the zoom steps.
+    pub fn set_ui_scale(&mut self, scale: f32) {
+        self.ui_scale = clamp_ui_scale(scale);
+    }
+
+    /// Apply the changed setting of the Preferences window, returning the side effects to be
+    /// performed by the caller.
+    pub fn apply_change(&mut self, setting: Setting) -> Vec<SideEffect> {
+        trace!("Applying setting: {:?}", setting);
+        match setting {
+            Setting::Language(language) => {
+                self.ui.language = language.as_str().to_string();
+                vec![SideEffect::ReLocalise]
+            }
+            Setting::Log(log) => {
+                match log {
+                    LogSetting::LevelDefault(log_level) => self.log_levels.default = log_level,
+                    LogSetting::LevelApplication(log_level) => {
+                        self.log_levels.application = log_level
+                    }
+                    LogSetting::LevelOther(log_level) => self.log_levels.other = log_level,
+                    LogSetting::LevelIced(log_level) => self.log_levels.iced = log_level,
+                    LogSetting::LevelI18n(log_level) => self.log_levels.i18n = log_level,
+                    LogSetting::ToFile(enabled) => {
+                        let log_to_file = &mut self.log_levels.log_to_file;
+                        *log_to_file = match enabled {
+                            false => None,
+                            true => log_to_file.take().or_else(default_log_file),
+                        };
+                    }
+                }
+                vec![SideEffect::UpdateLogger]
+            }
+            Setting::Theme(theme) => {
+                self.theme = theme;
+                vec![SideEffect::ReTheme]
+            }
+            Setting::Accent(accent) => {
+                self.accent = accent;
+                vec![SideEffect::ReTheme]
+            }
+        }
+    }
+}
+
+/// A changed setting of the Preferences window, see `Settings::apply_change()`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Setting {
+    Language(RefCount<LanguageTag>),
+    Log(LogSetting),
+    Theme(ThemePreference),
+    Accent([f32; 3]),
+}
+
+/// A changed setting of the Logs tab.
+#[derive(PartialEq, Clone, Debug)]
+pub enum LogSetting {
+    LevelDefault(LogLevel),
+    LevelApplication(LogLevel),
+    LevelOther(LogLevel),
+    LevelIced(LogLevel),
+    LevelI18n(LogLevel),
+    ToFile(bool),
+}
+
+/// The side effects of a changed setting, see `Settings::apply_change()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideEffect {
+    ReLocalise,   // Apply the language to the localiser, and update the localised strings.
+    UpdateLogger, // Reconfigure the loggers with the log levels.
+    ReTheme,      // Redraw the windows with the theme.
+}
+
+fn default_accent() -> [f32; 3] {
+    constants::DEFAULT_ACCENT
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+/// Clamp the scale of the user interface to `UI_SCALE_MIN..=UI_SCALE_MAX`, rounded to hundredths.
+/// A scale that is not a number, such as from an edited settings file, is the default scale.
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    if scale.is_nan() {
+        return default_ui_scale();
+    }
+    let scale = scale.clamp(constants::UI_SCALE_MIN, constants::UI_SCALE_MAX);
+    (scale * 100.0).round() / 100.0
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -97,7 +372,7 @@ pub struct Ui {
 impl Default for Ui {
     fn default() -> Self {
         Ui {
-            language: "en_ZA".to_string(),  // Same as the default language of the "application"
+            language: "en-ZA".to_string(),  // Same as the default language of the "application"
                                             // component in localisation database.
         }
     }
@@ -105,8 +380,26 @@ impl Default for Ui {
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct WindowData {
-    pub size: (f32, f32),
-    pub position: Option<(f32, f32)>,
+    pub size: (f32, f32), // The floating (not maximised) size.
+    pub position: Option<(f32, f32)>, // The floating (not maximised) position.
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// A window thread open at exit. The geometry is restored from `Session::windows` of the window
+/// type.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OpenWindowRecord {
+    pub window_type: WindowType,
+    pub path: Option<String>, // The path of the window's data, if any.
+}
+
+/// How the documents are opened, see `main::display()`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub enum DocumentMode {
+    #[default]
+    Windows, // Each document is opened in its own Main window thread.
+    Tabs, // The documents are opened as tabs of an already opened Main window.
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -116,6 +409,32 @@ pub struct LogLevels {
     pub other: LogLevel,
     pub iced: LogLevel,
     pub i18n: LogLevel,
+    #[serde(default)]
+    pub log_to_file: Option<PathBuf>, // When set, the log is also written to this file.
+    #[serde(default)]
+    pub targets: BTreeMap<String, LogLevel>, // Log level overrides of targets, for advanced users.
+    #[serde(default = "default_log_format")]
+    pub log_format: String, // `log4rs` pattern of the console and the log file.
+}
+
+fn default_log_format() -> String {
+    constants::DEFAULT_LOG_FORMAT.to_string()
+}
+
+impl LogLevels {
+    /// The log level, using the `default` log level for `LogLevel::Default`.
+    pub fn resolve(&self, level: LogLevel) -> LogLevel {
+        level.resolve(self.default)
+    }
+
+    /// The log level of the group of log targets.
+    pub fn group(&self, group: LogGroup) -> LogLevel {
+        match group {
+            LogGroup::Application => self.resolve(self.application),
+            LogGroup::Iced => self.resolve(self.iced),
+            LogGroup::I18n => self.resolve(self.i18n),
+        }
+    }
 }
 
 impl Default for LogLevels {
@@ -126,6 +445,238 @@ impl Default for LogLevels {
             other: LogLevel::Default,
             iced: LogLevel::Default,
             i18n: LogLevel::Default,
+            log_to_file: None,
+            targets: BTreeMap::<String, LogLevel>::new(),
+            log_format: default_log_format(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{localisation, TempDir};
+
+    /// A configuration file path within a new empty directory of the test, which is removed when
+    /// the returned directory is dropped.
+    fn config_path(test: &str) -> (TempDir, PathBuf) {
+        let directory = TempDir::new(&format!("session_{}", test));
+        let path = directory.path().join("config.ron");
+        (directory, path)
+    }
+
+    #[test]
+    fn save_leaves_no_temporary_file() {
+        let (_directory, path) = config_path("save_leaves_no_temporary_file");
+        let mut session = Session::default();
+        session.settings.recent.push("document".to_string());
+        session.save(&path).unwrap();
+        assert!(!temporary_path(&path).exists());
+        let restored = Session::try_restore(&path).unwrap();
+        assert_eq!(restored.settings.recent, vec!["document".to_string()]);
+    }
+
+    #[test]
+    fn restore_recovers_from_half_written_file() {
+        let (_directory, path) = config_path("restore_recovers_from_half_written_file");
+        let mut session = Session::default();
+        session.settings.recent.push("document".to_string());
+        session.save(&path).unwrap();
+
+        // Simulate a crash during the next save, leaving the temporary file complete and the
+        // configuration file truncated.
+        fs::copy(&path, temporary_path(&path)).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(&path, &contents[..contents.len() / 2]).unwrap();
+        let restored = Session::try_restore(&path).unwrap();
+        assert_eq!(restored.settings.recent, vec!["document".to_string()]);
+    }
+
+    #[test]
+    fn restore_migrates_version_0() {
+        let (_directory, path) = config_path("restore_migrates_version_0");
+        fs::write(
+            &path,
+            "(settings: (ui: (language: \"en_ZA\"), log_levels: (default: Error, \
+             application: Info, other: Default, iced: Default, i18n: Default)), \
+             history: [], windows: {})",
+        )
+        .unwrap();
+        let session = Session::try_restore(&path).unwrap();
+        assert_eq!(session.version, constants::SETTINGS_VERSION);
+        assert_eq!(session.settings.ui.language, "en-ZA");
+
+        // The fields added since version 0 have their defaults.
+        assert_eq!(session.settings.theme, ThemePreference::default());
+        assert_eq!(session.settings.accent, constants::DEFAULT_ACCENT);
+        assert_eq!(session.settings.ui_scale, 1.0);
+        assert!(session.settings.recent.is_empty());
+        assert_eq!(session.settings.log_levels.log_format, constants::DEFAULT_LOG_FORMAT);
+
+        // The upgraded session is saved.
+        let saved = Session::try_read(&path).unwrap();
+        assert_eq!(saved.version, constants::SETTINGS_VERSION);
+        assert_eq!(saved.settings.ui.language, "en-ZA");
+    }
+
+    #[test]
+    fn restore_fails_without_temporary_file() {
+        let (_directory, path) = config_path("restore_fails_without_temporary_file");
+        fs::write(&path, "(version: 1, settings: (").unwrap();
+        assert!(Session::try_restore(&path).is_err());
+    }
+
+    #[test]
+    fn config_override_round_trips() {
+        use crate::application::{clap::Clap, environment::config_file};
+        use clap::Parser;
+
+        let (_directory, path) = config_path("config_override_round_trips");
+        let path = path.with_file_name("profile.ron");
+        let clap = Clap::parse_from([
+            constants::PACKAGE_NAME,
+            "--config",
+            path.to_str().unwrap(),
+        ]);
+        let path_file = config_file(&clap).unwrap();
+        assert_eq!(path_file, path);
+
+        // A missing override file is the first use, rather than an error of the path.
+        assert!(matches!(
+            Session::try_restore(&path_file),
+            Err(CoreError::NoConfigFile(_))
+        ));
+
+        let mut session = Session::default();
+        session.push_recent("document");
+        session.save(&path_file).unwrap();
+        let restored = Session::try_restore(&path_file).unwrap();
+        assert_eq!(restored.version, constants::SETTINGS_VERSION);
+        assert_eq!(restored.settings.recent, vec!["document".to_string()]);
+    }
+
+    #[test]
+    fn import_rejects_newer_version() {
+        let (_directory, path) = config_path("import_rejects_newer_version");
+        let session = Session {
+            version: constants::SETTINGS_VERSION + 1,
+            ..Default::default()
+        };
+        session.export_to(&path).unwrap();
+        let Err(error) = Session::import_from(&path) else {
+            panic!("A newer settings version was imported.");
+        };
+        assert!(matches!(
+            error,
+            CoreError::SettingsVersionUnsupported(version)
+                if version == constants::SETTINGS_VERSION + 1
+        ));
+
+        // The rejection is reported with a localised message.
+        let localisation = localisation("en-ZA");
+        let message = localisation.format_error_with_defaults(&error).unwrap().0;
+        assert!(message.contains(&(constants::SETTINGS_VERSION + 1).to_string()));
+    }
+
+    #[test]
+    fn import_migrates_older_version() {
+        let (_directory, path) = config_path("import_migrates_older_version");
+        let mut session = Session {
+            version: 0,
+            ..Default::default()
+        };
+        session.settings.ui.language = "en_ZA".to_string();
+        session.push_recent("document");
+        session.export_to(&path).unwrap();
+        let imported = Session::import_from(&path).unwrap();
+        assert_eq!(imported.version, constants::SETTINGS_VERSION);
+        assert_eq!(imported.settings.ui.language, "en-ZA");
+        assert_eq!(imported.settings.recent, vec!["document".to_string()]);
+    }
+
+    #[test]
+    fn save_backs_up_newer_file() {
+        let (_directory, path) = config_path("save_backs_up_newer_file");
+        let mut session = Session::default();
+        session.save(&path).unwrap();
+        session.save(&path).unwrap();
+        assert!(!conflict_path(&path).exists());
+
+        // Another instance saves the file after this session was saved.
+        let mut other = Session::try_restore(&path).unwrap();
+        other.push_recent("other");
+        other.save(&path).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(10))
+            .unwrap();
+        session.push_recent("document");
+        session.save(&path).unwrap();
+        let conflict = Session::try_read(&conflict_path(&path)).unwrap();
+        assert_eq!(conflict.settings.recent, vec!["other".to_string()]);
+        let saved = Session::try_read(&path).unwrap();
+        assert_eq!(saved.settings.recent, vec!["document".to_string()]);
+    }
+
+    #[test]
+    fn each_setting_yields_its_side_effects() {
+        let localisation = localisation("it");
+        let mut settings = Settings::default();
+        let effects = settings.apply_change(Setting::Language(localisation.default_language()));
+        assert_eq!(effects, vec![SideEffect::ReLocalise]);
+        assert_eq!(settings.ui.language, "it");
+
+        for log in [
+            LogSetting::LevelDefault(LogLevel::Trace),
+            LogSetting::LevelApplication(LogLevel::Debug),
+            LogSetting::LevelOther(LogLevel::Info),
+            LogSetting::LevelIced(LogLevel::Warn),
+            LogSetting::LevelI18n(LogLevel::Error),
+            LogSetting::ToFile(false),
+        ] {
+            assert_eq!(settings.apply_change(Setting::Log(log)), vec![SideEffect::UpdateLogger]);
+        }
+        assert_eq!(settings.log_levels.default, LogLevel::Trace);
+        assert_eq!(settings.log_levels.application, LogLevel::Debug);
+        assert_eq!(settings.log_levels.other, LogLevel::Info);
+        assert_eq!(settings.log_levels.iced, LogLevel::Warn);
+        assert_eq!(settings.log_levels.i18n, LogLevel::Error);
+        assert!(settings.log_levels.log_to_file.is_none());
+
+        let effects = settings.apply_change(Setting::Theme(ThemePreference::HighContrast));
+        assert_eq!(effects, vec![SideEffect::ReTheme]);
+        assert_eq!(settings.theme, ThemePreference::HighContrast);
+        let effects = settings.apply_change(Setting::Accent([0.1, 0.2, 0.3]));
+        assert_eq!(effects, vec![SideEffect::ReTheme]);
+        assert_eq!(settings.accent, [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn ui_scale_is_clamped() {
+        assert_eq!(clamp_ui_scale(1.0), 1.0);
+        assert_eq!(clamp_ui_scale(0.5), 0.5);
+        assert_eq!(clamp_ui_scale(3.0), 3.0);
+        assert_eq!(clamp_ui_scale(0.1), constants::UI_SCALE_MIN);
+        assert_eq!(clamp_ui_scale(-2.0), constants::UI_SCALE_MIN);
+        assert_eq!(clamp_ui_scale(7.5), constants::UI_SCALE_MAX);
+        assert_eq!(clamp_ui_scale(f32::INFINITY), constants::UI_SCALE_MAX);
+        assert_eq!(clamp_ui_scale(f32::NAN), 1.0);
+        assert_eq!(clamp_ui_scale(1.234), 1.23);
+
+        // Repeated zoom steps don't accumulate rounding errors.
+        let mut settings = Settings::default();
+        for _ in 0..3 {
+            settings.set_ui_scale(settings.ui_scale + constants::UI_SCALE_STEP);
+        }
+        for _ in 0..3 {
+            settings.set_ui_scale(settings.ui_scale - constants::UI_SCALE_STEP);
+        }
+        assert_eq!(settings.ui_scale, 1.0);
+        for _ in 0..100 {
+            settings.set_ui_scale(settings.ui_scale + constants::UI_SCALE_STEP);
         }
+        assert_eq!(settings.ui_scale, constants::UI_SCALE_MAX);
     }
 }
@@ -0,0 +1,172 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Single instance enforcement, where a second launch forwards its command line arguments to the
+//! running instance and exits.
+//!
+//! On Unix platforms a Unix domain socket is used, on other platforms a loopback TCP port is
+//! used. Both are derived from `APPLICATION_NAME_SHORT`.
+
+use crate::application::{clap::Clap, constants};
+use clap::Parser;
+use iced::futures::{channel::mpsc, Stream};
+use std::{
+    env, fs,
+    io::{self, BufRead, BufReader, Write},
+    sync::Mutex,
+    thread,
+};
+
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener as Listener, UnixStream as LocalStream},
+    path::PathBuf,
+};
+
+#[cfg(not(unix))]
+use std::net::{Ipv4Addr, TcpListener as Listener, TcpStream as LocalStream};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The bound listener of the running instance, taken by the `instance_requests` subscription.
+static LISTENER: Mutex<Option<Listener>> = Mutex::new(None);
+
+/// Try to become the single running instance by binding the listener.
+///
+/// Returns `Ok(true)` when this process is now the running instance. Returns `Ok(false)` when
+/// another instance is running, in which case the command line arguments have been forwarded to
+/// it, and this process should exit.
+pub fn try_acquire() -> Result<bool, io::Error> {
+    match bind() {
+        Ok(listener) => {
+            *LISTENER.lock().unwrap() = Some(listener);
+            Ok(true)
+        }
+        Err(error) if error.kind() == io::ErrorKind::AddrInUse => match connect() {
+            Ok(mut stream) => {
+                for argument in canonicalise_path(env::args().collect()) {
+                    writeln!(stream, "{}", argument)?;
+                }
+                stream.flush()?;
+                Ok(false)
+            }
+            Err(_) => {
+                // The previous instance did not exit cleanly.
+                #[cfg(unix)]
+                {
+                    std::fs::remove_file(address())?;
+                    *LISTENER.lock().unwrap() = Some(bind()?);
+                    Ok(true)
+                }
+                #[cfg(not(unix))]
+                Err(error)
+            }
+        },
+        Err(error) => Err(error),
+    }
+}
+
+/// The stream of the command line arguments forwarded by later launches of the application.
+///
+/// Does nothing if this process did not acquire the listener.
+pub fn instance_requests() -> impl Stream<Item = Vec<String>> {
+    let (sender, receiver) = mpsc::unbounded();
+    if let Some(listener) = LISTENER.lock().unwrap().take() {
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        warn!("Failed to accept instance request: {}", error);
+                        continue;
+                    }
+                };
+                let arguments = BufReader::new(stream)
+                    .lines()
+                    .map_while(Result::ok)
+                    .collect::<Vec<String>>();
+                if sender.unbounded_send(arguments).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    receiver
+}
+
+/// Remove the socket file of the running instance, as part of exiting.
+pub fn release() {
+    #[cfg(unix)]
+    let _ = std::fs::remove_file(address());
+}
+
+#[cfg(unix)]
+fn address() -> PathBuf {
+    let name = format!(
+        "{}.sock",
+        constants::APPLICATION_NAME_SHORT.replace(' ', "_").to_lowercase()
+    );
+    dirs::runtime_dir().unwrap_or_else(std::env::temp_dir).join(name)
+}
+
+#[cfg(not(unix))]
+fn address() -> (Ipv4Addr, u16) {
+    // Derive a port in the dynamic range from the application name. The FNV-1a hash is used, as
+    // the port must be the same for every build of the application.
+    let hash = constants::APPLICATION_NAME_SHORT
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    (Ipv4Addr::LOCALHOST, 49152 + (hash % 16384) as u16)
+}
+
+fn bind() -> Result<Listener, io::Error> {
+    Listener::bind(address())
+}
+
+fn connect() -> Result<LocalStream, io::Error> {
+    LocalStream::connect(address())
+}
+
+/// Replace the path argument with its canonical path, as the running instance has its own
+/// working directory. A path that does not exist yet is made absolute instead.
+fn canonicalise_path(mut arguments: Vec<String>) -> Vec<String> {
+    let Some(path) = Clap::try_parse_from(&arguments).ok().and_then(|clap| clap.path) else {
+        return arguments;
+    };
+    let Ok(canonical) = fs::canonicalize(&path)
+        .or_else(|_| env::current_dir().map(|directory| directory.join(&path)))
+    else {
+        return arguments;
+    };
+    if let Some(position) = arguments.iter().rposition(|argument| *argument == path) {
+        arguments[position] = canonical.to_string_lossy().to_string();
+    }
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_argument_is_canonicalised() {
+        let arguments = canonicalise_path(vec![
+            "iced_af".to_string(),
+            "--log-level".to_string(),
+            "debug".to_string(),
+            "document.txt".to_string(),
+        ]);
+        let expected = std::env::current_dir().unwrap().join("document.txt");
+        assert_eq!(arguments[..3], ["iced_af", "--log-level", "debug"]);
+        assert_eq!(arguments[3], expected.to_string_lossy());
+    }
+
+    #[test]
+    fn arguments_without_path_are_unchanged() {
+        let arguments = vec!["iced_af".to_string(), "--defaults".to_string()];
+        assert_eq!(canonicalise_path(arguments.clone()), arguments);
+    }
+}
@@ -0,0 +1,163 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The user interface theme preference, and the detection of the operating system's theme.
+
+use crate::application::constants;
+use core::fmt::{Display, Formatter, Result as FormatterResult};
+use dark_light::Mode;
+use iced::{
+    futures::{channel::mpsc, Stream},
+    theme::Palette,
+    Color, Theme,
+};
+use serde::{Deserialize, Serialize};
+use std::thread;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash, Deserialize, Serialize)]
+pub enum ThemePreference {
+    Light,
+    Dark,
+    #[default]
+    System, // Follow the operating system's theme.
+    HighContrast, // White text on black, for accessibility.
+}
+
+/// The palette of the high contrast theme. White text on black has the maximal contrast ratio of
+/// 21:1, and the remaining colours are at least 7:1 against the background, thus focus outlines
+/// and selections are clearly visible.
+const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    background: Color::BLACK,
+    text: Color::WHITE,
+    primary: Color::from_rgb(1.0, 1.0, 0.0),
+    success: Color::from_rgb(0.0, 1.0, 0.0),
+    danger: Color::from_rgb(1.0, 0.5, 0.5),
+};
+
+impl ThemePreference {
+    /// Obtain the `iced` theme for the preference, using the detected operating system's theme
+    /// mode for `System`.
+    pub fn to_theme(&self, system_mode: Mode) -> Theme {
+        match self {
+            ThemePreference::Light => Theme::Light,
+            ThemePreference::Dark => Theme::Dark,
+            ThemePreference::System => match system_mode {
+                Mode::Dark => Theme::Dark,
+                Mode::Light | Mode::Default => Theme::Light,
+            },
+            ThemePreference::HighContrast => {
+                Theme::custom("High contrast".to_string(), HIGH_CONTRAST_PALETTE)
+            }
+        }
+    }
+
+    /// The multiplier of the border widths, which thickens the borders of the high contrast theme.
+    pub fn border_scale(&self) -> f32 {
+        match self {
+            ThemePreference::HighContrast => constants::HIGH_CONTRAST_BORDER_SCALE,
+            _ => 1.0,
+        }
+    }
+}
+
+impl Display for ThemePreference {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatterResult {
+        match self {
+            ThemePreference::Light => write!(formatter, "light"),
+            ThemePreference::Dark => write!(formatter, "dark"),
+            ThemePreference::System => write!(formatter, "system"),
+            ThemePreference::HighContrast => write!(formatter, "high_contrast"),
+        }
+    }
+}
+
+/// Apply the sRGB accent colour as the primary colour of the theme's palette.
+///
+/// The default accent is the primary colour of `iced`'s built-in themes, thus the theme is
+/// returned unchanged. The high contrast theme is also returned unchanged, as an accent may lower
+/// its contrast.
+pub fn with_accent(theme: Theme, accent: [f32; 3]) -> Theme {
+    if accent == constants::DEFAULT_ACCENT || theme.palette() == HIGH_CONTRAST_PALETTE {
+        return theme;
+    }
+    let palette = Palette {
+        primary: accent_color(accent),
+        ..theme.palette()
+    };
+    Theme::custom(format!("{} accent", theme), palette)
+}
+
+/// Convert the sRGB accent colour to `iced` colour.
+pub fn accent_color(accent: [f32; 3]) -> Color {
+    Color::from_rgb(accent[0], accent[1], accent[2])
+}
+
+/// Detect the operating system's theme mode.
+pub fn detect_system_mode() -> Mode {
+    dark_light::detect()
+}
+
+/// The stream of the operating system's theme mode changes, for use with
+/// `Subscription::run()`.
+///
+/// `dark-light` has no change notifications, thus the mode is polled on a separate thread every
+/// `SYSTEM_THEME_POLL_INTERVAL`. The thread ends once the subscription has been dropped.
+pub fn system_mode_changes() -> impl Stream<Item = Mode> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        let mut mode = detect_system_mode();
+        while !sender.is_closed() {
+            thread::sleep(constants::SYSTEM_THEME_POLL_INTERVAL);
+            let detected = detect_system_mode();
+            if detected != mode {
+                mode = detected;
+                if sender.unbounded_send(mode).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The relative luminance of the sRGB colour, as defined by WCAG 2.
+    fn luminance(color: Color) -> f32 {
+        let linear = |channel: f32| match channel <= 0.04045 {
+            true => channel / 12.92,
+            false => ((channel + 0.055) / 1.055).powf(2.4),
+        };
+        0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+    }
+
+    /// The WCAG 2 contrast ratio of the colours, ranging from 1 to 21.
+    fn contrast_ratio(first: Color, second: Color) -> f32 {
+        let (first, second) = (luminance(first), luminance(second));
+        (first.max(second) + 0.05) / (first.min(second) + 0.05)
+    }
+
+    #[test]
+    fn high_contrast_palette_exceeds_threshold() {
+        let theme = ThemePreference::HighContrast.to_theme(Mode::Light);
+        let palette = theme.palette();
+        assert_eq!(palette, HIGH_CONTRAST_PALETTE);
+        assert!(contrast_ratio(palette.text, palette.background) > 20.9);
+
+        // WCAG AAA level for normal text.
+        for color in [palette.primary, palette.success, palette.danger] {
+            let ratio = contrast_ratio(color, palette.background);
+            assert!(ratio >= 7.0, "{:?}: {}", color, ratio);
+        }
+
+        // The accent doesn't lower the contrast, and the borders are thicker.
+        assert_eq!(with_accent(theme, [1.0, 0.0, 0.0]).palette(), HIGH_CONTRAST_PALETTE);
+        let border_scale = ThemePreference::HighContrast.border_scale();
+        assert!(border_scale > ThemePreference::Dark.border_scale());
+    }
+}
@@ -0,0 +1,47 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The timers used by subscriptions, such as for autosave and the expiry of windows and toasts.
+//!
+//! Note: `iced::time::every()` requires one of the async runtime features of `iced`, thus threads
+//! are used for the timers instead. The timer threads are only started once the streams are
+//! polled, as the subscriptions recreate the streams on every update.
+
+use iced::futures::{
+    channel::{mpsc, oneshot},
+    stream, Stream, StreamExt,
+};
+use std::{thread, time::Duration};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// A stream that produces a tick every interval, until the stream is dropped.
+pub fn ticks(interval: Duration) -> impl Stream<Item = ()> {
+    stream::once(async move {
+        let (sender, receiver) = mpsc::unbounded();
+        thread::spawn(move || {
+            while !sender.is_closed() {
+                thread::sleep(interval);
+                if sender.unbounded_send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    })
+    .flatten()
+}
+
+/// A stream that produces the value once, after the duration has passed.
+pub fn after<T: Send + 'static>(duration: Duration, value: T) -> impl Stream<Item = T> {
+    stream::once(async move {
+        let (sender, receiver) = oneshot::channel();
+        thread::spawn(move || {
+            thread::sleep(duration);
+            let _ = sender.send(());
+        });
+        let _ = receiver.await;
+        value
+    })
+}
@@ -0,0 +1,61 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Toasts are short lived notifications displayed on top of the focused window, such as for
+//! recoverable errors that do not require the FatalError window.
+
+use crate::{
+    application::Message,
+    widget::toast::{Item, Severity, Toasts},
+};
+use iced::Element;
+use std::collections::VecDeque;
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ToastLevel {
+    Information,
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+pub struct Toast {
+    pub id: usize, // Unique identifier, used for dismissing the toast.
+    pub level: ToastLevel,
+    pub message: RefCount<String>,
+}
+
+impl From<ToastLevel> for Severity {
+    fn from(level: ToastLevel) -> Self {
+        match level {
+            ToastLevel::Information => Severity::Information,
+            ToastLevel::Warning => Severity::Warning,
+            ToastLevel::Error => Severity::Error,
+        }
+    }
+}
+
+/// The toasts displayed on top of the content, placed at the bottom end of the window.
+pub fn view<'a>(content: Element<'a, Message>, toasts: &VecDeque<Toast>) -> Element<'a, Message> {
+    let items = toasts
+        .iter()
+        .map(|toast| Item {
+            id: toast.id,
+            severity: toast.level.into(),
+            message: toast.message.to_string(),
+        })
+        .collect();
+    Toasts::new(content, items)
+        .on_dismiss(Message::DismissToast)
+        .into()
+}
@@ -0,0 +1,118 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The system tray icon, with a menu to show the windows, open the Preferences window, and quit.
+//! The icon is only created when the `tray` feature is enabled.
+//!
+//! The menu actions are delivered as `Message::Tray`, independent of the platform. Platform
+//! caveats of the `tray-icon` crate:
+//!
+//! * Linux: the icon requires GTK and `libappindicator` (or `libayatana-appindicator`), and is
+//!   only displayed while a GTK event loop is running on the thread that created the icon, which
+//!   `iced` does not provide.
+//!
+//! * macOS: the icon must be created on the main thread, which is the case as it is created in
+//!   `State::try_new()`.
+//!
+//! * Windows: no caveats.
+
+#[cfg(feature = "tray")]
+use crate::{application::constants, core::localisation::Localisation};
+#[cfg(feature = "tray")]
+use iced::futures::{channel::mpsc, Stream};
+#[cfg(feature = "tray")]
+use std::thread;
+#[cfg(feature = "tray")]
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    Icon, TrayIcon, TrayIconBuilder,
+};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The actions of the tray icon's menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    Show,        // Show the hidden windows, and focus the main windows.
+    Preferences, // Open the Preferences window.
+    Quit,        // Save settings and exit.
+}
+
+#[cfg(feature = "tray")]
+const ICON_SIZE: u32 = 32; // The width and height of the generated icon.
+
+/// Create the tray icon, using the localised labels for the menu. A failure is only logged, as the
+/// application is still usable without the tray icon.
+#[cfg(feature = "tray")]
+pub fn new(localisation: &Localisation, accent: [f32; 3]) -> Option<TrayIcon> {
+    let label = |identifier: &str| match localisation.literal_with_defaults("word", identifier) {
+        Ok(value) => value.0.to_string(),
+        Err(error) => {
+            warn!("Failed to localise the tray menu: {}", error);
+            identifier.to_string()
+        }
+    };
+    let menu = Menu::new();
+    let items = menu.append_items(&[
+        &MenuItem::with_id("show", label("show_i"), true, None),
+        &MenuItem::with_id("preferences", label("preferences_i"), true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id("quit", label("exit_i"), true, None),
+    ]);
+    if let Err(error) = items {
+        warn!("Failed to create the tray menu: {}", error);
+        return None;
+    }
+
+    // A filled circle of the accent colour.
+    let colour = accent.map(|component| (component * 255.0) as u8);
+    let centre = ICON_SIZE as f32 / 2.0;
+    let mut rgba = Vec::<u8>::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let distance = (x as f32 + 0.5 - centre).hypot(y as f32 + 0.5 - centre);
+            let alpha = if distance <= centre { 255 } else { 0 };
+            rgba.extend_from_slice(&[colour[0], colour[1], colour[2], alpha]);
+        }
+    }
+    let icon = match Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE) {
+        Ok(value) => value,
+        Err(error) => {
+            warn!("Failed to create the tray icon image: {}", error);
+            return None;
+        }
+    };
+    match TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip(constants::APPLICATION_NAME)
+        .with_icon(icon)
+        .build()
+    {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Failed to create the tray icon: {}", error);
+            None
+        }
+    }
+}
+
+/// The stream of the actions selected in the tray icon's menu.
+#[cfg(feature = "tray")]
+pub fn tray_events() -> impl Stream<Item = TrayAction> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        while let Ok(event) = MenuEvent::receiver().recv() {
+            let action = match event.id.as_ref() {
+                "show" => TrayAction::Show,
+                "preferences" => TrayAction::Preferences,
+                "quit" => TrayAction::Quit,
+                _ => continue,
+            };
+            if sender.unbounded_send(action).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
@@ -0,0 +1,91 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Checking for updates, by comparing the version of the release manifest at
+//! `constants::UPDATE_MANIFEST_URL` with the application's version. The check is only available
+//! when the `updates` feature is enabled, and is performed with `Task::perform()` off the UI
+//! thread.
+//!
+//! The release manifest is a JSON object containing at least the version of the latest release:
+//!
+//! ```json
+//! { "version": "0.5.0" }
+//! ```
+
+#[cfg(feature = "updates")]
+use crate::application::constants::{UPDATE_MANIFEST_URL, VERSION};
+use serde::Deserialize;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The release manifest, additional fields are ignored.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub version: String, // Semantic version, optionally prefixed with `v`.
+}
+
+/// The result of checking for updates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    Available(String), // The version of the newer release.
+    UpToDate,
+}
+
+/// Fetch the release manifest, and compare its version with the application's version. The error
+/// is the message of the failure, as the `reqwest` errors are not `Clone`.
+#[cfg(feature = "updates")]
+pub async fn check() -> Result<UpdateStatus, String> {
+    debug!("Checking for updates at ‘{}’", UPDATE_MANIFEST_URL);
+    let manifest = reqwest::get(UPDATE_MANIFEST_URL)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| error.to_string())?
+        .json::<Manifest>()
+        .await
+        .map_err(|error| error.to_string())?;
+    compare(&manifest, VERSION)
+}
+
+/// Compare the version of the release manifest with the current version.
+#[cfg(feature = "updates")]
+pub fn compare(manifest: &Manifest, current: &str) -> Result<UpdateStatus, String> {
+    let parse = |version: &str| {
+        semver::Version::parse(version.trim().trim_start_matches('v'))
+            .map_err(|error| format!("‘{}’: {}", version, error))
+    };
+    match parse(manifest.version.as_str())? > parse(current)? {
+        true => Ok(UpdateStatus::Available(manifest.version.clone())),
+        false => Ok(UpdateStatus::UpToDate),
+    }
+}
+
+#[cfg(all(test, feature = "updates"))]
+mod tests {
+    use super::*;
+
+    /// The release manifest of the version, with an additional field.
+    fn manifest(version: &str) -> Manifest {
+        let json = format!(r#"{{ "version": "{}", "notes": "Release notes." }}"#, version);
+        serde_json::from_str(json.as_str()).unwrap()
+    }
+
+    #[test]
+    fn compare_versions() {
+        assert_eq!(
+            compare(&manifest("0.5.0"), "0.4.2"),
+            Ok(UpdateStatus::Available("0.5.0".to_string()))
+        );
+        assert_eq!(
+            compare(&manifest("v0.4.10"), "0.4.9"),
+            Ok(UpdateStatus::Available("v0.4.10".to_string()))
+        );
+        assert_eq!(compare(&manifest("0.4.2"), "0.4.2"), Ok(UpdateStatus::UpToDate));
+        assert_eq!(compare(&manifest("0.4.1"), "0.4.2"), Ok(UpdateStatus::UpToDate));
+
+        // A pre-release is older than its release.
+        assert_eq!(compare(&manifest("0.5.0-beta.1"), "0.5.0"), Ok(UpdateStatus::UpToDate));
+        assert!(compare(&manifest("latest"), "0.4.2").is_err());
+        assert!(compare(&manifest("0.5.0"), VERSION).is_ok());
+    }
+}
@@ -5,7 +5,12 @@
 //!
 //! No alteration for these should be required.
 
+pub mod embedded_provider;
 pub mod error;
 pub mod localisation;
 pub mod state;
 pub mod traits;
+pub mod undo;
+
+#[cfg(test)]
+pub mod testing;
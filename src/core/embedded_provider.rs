@@ -0,0 +1,168 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! A minimal English string set compiled into the application, used by `Localisation::try_new()`
+//! when the localisation database is missing, such as an incomplete installation. Only the strings
+//! of the FatalError window, and the error reporting the missing database, are provided.
+
+use i18n::{
+    provider::{
+        ComponentDetails, IdentifierDetails, LanguageData, LocalisationProviderTrait,
+        ProviderError, RepositoryDetails,
+    },
+    utility::{LanguageTag, LanguageTagRegistry, RegistryError},
+};
+use std::collections::HashMap;
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+const LANGUAGE: &str = "en-ZA"; // Same as the default language of the localisation database.
+
+/// The embedded strings as (component, identifier, pattern).
+const STRINGS: [(&str, &str, &str); 9] = [
+    ("application", "window_title_format", "{application} - {window}"),
+    ("application", "fatal_error", "Fatal error"),
+    ("application", "uncaught_error", "The following error was not caught: '{error}'"),
+    ("application", "copy_details", "Copy details"),
+    ("application", "quit_macos", "Quit {short_name}"),
+    (
+        "application",
+        "localisation_missing",
+        "The localisation database is missing from ‘{path}’. Please reinstall the application.",
+    ),
+    ("word", "exit_i", "Exit"),
+    ("i18n_localiser", "error_format_enum", "{type}::{variant}: ‘{message}’."),
+    ("i18n_localiser", "error_format_enum_embedded", "{type}::{variant}: [{error}]."),
+];
+
+pub struct EmbeddedProvider {
+    language_tag: RefCount<LanguageTag>,
+}
+
+impl EmbeddedProvider {
+    pub fn try_new(
+        language_tag_registry: &RefCount<LanguageTagRegistry>,
+    ) -> Result<Self, RegistryError> {
+        Ok(EmbeddedProvider {
+            language_tag: language_tag_registry.tag(LANGUAGE)?,
+        })
+    }
+
+    /// The embedded pattern of the identifier within the component, if any.
+    fn pattern(&self, component: &str, identifier: &str) -> Option<(String, RefCount<LanguageTag>)> {
+        STRINGS
+            .iter()
+            .find(|(string_component, string_identifier, _pattern)| {
+                *string_component == component && *string_identifier == identifier
+            })
+            .map(|(_component, _identifier, pattern)| {
+                (pattern.to_string(), RefCount::clone(&self.language_tag))
+            })
+    }
+
+    /// The single language of the embedded strings, with the number of strings.
+    fn languages(&self, count: usize) -> HashMap<RefCount<LanguageTag>, LanguageData> {
+        let mut languages = HashMap::<RefCount<LanguageTag>, LanguageData>::new();
+        languages.insert(
+            RefCount::clone(&self.language_tag),
+            LanguageData {
+                count,
+                ratio: 1.0,
+                contributors: Vec::<String>::new(),
+            },
+        );
+        languages
+    }
+}
+
+impl LocalisationProviderTrait for EmbeddedProvider {
+    // Only the one language is available, thus it is the closest match for any language tag.
+    fn string(
+        &self,
+        component: &str,
+        identifier: &str,
+        _language_tag: &RefCount<LanguageTag>,
+    ) -> Result<Option<(String, RefCount<LanguageTag>)>, ProviderError> {
+        Ok(self.pattern(component, identifier))
+    }
+
+    fn string_exact_match(
+        &self,
+        component: &str,
+        identifier: &str,
+        language_tag: &RefCount<LanguageTag>,
+    ) -> Result<Option<(String, RefCount<LanguageTag>)>, ProviderError> {
+        if *language_tag != self.language_tag {
+            return Ok(None);
+        }
+        Ok(self.pattern(component, identifier))
+    }
+
+    fn strings(
+        &self,
+        component: &str,
+        identifier: &str,
+        language_tag: &RefCount<LanguageTag>,
+    ) -> Result<Vec<(String, RefCount<LanguageTag>)>, ProviderError> {
+        Ok(self
+            .string(component, identifier, language_tag)?
+            .into_iter()
+            .collect())
+    }
+
+    fn identifier_details(
+        &self,
+        component: &str,
+        _identifier: &str,
+    ) -> Result<IdentifierDetails, ProviderError> {
+        if !STRINGS.iter().any(|(string_component, _, _)| *string_component == component) {
+            return Err(ProviderError::ComponentNotFound(component.to_string()));
+        }
+        Ok(IdentifierDetails {
+            languages: vec![RefCount::clone(&self.language_tag)],
+            default: RefCount::clone(&self.language_tag),
+        })
+    }
+
+    fn component_details(
+        &self,
+        component: &str,
+    ) -> Result<RefCount<ComponentDetails>, ProviderError> {
+        let count = STRINGS
+            .iter()
+            .filter(|(string_component, _, _)| *string_component == component)
+            .count();
+        if count == 0 {
+            return Err(ProviderError::ComponentNotFound(component.to_string()));
+        }
+        Ok(RefCount::new(ComponentDetails {
+            languages: self.languages(count),
+            default: RefCount::clone(&self.language_tag),
+            total_strings: count,
+        }))
+    }
+
+    fn repository_details(&self) -> Result<RefCount<RepositoryDetails>, ProviderError> {
+        let mut components = Vec::<String>::new();
+        for (component, _, _) in STRINGS.iter() {
+            if !components.iter().any(|existing| existing == component) {
+                components.push(component.to_string());
+            }
+        }
+        Ok(RefCount::new(RepositoryDetails {
+            languages: self.languages(STRINGS.len()),
+            default: Some(RefCount::clone(&self.language_tag)),
+            total_strings: STRINGS.len(),
+            components,
+            contributors: Vec::<String>::new(),
+        }))
+    }
+}
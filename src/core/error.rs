@@ -5,7 +5,7 @@
 //!
 //! Add new errors to `ApplicationError` in the `src/application/error.rs` file.
 
-use crate::application::WindowType;
+use crate::application::{constants::SETTINGS_VERSION, WindowType};
 use core::fmt::{Display, Formatter, Result};
 use i18n::{
     lexer::IcuError,
@@ -18,6 +18,10 @@ use i18n::{
     },
 };
 use iced::window;
+use icu_calendar::CalendarError;
+use icu_datetime::DateTimeError;
+use icu_decimal::DecimalError;
+use icu_plurals::PluralsError;
 use rusqlite::Error as Sqlite3Error;
 use std::{error::Error, fmt::Debug, io::Error as IoError, path::PathBuf};
 use ron::error::{Error as RonError, SpannedError};
@@ -40,19 +44,29 @@ pub enum CoreError {
     Provider(ProviderError),
     ProviderSqlite3(ProviderSqlite3Error),
     Icu(IcuError),
+    IcuDecimal(DecimalError),
+    IcuDateTime(DateTimeError),
+    IcuCalendar(CalendarError),
+    IcuPlurals(PluralsError),
     Sqlite3(RefCount<Sqlite3Error>),
     Io(String), // Can't clone io::Error, as it is an OS error, thus converted to final String (can't be translated).
     ApplicationPath,
     ConfigDirNotFound,
+    DataDirNotFound,
     NoVendorDir(PathBuf),
     NoConfigFile(PathBuf),
     WindowIdNotFound(window::Id, String),
     WindowTypeNotFound(WindowType, String),
     ExpectedWindowParent(WindowType),
+    InvalidReparent(window::Id, window::Id),
     LanguageTagNotSupported(String),
     InvalidWindowTypeMain(WindowType),
     StateNotReusable(WindowType),
     PlaceholderNotFound(WindowType),
+    SettingsVersionUnsupported(u32),
+    UpdateCheckFailed(String),
+    WindowLimitReached(WindowType, usize),
+    LocalisationMissing(PathBuf),
 }
 
 impl LocalisationErrorTrait for CoreError {}
@@ -200,6 +214,78 @@ impl LocalisationTrait for CoreError {
                     values: Some(values),
                 }
             }
+            CoreError::IcuDecimal(ref error) => {
+                // Currently no localisation is available for this error type: DecimalError.
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("IcuDecimal".to_string()),
+                );
+                values.insert(
+                    "error".to_string(),
+                    PlaceholderValue::String(error.to_string()),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum_embedded".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::IcuDateTime(ref error) => {
+                // Currently no localisation is available for this error type: DateTimeError.
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("IcuDateTime".to_string()),
+                );
+                values.insert(
+                    "error".to_string(),
+                    PlaceholderValue::String(error.to_string()),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum_embedded".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::IcuCalendar(ref error) => {
+                // Currently no localisation is available for this error type: CalendarError.
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("IcuCalendar".to_string()),
+                );
+                values.insert(
+                    "error".to_string(),
+                    PlaceholderValue::String(error.to_string()),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum_embedded".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::IcuPlurals(ref error) => {
+                // Currently no localisation is available for this error type: PluralsError.
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("IcuPlurals".to_string()),
+                );
+                values.insert(
+                    "error".to_string(),
+                    PlaceholderValue::String(error.to_string()),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum_embedded".to_string(),
+                    values: Some(values),
+                }
+            }
             CoreError::Io(ref error) => {
                 // Currently no localisation is available for this error type: IoError (always a String).
                 let mut values = HashMap::<String, PlaceholderValue>::new();
@@ -262,6 +348,28 @@ impl LocalisationTrait for CoreError {
                     values: Some(values),
                 }
             }
+            CoreError::DataDirNotFound => {
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "data_directory_not_found".to_string(),
+                    values: None,
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("DataDirNotFound".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
             CoreError::NoVendorDir(ref path) => {
                 let mut message_values = HashMap::<String, PlaceholderValue>::new();
                 message_values.insert(
@@ -405,6 +513,37 @@ impl LocalisationTrait for CoreError {
                     values: Some(values),
                 }
             }
+            CoreError::InvalidReparent(ref id, ref new_parent) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "id".to_string(),
+                    PlaceholderValue::String(format!("{:?}", id)),
+                );
+                message_values.insert(
+                    "parent".to_string(),
+                    PlaceholderValue::String(format!("{:?}", new_parent)),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "invalid_reparent".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("InvalidReparent".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
             CoreError::LanguageTagNotSupported(ref tag) => {
                 let mut message_values = HashMap::<String, PlaceholderValue>::new();
                 message_values.insert("tag".to_string(), PlaceholderValue::String(tag.to_string()));
@@ -510,6 +649,122 @@ impl LocalisationTrait for CoreError {
                     values: Some(values),
                 }
             }
+            CoreError::SettingsVersionUnsupported(ref version) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "version".to_string(),
+                    PlaceholderValue::String(version.to_string()),
+                );
+                message_values.insert(
+                    "supported".to_string(),
+                    PlaceholderValue::String(SETTINGS_VERSION.to_string()),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "settings_version_unsupported".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("SettingsVersionUnsupported".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::UpdateCheckFailed(ref error) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "error".to_string(),
+                    PlaceholderValue::String(error.to_string()),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "update_check_failed".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("UpdateCheckFailed".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::WindowLimitReached(ref window_type, ref limit) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "type".to_string(),
+                    PlaceholderValue::String(window_type.as_str().to_string()),
+                );
+                message_values.insert(
+                    "limit".to_string(),
+                    PlaceholderValue::Unsigned(*limit as u128),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "window_limit_reached".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("WindowLimitReached".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
+            CoreError::LocalisationMissing(ref path) => {
+                let mut message_values = HashMap::<String, PlaceholderValue>::new();
+                message_values.insert(
+                    "path".to_string(),
+                    PlaceholderValue::String(path.display().to_string()),
+                );
+                let message = LocalisationData {
+                    component: "application".to_string(),
+                    identifier: "localisation_missing".to_string(),
+                    values: Some(message_values),
+                };
+                let mut values = HashMap::<String, PlaceholderValue>::new();
+                values.insert("type".to_string(), type_string);
+                values.insert(
+                    "variant".to_string(),
+                    PlaceholderValue::String("LocalisationMissing".to_string()),
+                );
+                values.insert(
+                    "message".to_string(),
+                    PlaceholderValue::LocalisationData(message),
+                );
+                LocalisationData {
+                    component: "i18n_localiser".to_string(),
+                    identifier: "error_format_enum".to_string(),
+                    values: Some(values),
+                }
+            }
         }
     }
 }
@@ -524,6 +779,10 @@ impl Display for CoreError {
             CoreError::Provider(ref error) => Display::fmt(&error, formatter),
             CoreError::ProviderSqlite3(ref error) => Display::fmt(&error, formatter),
             CoreError::Icu(ref error) => Display::fmt(&error, formatter),
+            CoreError::IcuDecimal(ref error) => Display::fmt(&error, formatter),
+            CoreError::IcuDateTime(ref error) => Display::fmt(&error, formatter),
+            CoreError::IcuCalendar(ref error) => Display::fmt(&error, formatter),
+            CoreError::IcuPlurals(ref error) => Display::fmt(&error, formatter),
             CoreError::Sqlite3(ref error) => Display::fmt(&error, formatter),
             CoreError::Io(ref error) => Display::fmt(&error, formatter),
             CoreError::ApplicationPath => {
@@ -533,6 +792,10 @@ impl Display for CoreError {
                 formatter,
                 "Failed to retrieve the user's configuration path."
             ),
+            CoreError::DataDirNotFound => write!(
+                formatter,
+                "Failed to retrieve the user's data path."
+            ),
             CoreError::NoVendorDir(ref path) => write!(
                 formatter,
                 "The vendor directory ‘{}’ does not exist.",
@@ -541,6 +804,11 @@ impl Display for CoreError {
             CoreError::NoConfigFile(ref path) => {
                 write!(formatter, "The file ‘{}’ does not exist.", path.display())
             }
+            CoreError::LocalisationMissing(ref path) => write!(
+                formatter,
+                "The localisation database is missing from ‘{}’.",
+                path.display()
+            ),
             CoreError::WindowIdNotFound(ref id, field) => write!(
                 formatter,
                 "The window Id ‘{:?}’ was not found in the struct field ‘{:?}’.",
@@ -556,6 +824,11 @@ impl Display for CoreError {
                 "Expected to get the parent window for the window type ‘{:?}’.",
                 window_type
             ),
+            CoreError::InvalidReparent(ref id, ref new_parent) => write!(
+                formatter,
+                "The window Id ‘{:?}’ can't be moved on top of the window Id ‘{:?}’.",
+                id, new_parent
+            ),
             CoreError::LanguageTagNotSupported(ref tag) => write!(
                 formatter,
                 "The language tag ‘{}’ is supported for the application's user interface.",
@@ -576,6 +849,21 @@ impl Display for CoreError {
                 "The placeholder of window type ‘{:?}’ is not found.",
                 window_type
             ),
+            CoreError::SettingsVersionUnsupported(ref version) => write!(
+                formatter,
+                "The settings version ‘{}’ is newer than the supported version ‘{}’.",
+                version, SETTINGS_VERSION
+            ),
+            CoreError::UpdateCheckFailed(ref error) => write!(
+                formatter,
+                "Failed to check for updates: {}",
+                error
+            ),
+            CoreError::WindowLimitReached(ref window_type, ref limit) => write!(
+                formatter,
+                "The limit of {} opened windows of the window type ‘{:?}’ is reached.",
+                limit, window_type
+            ),
         }
     }
 }
@@ -625,6 +913,30 @@ impl From<IcuError> for CoreError {
     }
 }
 
+impl From<DecimalError> for CoreError {
+    fn from(error: DecimalError) -> CoreError {
+        CoreError::IcuDecimal(error)
+    }
+}
+
+impl From<DateTimeError> for CoreError {
+    fn from(error: DateTimeError) -> CoreError {
+        CoreError::IcuDateTime(error)
+    }
+}
+
+impl From<CalendarError> for CoreError {
+    fn from(error: CalendarError) -> CoreError {
+        CoreError::IcuCalendar(error)
+    }
+}
+
+impl From<PluralsError> for CoreError {
+    fn from(error: PluralsError) -> CoreError {
+        CoreError::IcuPlurals(error)
+    }
+}
+
 impl From<RegistryError> for CoreError {
     fn from(error: RegistryError) -> CoreError {
         CoreError::LanguageTagRegistry(error)
@@ -7,20 +7,30 @@
 
 use crate::{
     application::{environment::Environment, StringGroup},
-    core::{error::CoreError, traits::AnyLocalisedTrait},
+    localisation::try_strings,
+    core::{embedded_provider::EmbeddedProvider, error::CoreError, traits::AnyLocalisedTrait},
 };
 use i18n::{
     lexer::{DataProvider, IcuDataProvider},
-    localiser::{CommandRegistry, Localiser},
-    provider::RepositoryDetails,
+    localiser::{CommandRegistry, Localiser, LocaliserError},
+    provider::{LocalisationProviderTrait, RepositoryDetails},
     provider_sqlite3::LocalisationProviderSqlite3,
     utility::{
         Direction, LanguageTag, LanguageTagRegistry, LocalisationData, LocalisationErrorTrait,
         PlaceholderValue, ScriptDirection,
     },
 };
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use fixed_decimal::FixedDecimal;
 use iced::Alignment;
-use std::collections::HashMap;
+use icu_calendar::DateTime;
+use icu_datetime::{options::length, DateTimeFormatter};
+use icu_decimal::{options::FixedDecimalFormatterOptions, FixedDecimalFormatter};
+use icu_plurals::{PluralCategory, PluralRules};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
@@ -42,6 +52,9 @@ use std::sync::Arc as RefCount;
 /// in the window's state.
 pub struct StringCache {
     cache: HashMap<StringGroup, Box<dyn AnyLocalisedTrait>>,
+
+    // The fallback chain used for the cached strings.
+    fallback_chain: Vec<RefCount<LanguageTag>>,
 }
 
 impl StringCache {
@@ -49,14 +62,22 @@ impl StringCache {
     pub fn new() -> StringCache {
         StringCache {
             cache: HashMap::<StringGroup, Box<dyn AnyLocalisedTrait>>::new(),
+            fallback_chain: Vec::<RefCount<LanguageTag>>::new(),
         }
     }
 
     /// Attempts to update the localised string to the selected language
-    /// contained in `Localisation`.
+    /// contained in `Localisation`. All the strings are reloaded when the fallback chain has
+    /// changed.
     pub fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        let reload = self.fallback_chain != *localisation.fallback_chain();
+        self.fallback_chain = localisation.fallback_chain().clone();
         for (string_group, strings) in self.cache.iter_mut() {
-            strings.try_update(localisation)?;
+            if reload {
+                strings.try_reload(localisation)?;
+            } else {
+                strings.try_update(localisation)?;
+            }
             trace!(
                 "try_update(): Updated strings for string group ‘{:?}’: {:?}",
                 string_group,
@@ -80,10 +101,107 @@ impl StringCache {
         let _ = self.cache.insert(string_group, localised_strings);
     }
 
+    /// Insert the localised strings of the `StringGroup` into the cache, if not already cached.
+    /// The strings are created using `localisation::try_strings()`.
+    pub fn try_ensure(
+        &mut self,
+        string_group: StringGroup,
+        localisation: &Localisation,
+    ) -> Result<(), CoreError> {
+        if !self.exists(&string_group) {
+            let strings = try_strings(&string_group, localisation)?;
+            self.insert(string_group, strings);
+        }
+        Ok(())
+    }
+
     /// Get a reference to the localised strings for the specified `StringGroup`.
     pub fn get(&self, string_group: &StringGroup) -> Option<&Box<dyn AnyLocalisedTrait>> {
         self.cache.get(string_group)
     }
+
+    /// Get the window title of the `StringGroup`, for the `WindowTrait::title()` of windows having
+    /// a constant title. Being read from the cache, the title is always of the current language.
+    ///
+    /// Panics if the `StringGroup` is not cached, as `display()` of the window ensures it is.
+    pub fn title(&self, string_group: &StringGroup) -> &String {
+        self.cache.get(string_group).unwrap().title()
+    }
+}
+
+/// The left and right pointing variants of the directional glyphs, see
+/// `Localisation::mirror_icon()`.
+const MIRRORED_GLYPHS: [(char, char); 7] = [
+    ('←', '→'),
+    ('◀', '▶'),
+    ('◂', '▸'),
+    ('‹', '›'),
+    ('«', '»'),
+    ('⟨', '⟩'),
+    ('❮', '❯'),
+];
+
+/// The script of the language subtag, for the languages whose script is not covered by the
+/// default font, see `preferred_font()`.
+const LANGUAGE_SCRIPTS: [(&str, &str); 14] = [
+    ("ar", "Arab"),
+    ("fa", "Arab"),
+    ("ur", "Arab"),
+    ("he", "Hebr"),
+    ("yi", "Hebr"),
+    ("zh", "Hans"),
+    ("ja", "Jpan"),
+    ("ko", "Kore"),
+    ("hi", "Deva"),
+    ("mr", "Deva"),
+    ("ne", "Deva"),
+    ("th", "Thai"),
+    ("bn", "Beng"),
+    ("ta", "Taml"),
+];
+
+/// The preferred font family of the script.
+const SCRIPT_FONTS: [(&str, &str); 10] = [
+    ("Arab", "Noto Sans Arabic"),
+    ("Hebr", "Noto Sans Hebrew"),
+    ("Hans", "Noto Sans CJK SC"),
+    ("Hant", "Noto Sans CJK TC"),
+    ("Jpan", "Noto Sans CJK JP"),
+    ("Kore", "Noto Sans CJK KR"),
+    ("Deva", "Noto Sans Devanagari"),
+    ("Thai", "Noto Sans Thai"),
+    ("Beng", "Noto Sans Bengali"),
+    ("Taml", "Noto Sans Tamil"),
+];
+
+/// Suggest the font family for the script of the language tag, such as `zh-Hant-TW`. An explicit
+/// script subtag is preferred, else the script of the language subtag is used. `None` when the
+/// script is covered by the default font.
+pub fn preferred_font(language_tag: &str) -> Option<&'static str> {
+    let mut subtags = language_tag.split(['-', '_']);
+    let language = subtags.next()?;
+    let script = subtags
+        .find(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+        .or_else(|| {
+            LANGUAGE_SCRIPTS
+                .iter()
+                .find(|(subtag, _script)| subtag.eq_ignore_ascii_case(language))
+                .map(|(_subtag, script)| *script)
+        })?;
+    SCRIPT_FONTS
+        .iter()
+        .find(|(subtag, _font)| subtag.eq_ignore_ascii_case(script))
+        .map(|(_subtag, font)| *font)
+}
+
+/// Indicates whether the directory contains a localisation database file.
+fn has_database(path: &Path) -> bool {
+    let Ok(entries) = path.read_dir() else {
+        return false;
+    };
+    entries
+        .flatten()
+        .any(|entry| entry.path().extension().is_some_and(|extension| extension == "sqlite3"))
 }
 
 /// `Localisation` is a wrapper for the `Localiser` of the
@@ -99,6 +217,12 @@ pub struct Localisation {
 
     // Available languages according to supported scripts
     available_languages: HashMap<RefCount<LanguageTag>, (LayoutData, f32)>,
+
+    // Languages tried in order for strings, before the `Localiser`'s own fallback.
+    fallback_chain: Vec<RefCount<LanguageTag>>,
+
+    // The expected directory of the missing localisation database, when the embedded strings are used.
+    missing_database: Option<PathBuf>,
 }
 
 impl Localisation {
@@ -107,18 +231,58 @@ impl Localisation {
         environment: &Environment,
         language: T,
     ) -> Result<Localisation, CoreError> {
-        let directions = vec![
-            ScriptDirection::TopToBottomLeftToRight,
-            ScriptDirection::TopToBottomRightToLeft,
-        ];
-        let mut available_languages = HashMap::<RefCount<LanguageTag>, (LayoutData, f32)>::new();
+        Localisation::try_new_with_fallback(&environment.application_path, language)
+    }
+
+    /// Same as `try_new_with_path`, except when the localisation database is missing, such as an
+    /// incomplete installation, the minimal English strings embedded in the application are used,
+    /// so the missing database can be reported in the FatalError window. See `missing_database()`.
+    pub fn try_new_with_fallback<T: AsRef<str>>(
+        application_path: &Path,
+        language: T,
+    ) -> Result<Localisation, CoreError> {
+        let path = application_path.join("l10n");
+        if has_database(&path) {
+            return Localisation::try_new_with_path(application_path, language);
+        }
+        error!(
+            "The localisation database is missing from ‘{}’, using the embedded strings.",
+            path.display()
+        );
+        let language_tag_registry = RefCount::new(LanguageTagRegistry::new());
+        let localisation_provider = Box::new(EmbeddedProvider::try_new(&language_tag_registry)?);
+        let mut localisation =
+            Localisation::try_new_with_provider(language_tag_registry, localisation_provider, language)?;
+        localisation.missing_database = Some(path);
+        Ok(localisation)
+    }
+
+    /// Same as `try_new`, for when the `Environment` is not yet available, such as for the
+    /// `--version` command line option.
+    pub fn try_new_with_path<T: AsRef<str>>(
+        application_path: &Path,
+        language: T,
+    ) -> Result<Localisation, CoreError> {
         let language_tag_registry = RefCount::new(LanguageTagRegistry::new());
-        let path = environment.application_path.join("l10n");
+        let path = application_path.join("l10n");
         let localisation_provider = Box::new(
             LocalisationProviderSqlite3::try_new(
                 path, &language_tag_registry, false
             )?
         );
+        Localisation::try_new_with_provider(language_tag_registry, localisation_provider, language)
+    }
+
+    fn try_new_with_provider<T: AsRef<str>>(
+        language_tag_registry: RefCount<LanguageTagRegistry>,
+        localisation_provider: Box<dyn LocalisationProviderTrait>,
+        language: T,
+    ) -> Result<Localisation, CoreError> {
+        let directions = vec![
+            ScriptDirection::TopToBottomLeftToRight,
+            ScriptDirection::TopToBottomRightToLeft,
+        ];
+        let mut available_languages = HashMap::<RefCount<LanguageTag>, (LayoutData, f32)>::new();
         let icu_data_provider = RefCount::new(IcuDataProvider::try_new(DataProvider::Internal)?);
         let command_registry = RefCount::new(CommandRegistry::new());
         let localiser = Localiser::try_new(
@@ -155,16 +319,60 @@ impl Localisation {
                 }
             }
         }
-        let layout_data = available_languages
-            .get(&localiser.default_language())
-            .unwrap()
-            .0
-            .clone();
-        Ok(Localisation {
+
+
+        // The language may no longer be available, such as a translation having been removed, thus
+        // fall back to the base language, else to the default language of the application component.
+        let language = localiser.default_language();
+        if !available_languages.contains_key(&language) {
+            let fallback = language
+                .as_str()
+                .split('-')
+                .next()
+                .and_then(|base| language_tag_registry.tag(base).ok())
+                .filter(|tag| available_languages.contains_key(tag))
+                .unwrap_or_else(|| RefCount::clone(&binding.default));
+            warn!(
+                "The language ‘{}’ is not available, using ‘{}’ instead.",
+                language.as_str(),
+                fallback.as_str()
+            );
+            localiser.defaults(Some(fallback), None, None)?;
+        }
+        let Some(layout_data) = available_languages.get(&localiser.default_language()) else {
+            return Err(CoreError::LanguageTagNotSupported(
+                localiser.default_language().as_str().to_string(),
+            ));
+        };
+        let layout_data = layout_data.0.clone();
+        let mut localisation = Localisation {
             localiser,
             layout_data,
             available_languages,
-        })
+            fallback_chain: Vec::<RefCount<LanguageTag>>::new(),
+            missing_database: None,
+        };
+        localisation.fallback_chain = localisation.default_fallback_chain();
+        Ok(localisation)
+    }
+
+    /// Reopen the localisation database, keeping the current default language and fallback chain.
+    /// On failure, such as a malformed database while being edited, the current `Localisation`
+    /// is kept unchanged.
+    pub fn try_reload_provider(&mut self, application_path: &Path) -> Result<(), CoreError> {
+        let mut localisation = Localisation::try_new_with_path(
+            application_path,
+            self.default_language().as_str(),
+        )?;
+        localisation.fallback_chain = self.fallback_chain.clone();
+        *self = localisation;
+        Ok(())
+    }
+
+    /// The expected directory of the localisation database, when it is missing and only the
+    /// embedded strings are available, see `try_new_with_fallback()`.
+    pub fn missing_database(&self) -> Option<&Path> {
+        self.missing_database.as_deref()
     }
 
     // ----- Exposed Localiser methods
@@ -197,29 +405,100 @@ impl Localisation {
             .repository_details()?)
     }
 
-    /// Get a literal string using `Localiser` defaults.
+    /// Get a literal string using `Localiser` defaults, trying the languages of the fallback
+    /// chain in order.
     pub fn literal_with_defaults(
         &self,
         component: &str,
         identifier: &str,
     ) -> Result<(RefCount<String>, RefCount<LanguageTag>), CoreError> {
+        for tag in self.fallback_chain.iter() {
+            match self.localiser.literal(component, identifier, tag, Some(false), None) {
+                Err(LocaliserError::StringNotFound(..)) => continue,
+                result => return Ok(result?),
+            }
+        }
         Ok(self
             .localiser
             .literal_with_defaults(component, identifier)?)
     }
 
-    /// Format a string using `Localiser` defaults.
+    /// Get a literal string using `Localiser` defaults, where a missing string is either an error
+    /// (`strict` is `true`), or is logged as a warning and replaced with the visible placeholder
+    /// `⟦component.identifier⟧` (`strict` is `false`).
+    ///
+    /// Usually `strict` is `cfg!(debug_assertions)`, so that debug builds fail loudly while
+    /// release builds remain usable.
+    pub fn literal_or_placeholder(
+        &self,
+        component: &str,
+        identifier: &str,
+        strict: bool,
+    ) -> Result<(RefCount<String>, RefCount<LanguageTag>), CoreError> {
+        match self.literal_with_defaults(component, identifier) {
+            Ok(value) => Ok(value),
+            Err(CoreError::Localiser(LocaliserError::StringNotFound(..))) if !strict => {
+                warn!("Missing localisation string `{}` of component `{}`.", identifier, component);
+                Ok((
+                    RefCount::new(format!("⟦{}.{}⟧", component, identifier)),
+                    self.default_language(),
+                ))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Format a string using `Localiser` defaults, trying the languages of the fallback chain in
+    /// order.
     pub fn format_with_defaults(
         &self,
         component: &str,
         identifier: &str,
         values: &HashMap<String, PlaceholderValue>,
     ) -> Result<(RefCount<String>, RefCount<LanguageTag>), CoreError> {
+        for tag in self.fallback_chain.iter() {
+            match self.localiser.format(component, identifier, values, tag, Some(false), None) {
+                Err(LocaliserError::StringNotFound(..)) => continue,
+                result => return Ok(result?),
+            }
+        }
         Ok(self
             .localiser
             .format_with_defaults(component, identifier, values)?)
     }
 
+    /// Format the pattern for the plural category of the count, where the pattern identifier is
+    /// the `identifier` with the category appended, such as `unsaved_windows_one`. The count is
+    /// added to the values as the `count` placeholder.
+    ///
+    /// The category is selected using the plural rules of each language of the fallback chain. A
+    /// language lacking the pattern of the category uses its `other` pattern, thus only the `other`
+    /// pattern is required for every language.
+    pub fn format_plural(
+        &self,
+        component: &str,
+        identifier: &str,
+        count: usize,
+        extra_values: &HashMap<String, PlaceholderValue>,
+    ) -> Result<(RefCount<String>, RefCount<LanguageTag>), CoreError> {
+        let mut values = extra_values.clone();
+        values.insert("count".to_string(), PlaceholderValue::Unsigned(count as u128));
+        let other = format!("{}_other", identifier);
+        for tag in self.fallback_chain.iter() {
+            let rules = PluralRules::try_new_cardinal(&(&tag.icu_language()).into())?;
+            let category = format!("{}_{}", identifier, category_name(rules.category_for(count)));
+            for identifier in [category.as_str(), other.as_str()] {
+                match self.localiser.format(component, identifier, &values, tag, Some(false), None) {
+                    Err(LocaliserError::StringNotFound(..)) => continue,
+                    result => return Ok(result?),
+                }
+            }
+        }
+        Ok(self
+            .localiser
+            .format_with_defaults(component, other.as_str(), &values)?)
+    }
+
     /// Format an error into a string using `Localiser` defaults.
     pub fn format_error_with_defaults(
         &self,
@@ -242,10 +521,65 @@ impl Localisation {
 
     /// Get a reference to available languages. Also includes the layout data
     /// and ratio of translated string to development language.
+    ///
+    /// Note: Intended for internal use, use `language_completion()` instead.
     pub fn available_languages(&self) -> &HashMap<RefCount<LanguageTag>, (LayoutData, f32)> {
         &self.available_languages
     }
 
+    /// Get the available languages with their ratio of translated strings to the development
+    /// language. The most complete translations are first, ties are ordered by language tag.
+    ///
+    /// This is the supported way to obtain the language availability for displaying.
+    pub fn language_completion(&self) -> Vec<(RefCount<LanguageTag>, f32)> {
+        let mut languages = self
+            .available_languages
+            .iter()
+            .map(|(tag, (_layout, ratio))| (RefCount::clone(tag), *ratio))
+            .collect::<Vec<(RefCount<LanguageTag>, f32)>>();
+        sort_by_completion(&mut languages);
+        languages
+    }
+
+    /// Indicates whether the language is available for the user interface.
+    pub fn is_language_supported(&self, tag: &RefCount<LanguageTag>) -> bool {
+        self.available_languages.contains_key(tag)
+    }
+
+    /// Get the layout data of the available language, without changing the default language, such
+    /// as for widgets displaying text of another language. `None` when the language is not
+    /// available for the user interface.
+    pub fn layout_data_for(&self, tag: &RefCount<LanguageTag>) -> Option<&LayoutData> {
+        self.available_languages.get(tag).map(|(layout, _ratio)| layout)
+    }
+
+    /// Indicates whether the words of the available language flow from right to left. `false`
+    /// when the language is not available for the user interface.
+    pub fn is_rtl(&self, tag: &RefCount<LanguageTag>) -> bool {
+        self.layout_data_for(tag)
+            .is_some_and(|layout| layout.flow_word == Direction::RightToLeft)
+    }
+
+    /// Find the available language for the locale, such as the locale of the operating system.
+    ///
+    /// An exact match of the language tag is preferred, else the first available language (in
+    /// sorted order) having the same language subtag is used. `None` is returned when the locale
+    /// is not a valid language tag, or has no matching available language.
+    pub fn match_language(&self, locale: &str) -> Option<RefCount<LanguageTag>> {
+        let tag = self.language_tag_registry().tag(locale).ok()?;
+        if self.available_languages.contains_key(&tag) {
+            return Some(tag);
+        }
+        let language = tag.as_str().split('-').next()?;
+        let mut matches = self
+            .available_languages
+            .keys()
+            .filter(|available| available.as_str().split('-').next() == Some(language))
+            .collect::<Vec<&RefCount<LanguageTag>>>();
+        matches.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        matches.first().map(|available| RefCount::clone(available))
+    }
+
     /// Change the default language of the `Localiser`, and change layout data
     /// to the new language.
     pub fn change_default_language(
@@ -253,20 +587,130 @@ impl Localisation {
         tag: RefCount<LanguageTag>,
     ) -> Result<bool, CoreError> {
         if tag != self.localiser.default_language() {
-            let Some(layout) = self.available_languages.get(&tag) else {
+            let Some(layout) = self.layout_data_for(&tag).cloned() else {
                 return Err(CoreError::LanguageTagNotSupported(tag.as_str().to_string()));
             };
             self.localiser.defaults(Some(tag), None, None)?;
-            self.layout_data = layout.0.clone();
+            self.layout_data = layout;
+            self.fallback_chain = self.default_fallback_chain();
             return Ok(true);
         }
         Ok(false)
     }
 
-    /// Get reference to the language layout data.
+    /// Format the number using the conventions of the default language, such as the decimal
+    /// separator and the digit grouping.
+    pub fn format_number(
+        &self,
+        value: &FixedDecimal,
+        options: FixedDecimalFormatterOptions,
+    ) -> Result<String, CoreError> {
+        let locale = self.default_language().icu_language();
+        let formatter = FixedDecimalFormatter::try_new(&(&locale).into(), options)?;
+        Ok(formatter.format_to_string(value))
+    }
+
+    /// Format the date and time using the conventions and calendar of the default language, where
+    /// the style is the lengths of the date and time, such as `length::Bag::from_date_time_style()`.
+    pub fn format_datetime(
+        &self,
+        date_time: &NaiveDateTime,
+        style: length::Bag,
+    ) -> Result<String, CoreError> {
+        let locale = self.default_language().icu_language();
+        let formatter = DateTimeFormatter::try_new(&(&locale).into(), style.into())?;
+        let date_time = DateTime::try_new_iso_datetime(
+            date_time.year(),
+            date_time.month() as u8,
+            date_time.day() as u8,
+            date_time.hour() as u8,
+            date_time.minute() as u8,
+            date_time.second() as u8,
+        )?;
+        Ok(formatter.format_to_string(&date_time.to_any())?)
+    }
+
+    /// Get reference to the language layout data of the default language. See `layout_data_for()`
+    /// for the other available languages.
     pub fn layout_data(&self) -> &LayoutData {
         &self.layout_data
     }
+
+    /// Mirror the directional glyph, such as an arrow or a chevron, when the words of the default
+    /// language flow from right to left, else the glyph is returned unchanged. Glyphs without a
+    /// mirrored variant are also returned unchanged.
+    pub fn mirror_icon(&self, icon: char) -> char {
+        if self.layout_data.flow_word != Direction::RightToLeft {
+            return icon;
+        }
+        MIRRORED_GLYPHS
+            .iter()
+            .find_map(|(left, right)| {
+                if icon == *left {
+                    Some(*right)
+                } else if icon == *right {
+                    Some(*left)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(icon)
+    }
+
+    /// Suggest the font family for the script of the default language, see `preferred_font()`.
+    pub fn preferred_font(&self) -> Option<&'static str> {
+        preferred_font(self.default_language().as_str())
+    }
+
+    /// Get reference to the fallback chain, the languages tried in order for strings.
+    pub fn fallback_chain(&self) -> &Vec<RefCount<LanguageTag>> {
+        &self.fallback_chain
+    }
+
+    /// Replace the fallback chain, the languages tried in order for strings, before the
+    /// `Localiser`'s fallback to the default language of the localisation database.
+    ///
+    /// Note: The chain is reset to the default chain when the default language is changed.
+    pub fn set_fallback_chain(&mut self, chain: Vec<RefCount<LanguageTag>>) {
+        self.fallback_chain = chain;
+    }
+
+    /// The default fallback chain: the selected language, its base language, and `en`.
+    fn default_fallback_chain(&self) -> Vec<RefCount<LanguageTag>> {
+        let selected = self.localiser.default_language();
+        let mut chain = vec![RefCount::clone(&selected)];
+        let mut candidates = Vec::<&str>::new();
+        if let Some(base) = selected.as_str().split('-').next() {
+            candidates.push(base);
+        }
+        candidates.push("en");
+        for candidate in candidates {
+            if let Ok(tag) = self.language_tag_registry().tag(candidate) {
+                if !chain.contains(&tag) {
+                    chain.push(tag);
+                }
+            }
+        }
+        chain
+    }
+}
+
+/// Sort the languages by their ratio of translated strings, the most complete first, and ties by
+/// language tag, thus the display order is stable.
+fn sort_by_completion(languages: &mut [(RefCount<LanguageTag>, f32)]) {
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+}
+
+/// The suffix of the pattern identifiers for the plural category.
+fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
 }
 
 //
@@ -403,3 +847,206 @@ impl LayoutData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{crate_path, localisation, TempDir};
+
+    #[test]
+    fn match_language_of_locale() {
+        let localisation = localisation("en-ZA");
+        let matched = localisation.match_language("en-ZA").unwrap();
+        assert_eq!(matched.as_str(), "en-ZA");
+
+        // Same language subtag.
+        let matched = localisation.match_language("it-IT").unwrap();
+        assert_eq!(matched.as_str(), "it");
+        let matched = localisation.match_language("en-GB").unwrap();
+        assert_eq!(matched.as_str(), "en-ZA");
+
+        // No available language.
+        assert!(localisation.match_language("de-DE").is_none());
+        assert!(localisation.match_language("").is_none());
+    }
+
+    #[test]
+    fn literal_or_placeholder_of_missing_string() {
+        let localisation = localisation("en-ZA");
+        let (string, _tag) = localisation
+            .literal_or_placeholder("application", "fatal_error", true)
+            .unwrap();
+        assert_eq!(string.as_str(), "Fatal error");
+
+        // Strict fails loudly, lenient displays the placeholder.
+        assert!(localisation.literal_or_placeholder("application", "missing", true).is_err());
+        let (string, _tag) = localisation
+            .literal_or_placeholder("application", "missing", false)
+            .unwrap();
+        assert_eq!(string.as_str(), "⟦application.missing⟧");
+    }
+
+    #[test]
+    fn sort_by_completion_order() {
+        let registry = LanguageTagRegistry::new();
+        let mut languages = [("it", 0.5f32), ("en-ZA", 1.0f32), ("fr", 0.5f32), ("de", 0.9f32)]
+            .iter()
+            .map(|(tag, ratio)| (registry.tag(tag).unwrap(), *ratio))
+            .collect::<Vec<(RefCount<LanguageTag>, f32)>>();
+        sort_by_completion(&mut languages);
+        let order = languages.iter().map(|(tag, _ratio)| tag.as_str()).collect::<Vec<&str>>();
+        assert_eq!(order, vec!["en-ZA", "de", "fr", "it"]);
+    }
+
+    #[test]
+    fn language_completion_matches_component_details() {
+        let localisation = localisation("en-ZA");
+        let registry = RefCount::new(LanguageTagRegistry::new());
+        let provider = LocalisationProviderSqlite3::try_new(
+            crate_path().join("l10n"),
+            &registry,
+            false,
+        )
+        .unwrap();
+        let details = provider.component_details("application").unwrap();
+        let completion = localisation.language_completion();
+        assert_eq!(completion.len(), details.languages.len());
+        for (tag, ratio) in completion.iter() {
+            let (_tag, data) = details
+                .languages
+                .iter()
+                .find(|(detail_tag, _data)| detail_tag.as_str() == tag.as_str())
+                .unwrap();
+            assert_eq!(*ratio, data.ratio);
+            assert!(localisation.is_language_supported(tag));
+        }
+        assert!(completion.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(!localisation.is_language_supported(&registry.tag("de").unwrap()));
+    }
+
+    #[test]
+    fn format_number_and_datetime_per_language() {
+        let value = FixedDecimal::from(12345678).multiplied_pow10(-1);
+        let date_time = NaiveDateTime::parse_from_str("2024-03-05 14:07:09", "%Y-%m-%d %H:%M:%S")
+            .unwrap();
+        let style = length::Bag::from_date_time_style(length::Date::Medium, length::Time::Short);
+
+        // South African English groups the digits with a no-break space, while Italian uses a dot.
+        let english = localisation("en-ZA");
+        assert_eq!(
+            english.format_number(&value, Default::default()).unwrap(),
+            "1\u{a0}234\u{a0}567,8"
+        );
+        assert_eq!(english.format_datetime(&date_time, style).unwrap(), "05 Mar 2024, 14:07");
+        let italian = localisation("it");
+        assert_eq!(italian.format_number(&value, Default::default()).unwrap(), "1.234.567,8");
+        assert_eq!(italian.format_datetime(&date_time, style).unwrap(), "5 mar 2024, 14:07");
+    }
+
+    #[test]
+    fn format_plural_categories() {
+        let format = |localisation: &Localisation, count: usize| {
+            localisation
+                .format_plural("application", "unsaved_windows", count, &HashMap::new())
+                .unwrap()
+                .0
+                .to_string()
+        };
+
+        // English only has the `one` and `other` categories.
+        let english = localisation("en-ZA");
+        assert_eq!(format(&english, 1), "There is unsaved data present in 1 window:");
+        assert_eq!(format(&english, 2), "There is unsaved data present in 2 windows:");
+        assert_eq!(format(&english, 0), "There is unsaved data present in 0 windows:");
+
+        // Italian also has the `many` category, such as for a million, which lacks a pattern thus
+        // falls back to the `other` pattern.
+        let italian = localisation("it");
+        let locale = italian.default_language().icu_language();
+        let rules = PluralRules::try_new_cardinal(&(&locale).into()).unwrap();
+        assert_eq!(rules.category_for(1_000_000usize), PluralCategory::Many);
+        assert_eq!(format(&italian, 1), "Ci sono dati non salvati presenti in 1 finestra:");
+        assert_eq!(format(&italian, 3), "Ci sono dati non salvati presenti in 3 finestre:");
+        assert!(format(&italian, 1_000_000).ends_with(" finestre:"));
+    }
+
+    #[test]
+    fn unsupported_language_falls_back() {
+        // The base language is preferred, else the default language of the application component.
+        assert_eq!(localisation("it-CH").default_language().as_str(), "it");
+        assert_eq!(localisation("de").default_language().as_str(), "en-ZA");
+        let localisation = localisation("de-AT");
+        assert_eq!(localisation.default_language().as_str(), "en-ZA");
+        assert_eq!(
+            localisation.literal_with_defaults("word", "exit_i").unwrap().0.as_str(),
+            "Exit"
+        );
+    }
+
+
+    #[test]
+    fn direction_of_ltr_and_rtl_languages() {
+        let mut localisation = localisation("en-ZA");
+        let english = localisation.language_tag_registry().tag("en-ZA").unwrap();
+        assert!(!localisation.is_rtl(&english));
+        let layout = localisation.layout_data_for(&english).unwrap();
+        assert!(!layout.reverse_words);
+        assert_eq!(layout.align_words_start, Alignment::Start);
+
+        // The database has no right to left language, thus an Arabic language is made available.
+        let arabic = localisation.language_tag_registry().tag("ar").unwrap();
+        assert!(!localisation.is_rtl(&arabic));
+        assert!(localisation.layout_data_for(&arabic).is_none());
+        localisation.available_languages.insert(
+            RefCount::clone(&arabic),
+            (LayoutData::new(&ScriptDirection::TopToBottomRightToLeft), 1f32),
+        );
+        assert!(localisation.is_rtl(&arabic));
+        let layout = localisation.layout_data_for(&arabic).unwrap();
+        assert!(layout.reverse_words);
+        assert_eq!(layout.align_words_start, Alignment::End);
+        assert!(!localisation.is_rtl(&english));
+    }
+
+
+    #[test]
+    fn mirror_icon_flips_in_rtl() {
+        let mut localisation = localisation("en-ZA");
+        for (left, right) in MIRRORED_GLYPHS {
+            assert_eq!(localisation.mirror_icon(left), left);
+            assert_eq!(localisation.mirror_icon(right), right);
+        }
+        assert_eq!(localisation.mirror_icon('x'), 'x');
+
+        localisation.layout_data = LayoutData::new(&ScriptDirection::TopToBottomRightToLeft);
+        for (left, right) in MIRRORED_GLYPHS {
+            assert_eq!(localisation.mirror_icon(left), right);
+            assert_eq!(localisation.mirror_icon(right), left);
+        }
+        assert_eq!(localisation.mirror_icon('x'), 'x');
+        assert_eq!(localisation.mirror_icon('↑'), '↑');
+    }
+
+    #[test]
+    fn missing_database_activates_embedded_strings() {
+        use crate::application::StringGroup;
+
+        let temp_dir = TempDir::new("missing_l10n");
+        let directory = temp_dir.path();
+        let path = directory.join("l10n");
+        assert!(Localisation::try_new_with_path(directory, "en-ZA").is_err());
+
+        let localisation = Localisation::try_new_with_fallback(directory, "en-ZA").unwrap();
+        assert_eq!(localisation.missing_database(), Some(path.as_path()));
+
+        // The FatalError window reports the missing database.
+        assert!(crate::localisation::try_strings(&StringGroup::FatalError, &localisation).is_ok());
+        let error = CoreError::LocalisationMissing(path.clone());
+        let message = localisation.format_error_with_defaults(&error).unwrap().0;
+        assert!(message.contains(path.to_str().unwrap()), "{}", message);
+
+        // The database is used when present.
+        let localisation = Localisation::try_new_with_fallback(crate_path(), "en-ZA").unwrap();
+        assert!(localisation.missing_database().is_none());
+    }
+}
@@ -7,17 +7,21 @@
 
 use crate::{
     application::{
-        constants::WINDOW_DEFAULT_DATA, session::WindowData, ApplicationError,
+        constants::{CASCADE_OFFSET, REUSABLE_LIMIT, WINDOW_DEFAULT_DATA}, session::WindowData, ApplicationError,
         Message, Session, WindowType
     },
     core::{
         error::CoreError,
+        localisation::StringCache,
         traits::{AnyWindowTrait, WindowTrait},
     },
     window::{default, fatal_error},
 };
-use iced::{window, Point, Size, Task};
-use std::{collections::BTreeMap, usize};
+use iced::{window, Point, Rectangle, Size, Task};
+
+#[cfg(any(test, feature = "debug_snapshot"))]
+use crate::core::traits::WindowSnapshot;
+use std::{cmp::Reverse, collections::{BTreeMap, VecDeque}, usize};
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
@@ -33,10 +37,16 @@ pub struct Manager {
     states: BTreeMap<window::Id, Entry>,
 
     // Window IDs placed in main window threads
-    threads: VecOption<Vec<window::Id>>,
+    threads: ThreadTable<Thread>,
 
     // Reusable states cache
     reusable: BTreeMap<WindowType, Box<dyn AnyWindowTrait>>,
+
+    // Window types of the reusable states cache, least recently cached first.
+    reusable_order: VecDeque<WindowType>,
+
+    // The known monitor areas, empty when unknown.
+    monitors: Vec<Rectangle>,
 }
 
 impl Manager {
@@ -47,12 +57,23 @@ impl Manager {
     /// The return `Result` is used instead of `Manager` instance, just in case
     /// the default window creation produces an error.
     pub fn try_new() -> Result<Manager, ApplicationError> {
+        // Fail fast on startup, instead of when the window is first opened.
+        for window_type in WindowType::all() {
+            if !WINDOW_DEFAULT_DATA.contains_key(window_type.as_str()) {
+                return Err(CoreError::WindowTypeNotFound(
+                    window_type.clone(),
+                    "WINDOW_DEFAULT_DATA".to_string(),
+                ))?;
+            }
+        }
         let mut reusable = BTreeMap::<WindowType, Box<dyn AnyWindowTrait>>::new();
         reusable.insert(WindowType::Default, Box::new(default::State::new()));
         Ok(Manager {
             states: BTreeMap::<window::Id, Entry>::new(),
-            threads: VecOption::<Vec<window::Id>>::new(),
+            threads: ThreadTable::<Thread>::new(),
             reusable,
+            reusable_order: VecDeque::from([WindowType::Default]),
+            monitors: Vec::<Rectangle>::new(),
         })
     }
 
@@ -72,20 +93,76 @@ impl Manager {
         self.states.get_mut(id).map(|x| &mut x.state)
     }
 
+    /// Indicates whether the window Id is of an existing window.
+    pub fn contains(&self, id: &window::Id) -> bool {
+        self.states.contains_key(id)
+    }
+
     /// Return the number of window threads.
     pub fn thread_count(&self) -> usize {
-        self.threads.count
+        self.threads.len()
     }
 
-    /// Return a list of the threads' root window Id.
+    /// Return a list of the threads' root window Id. Threads whose windows have all been closed,
+    /// though are yet to receive `Message::ThreadClosed`, are excluded.
     pub fn thread_list(&self) -> Vec<window::Id> {
-        let mut _vec = Vec::<window::Id>::new();
-        for option in self.threads.vec.iter() {
-            if option.is_some() {
-                _vec.push(option.as_ref().unwrap()[0].clone());
+        self.threads
+            .iter()
+            .filter_map(|(_, thread)| thread.windows.first().copied())
+            .collect()
+    }
+
+    /// Return the root window Id of the thread having the specified name, such as the file opened
+    /// in the thread. Should several threads have the same name, the earliest thread is returned.
+    pub fn thread_named(&self, name: &str) -> Option<window::Id> {
+        self.threads
+            .iter()
+            .find(|(_, thread)| thread.name.as_deref() == Some(name))
+            .and_then(|(_, thread)| thread.windows.first().copied())
+    }
+
+    /// Return the root window Id and the name of the named threads, in thread order.
+    pub fn thread_names(&self) -> Vec<(window::Id, String)> {
+        self.threads
+            .iter()
+            .filter_map(|(_, thread)| {
+                let name = thread.name.as_ref()?;
+                thread.windows.first().map(|id| (*id, name.clone()))
+            })
+            .collect()
+    }
+
+    /// Set the name of the thread containing the specified window Id, typically the name of the
+    /// data of the main window, such as after the data is saved to another file.
+    pub fn set_thread_name(&mut self, id: &window::Id, name: Option<String>) {
+        if let Some(name) = name.as_deref() {
+            if let Some(other) = self.thread_named(name) {
+                if !self.is_same_thread(&other, id) {
+                    warn!("The thread of {:?} has the same name ‘{}’ as {:?}.", id, name, other);
+                }
             }
         }
-        _vec
+        if let Some((_, thread)) = self
+            .threads
+            .iter_mut()
+            .find(|(_, thread)| thread.windows.contains(id))
+        {
+            thread.name = name;
+        }
+    }
+
+    /// The position of the window within its thread, the root window being 0.
+    fn thread_position(&self, id: &window::Id) -> Option<usize> {
+        self.threads
+            .iter()
+            .find_map(|(_, thread)| thread.windows.iter().position(|window| window == id))
+    }
+
+    /// Indicates whether both window Ids are in the same thread.
+    fn is_same_thread(&self, id: &window::Id, other: &window::Id) -> bool {
+        self.threads
+            .iter()
+            .any(|(_, thread)| thread.windows.contains(id) && thread.windows.contains(other))
     }
 
     /// Indicates whether the window is enabled for events.
@@ -93,11 +170,147 @@ impl Manager {
         self.states.get(id).map(|x| x.enabled)
     }
 
+    /// The size the window is locked to, `None` while the window is resizable.
+    pub fn locked_size(&self, id: &window::Id) -> Option<Size> {
+        self.states.get(id)?.locked_size
+    }
+
+    /// A summary of the visible state of the specified window, including whether it is enabled.
+    #[cfg(any(test, feature = "debug_snapshot"))]
+    pub fn debug_snapshot(
+        &self,
+        id: &window::Id,
+        string_cache: &StringCache,
+    ) -> Option<WindowSnapshot> {
+        self.states.get(id).map(|entry| WindowSnapshot {
+            enabled: entry.enabled,
+            ..entry.state.debug_snapshot(string_cache)
+        })
+    }
+
     /// Return the parent window Id of the specified window Id if available.
     pub fn parent(&self, id: &window::Id) -> Option<window::Id> {
         self.states.get(id).map(|x| x.parent)?
     }
 
+    /// Return the Ids of the windows of the specified window type, in window Id order.
+    pub fn ids_of_type(&self, window_type: WindowType) -> Vec<window::Id> {
+        self.states
+            .iter()
+            .filter(|(_, entry)| entry.state.window_type() == window_type)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Return the number of opened windows of the specified window type.
+    pub fn count_by_type(&self, window_type: WindowType) -> usize {
+        self.states
+            .values()
+            .filter(|entry| entry.state.window_type() == window_type)
+            .count()
+    }
+
+    /// Check the `max_instances` limit of the window type's `WINDOW_DEFAULT_DATA` entry, failing
+    /// with `CoreError::WindowLimitReached` when the limit is reached, which the caller may display
+    /// as a toast.
+    fn check_limit(&self, window_type: WindowType) -> Result<(), CoreError> {
+        let Some(defaults) = WINDOW_DEFAULT_DATA.get(window_type.as_str()) else {
+            return Err(CoreError::WindowTypeNotFound(
+                window_type,
+                "WINDOW_DEFAULT_DATA".to_string(),
+            ));
+        };
+        let count = self.count_by_type(window_type.clone());
+        within_limit(window_type, count, defaults.max_instances)
+    }
+
+    /// Return the Id of the first window of the specified window type if available.
+    pub fn first_of_type(&self, window_type: WindowType) -> Option<window::Id> {
+        self.states
+            .iter()
+            .find(|(_, entry)| entry.state.window_type() == window_type)
+            .map(|(id, _)| *id)
+    }
+
+    /// Set the known monitor areas, in logical coordinates. A restored window position outside
+    /// of all the monitors is replaced by a centred position, leaving the stored position intact
+    /// for when the monitor returns.
+    ///
+    /// The monitors are detected by `application::monitors::detect()`, on startup and off the UI
+    /// thread when a window gains focus. The stored positions are used as is when no monitors are
+    /// detected.
+    pub fn set_monitors(&mut self, monitors: Vec<Rectangle>) {
+        self.monitors = monitors;
+    }
+
+    /// The known monitor areas, in logical coordinates.
+    pub fn monitors(&self) -> &[Rectangle] {
+        &self.monitors
+    }
+
+    //
+    // ----- Global methods
+    //
+
+    /// Send the message to every window state, in window Id order, and batch the returned
+    /// tasks. Useful for notifying all windows of a global change.
+    ///
+    /// The first error returned by a window state aborts the broadcast.
+    pub fn broadcast(
+        &mut self,
+        message: Message,
+        string_cache: &StringCache,
+    ) -> Result<Task<Message>, ApplicationError> {
+        let mut tasks = Vec::<Task<Message>>::with_capacity(self.states.len());
+        for entry in self.states.values_mut() {
+            tasks.push(entry.state.try_update(message.clone(), string_cache)?);
+        }
+        Ok(Task::batch(tasks))
+    }
+
+    /// Call the closure for every window state, in window Id order, for changes that are not
+    /// done by messages.
+    ///
+    /// The first error returned by the closure aborts the iteration.
+    pub fn for_each_state_mut<F>(&mut self, mut function: F) -> Result<(), ApplicationError>
+    where
+        F: FnMut(&window::Id, &mut Box<dyn AnyWindowTrait>) -> Result<(), ApplicationError>,
+    {
+        for (id, entry) in self.states.iter_mut() {
+            function(id, &mut entry.state)?;
+        }
+        Ok(())
+    }
+
+    /// Lock or unlock the resizing of the window at runtime. The lock takes effect once the
+    /// current size of the window has been obtained, see `lock_size()`.
+    ///
+    /// Note: As `iced` does not provide a command for changing the resizable attribute of an
+    /// opened window, a locked window is resized back to its locked size when resized. Unlocking
+    /// a window created as not resizable, see `WINDOW_DEFAULT_DATA`, has no effect.
+    pub fn set_resizable(
+        &mut self,
+        id: window::Id,
+        resizable: bool,
+    ) -> Result<Task<Message>, CoreError> {
+        debug!("set_resizable(): {:?} to {}", id, resizable);
+        let Some(entry) = self.states.get_mut(&id) else {
+            return Err(CoreError::WindowIdNotFound(id, "Manager.states".to_string()));
+        };
+        if resizable {
+            entry.locked_size = None;
+            return Ok(Task::none());
+        }
+        Ok(window::get_size(id).map(move |size| Message::SizeLocked(id, size)))
+    }
+
+    /// Record the size the window is locked to.
+    pub fn lock_size(&mut self, id: &window::Id, size: Size) {
+        if let Some(entry) = self.states.get_mut(id) {
+            entry.locked_size = Some(size);
+        }
+    }
+
     //
     // ----- Spawning methods
     //
@@ -109,14 +322,12 @@ impl Manager {
         match parent {
             None => {
                 trace!("disable(): all threads");
-                let mut disabled = Vec::<window::Id>::with_capacity(self.threads.vec.len());
-                for thread in &self.threads.vec {
-                    if thread.is_some() {
-                        if let Some(id) = thread.as_ref().unwrap().last() {
-                            let entry = self.states.get_mut(id).unwrap();
-                            entry.enabled = false;
-                            disabled.push(*id);
-                        }
+                let mut disabled = Vec::<window::Id>::with_capacity(self.threads.len());
+                for (_, thread) in self.threads.iter() {
+                    if let Some(id) = thread.windows.last() {
+                        let entry = self.states.get_mut(id).unwrap();
+                        entry.enabled = false;
+                        disabled.push(*id);
                     }
                 }
                 disabled
@@ -133,22 +344,42 @@ impl Manager {
     /// Try to create a new window thread, using the provided `iced` window
     /// settings located in the application's session data, and the window
     /// state.
+    ///
+    /// Fails with `CoreError::WindowLimitReached` when the window type's `max_instances` is reached.
     pub fn try_create_thread(
         &mut self,
         session: &mut Session,
         state: Box<dyn AnyWindowTrait>,
+    ) -> Result<Task<Message>, CoreError> {
+        self.try_create_named_thread(session, state, None)
+    }
+
+    /// Try to create a new window thread having the specified name, typically the name of the
+    /// data of the main window, such as the opened file. See `thread_named()`.
+    pub fn try_create_named_thread(
+        &mut self,
+        session: &mut Session,
+        state: Box<dyn AnyWindowTrait>,
+        name: Option<String>,
     ) -> Result<Task<Message>, CoreError> {
         debug!(
             "try_create_thread(): for window type ‘{:?}’",
             state.window_type()
         );
 
-        // Set `iced` window settings, and spawn.
-        let id = try_create(session, state.window_type())?;
+        self.check_limit(state.window_type())?;
+
+        // Set `iced` window settings, and spawn. The window is cascaded from the already opened
+        // windows of the same window type, when enabled.
+        let cascade = match session.settings.cascade_new_windows {
+            true => self.ids_of_type(state.window_type()).len(),
+            false => 0,
+        };
+        let id = try_create(session, state.window_type(), &self.monitors, cascade)?;
 
         // Insert state and open the window.
-        let entry = Entry {state, enabled: true, parent: None, disabled: None};
-        let _ = self.threads.push(vec![id.0]);
+        let entry = Entry {state, enabled: true, parent: None, disabled: None, locked_size: None};
+        let _ = self.threads.insert(Thread {windows: vec![id.0], name});
         self.states.insert(id.0, entry);
         trace!("try_create_thread(): inserted state for {:?}, next open window", id.0);
         Ok(id.1.map(move |id| Message::WindowOpened(id)))
@@ -158,6 +389,9 @@ impl Manager {
     /// provided `iced` window settings located in the application's session
     /// data, the window state, and parent window Id (used to locate the main
     /// thread to attached window to).
+    ///
+    /// An opened singleton window is focused instead, otherwise fails with
+    /// `CoreError::WindowLimitReached` when the window type's `max_instances` is reached.
     pub fn try_create_window(
         &mut self,
         session: &mut Session,
@@ -174,6 +408,22 @@ impl Manager {
                 "Manager.state".to_string(),
             ));
         }
+
+        // A singleton window that is already opened is focused instead. The reusable cache only
+        // contains states of closed windows, thus the supplied state is not from the cache, though
+        // a reusable state is cached to avoid recreating it when the opened window is closed.
+        if state.is_singleton() {
+            if let Some(id) = self.first_of_type(state.window_type()) {
+                debug!("try_create_window(): focusing opened singleton window {:?}", id);
+                if state.is_reusable() && !self.reusable.contains_key(&state.window_type()) {
+                    self.cache_reusable(state);
+                }
+                return Ok(window::gain_focus(id));
+            }
+        }
+
+        self.check_limit(state.window_type())?;
+
         let parent = Some(parent);
         let disabled = if state.is_global_disable() {
             self.disable_windows(&None)
@@ -182,18 +432,16 @@ impl Manager {
         };
 
         // Set `iced` window settings, and spawn
-        let id = try_create(session, state.window_type())?;
+        let id = try_create(session, state.window_type(), &self.monitors, 0)?;
 
         // Insert state and open the window.
-        let entry = Entry {state, enabled: true, parent, disabled: Some(disabled)};
-        for thread in &mut self.threads.vec {
-            if thread.is_some() {
-                let actual = thread.as_mut().unwrap();
-                if actual.last() == entry.parent.as_ref() {
-                    actual.push(id.0);
-                    break;
-                }
-            }
+        let entry = Entry {state, enabled: true, parent, disabled: Some(disabled), locked_size: None};
+        if let Some((_, thread)) = self
+            .threads
+            .iter_mut()
+            .find(|(_, thread)| thread.windows.last() == entry.parent.as_ref())
+        {
+            thread.windows.push(id.0);
         }
         self.states.insert(id.0, entry);
         trace!("try_create_window(): inserted state for {:?}, next open window", id.0);
@@ -202,9 +450,38 @@ impl Manager {
 
     /// Obtain reusable state if available, for the specified window type.
     pub fn use_reusable(&mut self, window_type: WindowType) -> Option<Box<dyn AnyWindowTrait>> {
+        self.reusable_order.retain(|cached| *cached != window_type);
         self.reusable.remove(&window_type)
     }
 
+    /// Drop the cached reusable states, such as under memory pressure. The Default state is kept,
+    /// as it is required when a thread is closed.
+    pub fn clear_reusable(&mut self) {
+        self.reusable.retain(|window_type, _| *window_type == WindowType::Default);
+        self.reusable_order.retain(|window_type| *window_type == WindowType::Default);
+    }
+
+    /// Cache the reusable state. When the cache exceeds `REUSABLE_LIMIT`, the least recently
+    /// cached state is dropped, except for the Default state.
+    fn cache_reusable(&mut self, state: Box<dyn AnyWindowTrait>) {
+        let window_type = state.window_type();
+        self.reusable_order.retain(|cached| *cached != window_type);
+        self.reusable_order.push_back(window_type.clone());
+        self.reusable.insert(window_type, state);
+        while self.reusable.len() > REUSABLE_LIMIT {
+            let Some(index) = self
+                .reusable_order
+                .iter()
+                .position(|cached| *cached != WindowType::Default)
+            else {
+                break;
+            };
+            let evicted = self.reusable_order.remove(index).unwrap();
+            debug!("cache_reusable(): evicted reusable state for {:?}", evicted);
+            self.reusable.remove(&evicted);
+        }
+    }
+
     /// Create the fatal error window.
     ///
     /// Every window is disable to prevent any additional fatal errors from occurring, before
@@ -212,13 +489,14 @@ impl Manager {
     pub fn create_fatal_error_window(
         &mut self,
         session: &mut Session,
+        error: ApplicationError,
     ) -> Task<Message> {
-        let state = Box::new(fatal_error::State::new());
+        let state = Box::new(fatal_error::State::new(error));
         debug!(
             "create_fatal_window(): for window type ‘{:?}’",
             state.window_type()
         );
-        let id = match try_create(session, state.window_type()) {
+        let id = match try_create(session, state.window_type(), &self.monitors, 0) {
             Ok(value) => value,
             Err(_) => {
                 let settings = window::Settings {
@@ -234,15 +512,13 @@ impl Manager {
         let _ = self.disable_windows(&None);
 
         // Insert state and open the window.
-        let entry = Entry {state, enabled: true, parent: None, disabled: None};
-        for thread in &mut self.threads.vec {
-            if thread.is_some() {
-                let actual = thread.as_mut().unwrap();
-                if actual.last() == entry.parent.as_ref() {
-                    actual.push(id.0);
-                    break;
-                }
-            }
+        let entry = Entry {state, enabled: true, parent: None, disabled: None, locked_size: None};
+        if let Some((_, thread)) = self
+            .threads
+            .iter_mut()
+            .find(|(_, thread)| thread.windows.last() == entry.parent.as_ref())
+        {
+            thread.windows.push(id.0);
         }
         self.states.insert(id.0, entry);
         trace!("create_fatal_error_window(): inserted state for {:?}, next open window", id.0);
@@ -264,13 +540,16 @@ impl Manager {
     }
 
     /// Allows for multiple windows to be closed at once.
-    /// 
-    /// Ensure the vector is ordered from newest to oldest window, else fatal error may occur.
+    ///
+    /// The windows are closed in the reverse order of their position within their thread, that is
+    /// children before parents, thus the windows disabled by a closed window are re-enabled in
+    /// turn regardless of the order of the provided Ids.
     pub fn close_multiple(
         &mut self,
-        ids: Vec<window::Id>,
+        mut ids: Vec<window::Id>,
     ) -> Result<Task<Message>, CoreError> {
         debug!("close_multiple()");
+        self.sort_for_closing(&mut ids);
         let mut tasks = Task::none();
         for id in ids {
             tasks = tasks.chain(window::close(id));
@@ -279,6 +558,72 @@ impl Manager {
         Ok(tasks)
     }
 
+    /// Sort the window Ids in the reverse order of their position within their thread, that is
+    /// children before parents.
+    fn sort_for_closing(&self, ids: &mut [window::Id]) {
+        ids.sort_by_key(|id| Reverse(self.thread_position(id)));
+    }
+
+    /// Move a window on top of another window thread, making `new_parent` its parent. Only the
+    /// newest window of a thread can be moved, and only on top of the newest window of another
+    /// thread, thus the threads remain stacks of windows.
+    ///
+    /// The windows disabled by the moved window are re-enabled, and the new parent is disabled,
+    /// except for a window disabling all threads, which keeps them disabled.
+    pub fn reparent(
+        &mut self,
+        id: window::Id,
+        new_parent: window::Id,
+    ) -> Result<(), CoreError> {
+        debug!("reparent(): {:?} on top of {:?}", id, new_parent);
+        let Some(entry) = self.states.get(&id) else {
+            return Err(CoreError::WindowIdNotFound(id, "Manager.states".to_string()));
+        };
+        if !self.states.contains_key(&new_parent) {
+            return Err(CoreError::WindowIdNotFound(new_parent, "Manager.states".to_string()));
+        }
+        let is_global_disable = entry.state.is_global_disable();
+        let Some((from, _)) = self
+            .threads
+            .iter()
+            .find(|(_, thread)| thread.windows.len() > 1 && thread.windows.last() == Some(&id))
+        else {
+            return Err(CoreError::InvalidReparent(id, new_parent));
+        };
+        let Some((to, _)) = self
+            .threads
+            .iter()
+            .find(|(_, thread)| thread.windows.last() == Some(&new_parent))
+        else {
+            return Err(CoreError::InvalidReparent(id, new_parent));
+        };
+        if from == to {
+            return Err(CoreError::InvalidReparent(id, new_parent));
+        }
+
+        // Move the window between the threads.
+        if let Some(thread) = self.threads.get_mut(from) {
+            _ = thread.windows.pop();
+        }
+        if let Some(thread) = self.threads.get_mut(to) {
+            thread.windows.push(id);
+        }
+
+        // Swap the disabled parent.
+        if !is_global_disable {
+            let entry = self.states.get_mut(&id).unwrap();
+            let disabled = entry.disabled.replace(vec![new_parent]).unwrap_or_default();
+            for disabled_id in disabled {
+                if let Some(disabled_entry) = self.states.get_mut(&disabled_id) {
+                    disabled_entry.enabled = true;
+                }
+            }
+            self.states.get_mut(&new_parent).unwrap().enabled = false;
+        }
+        self.states.get_mut(&id).unwrap().parent = Some(new_parent);
+        Ok(())
+    }
+
     /// Close an entire main window thread, using any window Id in the thread.
     ///
     /// This method is only called once checks for unsaved data is done
@@ -292,28 +637,22 @@ impl Manager {
         };
 
         // Find the thread to close.
-        let index = self.threads.vec.iter().position(|thread| {
-            if thread.is_some() {
-                let actual = thread.as_ref().unwrap();
-                for id_ in actual {
-                    if id_ == &id {
-                        return true;
-                    }
-                }
-            }
-            false
-        })
-        .unwrap();
+        let index = self
+            .threads
+            .iter()
+            .find(|(_, thread)| thread.windows.contains(&id))
+            .map(|(index, _)| index)
+            .unwrap();
 
         // Close the thread.
-        let mut thread = self.threads.vec[index].as_ref().unwrap().clone();
+        let mut thread = self.threads.get(index).unwrap().windows.clone();
         thread.reverse();
         let mut tasks = Task::none();
         for state_id in thread {
             tasks = tasks.chain(window::close(state_id));
         }
         tasks = tasks.chain(Task::done(Message::ThreadClosed(index)));
-        trace!("{:?}", self.threads.vec);
+        trace!("{:?}", self.threads);
         Ok(tasks)
     }
     
@@ -345,21 +684,22 @@ impl Manager {
         }
 
         // Remove window ID from the window thread
-        for thread in &mut self.threads.vec {
-            if thread.is_some() {
-                let actual = thread.as_mut().unwrap();
-                if actual.last() == Some(&id) {
-                    _ = actual.pop();
-                    break;
-                }
-            }
+        if let Some((_, thread)) = self
+            .threads
+            .iter_mut()
+            .find(|(_, thread)| thread.windows.last() == Some(&id))
+        {
+            _ = thread.windows.pop();
         }
 
-        // Remove the state
-        let entry = self.states.remove(&id).unwrap();
+        // Remove the state, a failed clean up is only logged as the window is already closed.
+        let mut entry = self.states.remove(&id).unwrap();
         if entry.state.is_reusable() {
             debug!("window_closed(): cached reusable state for {:?}", id);
-            self.reusable.insert(entry.state.window_type(), entry.state);
+            entry.state.reset();
+            self.cache_reusable(entry.state);
+        } else if let Err(error) = entry.state.on_close() {
+            warn!("window_closed(): failed to clean up the state for {:?}: {}", id, error);
         }
         trace!("window_closed(): removed state for {:?}", id);
         Ok(())
@@ -371,7 +711,7 @@ impl Manager {
         index: usize,
     ) -> Result<(), CoreError> {
         trace!("thread_closed(): removed thread {:?}", index);
-        let _ = self.threads.take(index); 
+        let _ = self.threads.remove(index);
         Ok(())
     }
 }
@@ -382,9 +722,13 @@ impl Manager {
 
 /// Try to create the `iced` window for the specified window type, using the
 /// `iced` windows Settings located in the application's session data.
+/// The window is cascaded by `cascade` steps of `CASCADE_OFFSET` from the stored position, or from
+/// the centre of the first monitor, see `cascade_position()`.
 fn try_create(
     session: &mut Session,
     window_type: WindowType,
+    monitors: &[Rectangle],
+    cascade: usize,
 ) -> Result<(window::Id, Task<window::Id>), CoreError> {
     let Some(defaults) = WINDOW_DEFAULT_DATA.get(&window_type.as_str()) else {
         return Err(CoreError::WindowTypeNotFound(
@@ -399,104 +743,682 @@ fn try_create(
             WindowData {
                 size: defaults.size.clone(),
                 position: None,
+                maximized: false,
             },
         );
     }
-    let data = session.windows.get(&window_type).unwrap();
-    let position = if data.position.is_none() {
-        window::Position::Centered
-    } else {
-        let value = data.position.as_ref().unwrap();
-        window::Position::Specific(Point {
+    let data = session.windows.get_mut(&window_type).unwrap();
+
+    // A stored size smaller than the minimum, such as from an older version, is clamped.
+    data.size = clamp_size(data.size, defaults.size_min);
+
+    // A maximised window is opened centred using the stored floating size, as the monitor the
+    // window was maximised on may no longer exist, and then maximised once opened. A position
+    // on a monitor that no longer exists is also centred.
+    let position = match data.position {
+        Some(value) if !data.maximized && is_visible(value, data.size, monitors) => Some(value),
+        _ => None,
+    };
+    let position = match cascade {
+        0 => position,
+        _ => position
+            .or_else(|| centred_position(data.size, monitors))
+            .map(|value| cascade_position(value, data.size, cascade, monitors)),
+    };
+    let position = match position {
+        Some(value) => window::Position::Specific(Point {
             x: value.0,
             y: value.1,
-        })
+        }),
+        None => window::Position::Centered,
     };
     let settings = window::Settings {
         size: Size::new(data.size.0, data.size.1),
+        min_size: Some(Size::new(defaults.size_min.0, defaults.size_min.1)),
         resizable: defaults.resizable,
         position,
         exit_on_close_request: false,
         ..Default::default()
     };
-    Ok(window::open(settings))
+    let (id, task) = window::open(settings);
+    if data.maximized {
+        return Ok((id, task.chain(window::maximize(id, true))));
+    }
+    Ok((id, task))
 }
 
-#[derive(Debug)]
-struct VecOption<T> {
+/// Check another window can be opened, when `count` windows of the window type are opened.
+fn within_limit(
+    window_type: WindowType,
     count: usize,
-    vec: Vec<Option<T>>
+    max_instances: Option<usize>,
+) -> Result<(), CoreError> {
+    match max_instances {
+        Some(limit) if count >= limit => Err(CoreError::WindowLimitReached(window_type, limit)),
+        _ => Ok(()),
+    }
 }
 
-impl<T> VecOption<T> {
+/// Clamp the size up to the minimum size.
+fn clamp_size(size: (f32, f32), size_min: (f32, f32)) -> (f32, f32) {
+    (size.0.max(size_min.0), size.1.max(size_min.1))
+}
+
+/// The position of a window centred on the first monitor, `None` when the monitors are unknown.
+fn centred_position(size: (f32, f32), monitors: &[Rectangle]) -> Option<(f32, f32)> {
+    monitors.first().map(|monitor| {
+        (
+            monitor.x + (monitor.width - size.0).max(0f32) / 2f32,
+            monitor.y + (monitor.height - size.1).max(0f32) / 2f32,
+        )
+    })
+}
+
+/// The position of a window cascaded `steps` times by `CASCADE_OFFSET` from the position. When the
+/// window would extend beyond the monitor containing the position, the cascade wraps to the top
+/// left corner of that monitor.
+fn cascade_position(
+    position: (f32, f32),
+    size: (f32, f32),
+    steps: usize,
+    monitors: &[Rectangle],
+) -> (f32, f32) {
+    let monitor = monitors
+        .iter()
+        .find(|monitor| monitor.contains(Point::new(position.0, position.1)));
+    let mut result = position;
+    for _ in 0..steps {
+        result = (result.0 + CASCADE_OFFSET, result.1 + CASCADE_OFFSET);
+        if let Some(monitor) = monitor {
+            if result.0 + size.0 > monitor.x + monitor.width
+                || result.1 + size.1 > monitor.y + monitor.height
+            {
+                result = (monitor.x, monitor.y);
+            }
+        }
+    }
+    result
+}
+
+/// Indicates whether the top left corner region of the window, where the title bar usually is,
+/// lies within any of the monitors. Always visible when the monitors are unknown.
+fn is_visible(position: (f32, f32), size: (f32, f32), monitors: &[Rectangle]) -> bool {
+    if monitors.is_empty() {
+        return true;
+    }
+    let grip = Rectangle::new(
+        Point::new(position.0, position.1),
+        Size::new(size.0.min(50f32), size.1.min(30f32)),
+    );
+    monitors.iter().any(|monitor| monitor.intersects(&grip))
+}
+
+/// The window threads, with stable indices as used by `Message::ThreadClosed`. Indices of
+/// removed threads are reused.
+#[derive(Debug)]
+struct ThreadTable<T> {
+    slots: Vec<Option<T>>,
+    free: Vec<usize>, // Indices of the empty slots.
+}
+
+impl<T> ThreadTable<T> {
     fn new() -> Self {
-        VecOption::<T> {
-            count: 0,
-            vec: Vec::<Option<T>>::new(),
+        ThreadTable::<T> {
+            slots: Vec::<Option<T>>::new(),
+            free: Vec::<usize>::new(),
         }
     }
 
-    fn push(&mut self, element: T) -> usize {
-        let mut len = self.vec.len();
-        trace!("push(): start: len: {}, count: {}", len, self.count);
-        if self.count < len {
-            if let Some(index) = self.vec.iter().position(|x| x.is_none()) {
-                self.vec[index] = Some(element);
-                len = index;
+    /// The number of threads.
+    fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Insert the thread, returning its index.
+    fn insert(&mut self, element: T) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(element);
+                index
+            }
+            None => {
+                self.slots.push(Some(element));
+                self.slots.len() - 1
             }
-        } else {
-            self.vec.push(Some(element));
         }
-        self.count += 1;
-        trace!("push(): end: len: {}, count: {}, index: {}", self.vec.len(), self.count, len);
-        len
     }
 
-    fn pop(&mut self) -> Option<T> {
-        trace!("pop(): start: len: {}, count: {}", self.vec.len(), self.count);
-        if self.count == 0 {
-            return None;
-        }
-        let element = self.vec.pop().unwrap();
+    /// Remove the thread at the index, freeing the index for reuse.
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let element = self.slots.get_mut(index)?.take();
         if element.is_some() {
-            self.count -= 1;
-            if self.count == 0 {
-                self.vec.clear();
-            }
+            self.free.push(index);
         }
-        trace!("pop(): end: len: {}, count: {}", self.vec.len(), self.count);
         element
     }
 
-    fn replace(&mut self, index: usize, element: Option<T>) -> Option<T> {
-        trace!("replace(): start: len: {}, count: {}, index: {}", self.vec.len(), self.count, index);
-        let old = self.vec[index].take();
-        self.vec[index] = element;
-        trace!("replace(): end: len: {}, count: {}", self.vec.len(), self.count);
-        old
+    fn get(&self, index: usize) -> Option<&T> {
+        self.slots.get(index)?.as_ref()
     }
 
-    fn take(&mut self, index: usize) -> Option<T> {
-        trace!("take(): start: len: {}, count: {}, index: {}", self.vec.len(), self.count, index);
-        if self.count == 0 {
-            return None;
-        }
-        let element = self.vec[index].take();
-        if element.is_some() {
-            self.count -= 1;
-            if self.count == 0 {
-                self.vec.clear();
-            }
-        }
-        trace!("take(): end: len: {}, count: {}", self.vec.len(), self.count);
-        element
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slots.get_mut(index)?.as_mut()
+    }
+
+    /// Iterate over the threads with their indices.
+    fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|element| (index, element)))
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_mut().map(|element| (index, element)))
     }
 }
 
+/// A window thread, the root window followed by the windows opened on top of it.
+#[derive(Debug)]
+struct Thread {
+    windows: Vec<window::Id>,
+    name: Option<String>, // Name of the data of the root window, such as the opened file.
+}
+
 /// Just a simple struct with named fields.
 struct Entry {
     state: Box<dyn AnyWindowTrait>,    // The window state
     enabled: bool,                     // This window enabled
     parent: Option<window::Id>,        // Parent
     disabled: Option<Vec<window::Id>>, // Windows disabled by this window::Id
+    locked_size: Option<Size>,         // Size restored on resizing, while resizing is locked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::localisation::Localisation;
+    use iced::{widget::text, Element};
+    use std::{any::Any, cell::Cell, rc::Rc};
+
+    /// A window state of any window type, without localisation.
+    struct TestState {
+        window_type: WindowType,
+        title: String,
+        reusable: bool,
+        hooks: Rc<Cell<(usize, usize)>>, // The calls of `on_close()` and `reset()`.
+    }
+
+    impl TestState {
+        fn new(window_type: WindowType) -> Box<Self> {
+            TestState::hooked(window_type, false, Rc::new(Cell::new((0, 0))))
+        }
+
+        fn hooked(
+            window_type: WindowType,
+            reusable: bool,
+            hooks: Rc<Cell<(usize, usize)>>,
+        ) -> Box<Self> {
+            Box::new(TestState {
+                window_type,
+                title: String::new(),
+                reusable,
+                hooks,
+            })
+        }
+    }
+
+    impl AnyWindowTrait for TestState {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    impl WindowTrait for TestState {
+        fn title<'a>(&'a self, _string_cache: &'a StringCache) -> &'a String {
+            &self.title
+        }
+
+        fn view<'a>(
+            &'a self,
+            _id: window::Id,
+            _localisation: &Localisation,
+            _string_cache: &'a StringCache,
+        ) -> Element<'a, Message> {
+            text("").into()
+        }
+
+        fn window_type(&self) -> WindowType {
+            self.window_type.clone()
+        }
+
+        fn is_reusable(&self) -> bool {
+            self.reusable
+        }
+
+        fn on_close(&mut self) -> Result<(), ApplicationError> {
+            let (closed, reset) = self.hooks.get();
+            self.hooks.set((closed + 1, reset));
+            Ok(())
+        }
+
+        fn reset(&mut self) {
+            let (closed, reset) = self.hooks.get();
+            self.hooks.set((closed, reset + 1));
+        }
+    }
+
+    /// The Id of the window inserted by `create`, discarding the task opening the window.
+    fn created<F>(manager: &mut Manager, create: F) -> window::Id
+    where
+        F: FnOnce(&mut Manager) -> Result<Task<Message>, CoreError>,
+    {
+        let before = manager.states.keys().copied().collect::<Vec<window::Id>>();
+        let _ = create(manager).unwrap();
+        *manager.states.keys().find(|id| !before.contains(id)).unwrap()
+    }
+
+    fn open_thread(
+        manager: &mut Manager,
+        session: &mut Session,
+        window_type: WindowType,
+    ) -> window::Id {
+        created(manager, |manager| manager.try_create_thread(session, TestState::new(window_type)))
+    }
+
+    fn open_window(
+        manager: &mut Manager,
+        session: &mut Session,
+        window_type: WindowType,
+        parent: window::Id,
+    ) -> window::Id {
+        created(manager, |manager| {
+            manager.try_create_window(session, TestState::new(window_type), parent)
+        })
+    }
+
+    #[test]
+    fn ids_of_type_matches() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        assert!(manager.ids_of_type(WindowType::Main).is_empty());
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let default = open_thread(&mut manager, &mut session, WindowType::Default);
+        assert_eq!(manager.ids_of_type(WindowType::Main), vec![first]);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        let about = open_window(&mut manager, &mut session, WindowType::About, second);
+        assert_eq!(manager.ids_of_type(WindowType::Main), vec![first, second]);
+        assert_eq!(manager.ids_of_type(WindowType::Default), vec![default]);
+        assert_eq!(manager.ids_of_type(WindowType::About), vec![about]);
+        assert!(manager.ids_of_type(WindowType::LogViewer).is_empty());
+    }
+
+    fn monitor(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    fn is_visible_without_monitors() {
+        assert!(is_visible((-5000f32, -5000f32), (800f32, 600f32), &[]));
+    }
+
+    #[test]
+    fn is_visible_on_any_monitor() {
+        let monitors = [
+            monitor(0f32, 0f32, 1920f32, 1080f32),
+            monitor(1920f32, 0f32, 1280f32, 1024f32),
+        ];
+        assert!(is_visible((100f32, 100f32), (800f32, 600f32), &monitors));
+        assert!(is_visible((2000f32, 500f32), (800f32, 600f32), &monitors));
+
+        // Only the top left corner region is required to be on a monitor.
+        assert!(is_visible((-40f32, -20f32), (800f32, 600f32), &monitors));
+    }
+
+    #[test]
+    fn is_visible_off_screen() {
+        let monitors = [monitor(0f32, 0f32, 1920f32, 1080f32)];
+        assert!(!is_visible((2500f32, 100f32), (800f32, 600f32), &monitors));
+        assert!(!is_visible((100f32, -700f32), (800f32, 600f32), &monitors));
+        assert!(!is_visible((-60f32, 100f32), (800f32, 600f32), &monitors));
+    }
+
+    #[test]
+    fn clamp_size_to_minimum() {
+        let size_min = (300f32, 250f32);
+        assert_eq!(clamp_size((100f32, 600f32), size_min), (300f32, 600f32));
+        assert_eq!(clamp_size((800f32, 50f32), size_min), (800f32, 250f32));
+        assert_eq!(clamp_size((800f32, 600f32), size_min), (800f32, 600f32));
+    }
+
+    #[test]
+    fn undersized_stored_geometry_is_clamped() {
+        let defaults = WINDOW_DEFAULT_DATA.get(WindowType::Preferences.as_str()).unwrap();
+        let mut session = Session::default();
+        session.windows.insert(
+            WindowType::Preferences,
+            WindowData {
+                size: (10f32, 10f32),
+                position: None,
+                maximized: false,
+            },
+        );
+        let _ = try_create(&mut session, WindowType::Preferences, &[], 0).unwrap();
+        assert_eq!(session.windows[&WindowType::Preferences].size, defaults.size_min);
+    }
+
+    /// The window types of the reusable states cache, sorted.
+    fn cached(manager: &Manager) -> Vec<WindowType> {
+        manager.reusable.keys().cloned().collect()
+    }
+
+    #[test]
+    fn reusable_cache_evicts_least_recently_cached() {
+        let mut manager = Manager::try_new().unwrap();
+        for window_type in [
+            WindowType::About,
+            WindowType::LogViewer,
+            WindowType::Preferences,
+        ] {
+            manager.cache_reusable(TestState::new(window_type));
+        }
+        assert_eq!(manager.reusable.len(), REUSABLE_LIMIT);
+
+        // Reusing and caching again makes the About state the most recently cached.
+        let about = manager.use_reusable(WindowType::About).unwrap();
+        manager.cache_reusable(about);
+        manager.cache_reusable(TestState::new(WindowType::Information));
+        assert_eq!(manager.reusable.len(), REUSABLE_LIMIT);
+        assert!(!manager.reusable.contains_key(&WindowType::LogViewer));
+        let mut expected = vec![
+            WindowType::Information,
+            WindowType::Preferences,
+            WindowType::About,
+            WindowType::Default,
+        ];
+        expected.sort();
+        assert_eq!(cached(&manager), expected);
+    }
+
+    #[test]
+    fn reusable_cache_keeps_default() {
+        let mut manager = Manager::try_new().unwrap();
+        for window_type in [
+            WindowType::About,
+            WindowType::LogViewer,
+            WindowType::Preferences,
+            WindowType::Information,
+            WindowType::CommandPalette,
+        ] {
+            manager.cache_reusable(TestState::new(window_type));
+            assert!(manager.reusable.contains_key(&WindowType::Default));
+            assert!(manager.reusable.len() <= REUSABLE_LIMIT);
+        }
+        manager.clear_reusable();
+        assert_eq!(cached(&manager), vec![WindowType::Default]);
+        assert_eq!(manager.reusable_order, VecDeque::from([WindowType::Default]));
+    }
+
+    #[test]
+    fn thread_table_reuses_freed_indices() {
+        let mut table = ThreadTable::<&str>::new();
+        assert_eq!(table.insert("first"), 0);
+        assert_eq!(table.insert("second"), 1);
+        assert_eq!(table.insert("third"), 2);
+        assert_eq!(table.remove(1), Some("second"));
+        assert_eq!(table.remove(1), None);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(1), None);
+
+        // The indices of the remaining threads are stable.
+        assert_eq!(table.iter().collect::<Vec<_>>(), vec![(0, &"first"), (2, &"third")]);
+
+        // The freed index is reused, the most recently freed first.
+        assert_eq!(table.remove(0), Some("first"));
+        assert_eq!(table.insert("fourth"), 0);
+        assert_eq!(table.insert("fifth"), 1);
+        assert_eq!(table.insert("sixth"), 3);
+        assert_eq!(table.len(), 4);
+        *table.get_mut(2).unwrap() = "renamed";
+        assert_eq!(
+            table.iter().map(|(_, element)| *element).collect::<Vec<_>>(),
+            vec!["fourth", "fifth", "renamed", "sixth"]
+        );
+        assert_eq!(table.remove(7), None);
+    }
+
+    #[test]
+    fn thread_list_follows_closed_threads() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        assert_eq!(manager.thread_list(), vec![first, second]);
+        let (index, _) = manager
+            .threads
+            .iter()
+            .find(|(_, thread)| thread.windows[0] == first)
+            .unwrap();
+        manager.thread_closed(index).unwrap();
+        assert_eq!(manager.thread_count(), 1);
+        assert_eq!(manager.thread_list(), vec![second]);
+        let third = open_thread(&mut manager, &mut session, WindowType::Main);
+        assert_eq!(manager.thread_list(), vec![third, second]);
+    }
+
+    #[test]
+    fn thread_names_collide_and_rename() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        let about = open_window(&mut manager, &mut session, WindowType::About, second);
+        assert_eq!(manager.thread_named("a.txt"), None);
+        manager.set_thread_name(&first, Some("a.txt".to_string()));
+
+        // The name is set by any window of the thread, and the earliest thread wins a collision.
+        manager.set_thread_name(&about, Some("a.txt".to_string()));
+        assert_eq!(manager.thread_named("a.txt"), Some(first));
+        assert_eq!(
+            manager.thread_names(),
+            vec![(first, "a.txt".to_string()), (second, "a.txt".to_string())]
+        );
+
+        // Renamed after Save As.
+        manager.set_thread_name(&second, Some("b.txt".to_string()));
+        assert_eq!(manager.thread_named("b.txt"), Some(second));
+        assert_eq!(manager.thread_named("a.txt"), Some(first));
+        manager.set_thread_name(&first, None);
+        assert_eq!(manager.thread_named("a.txt"), None);
+        assert_eq!(manager.thread_names(), vec![(second, "b.txt".to_string())]);
+    }
+
+    #[test]
+    fn thread_names_omit_closed_threads() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        manager.set_thread_name(&first, Some("a.txt".to_string()));
+        manager.set_thread_name(&second, Some("b.txt".to_string()));
+        let (index, _) = manager
+            .threads
+            .iter()
+            .find(|(_, thread)| thread.windows[0] == second)
+            .unwrap();
+        manager.thread_closed(index).unwrap();
+        assert_eq!(manager.thread_names(), vec![(first, "a.txt".to_string())]);
+    }
+
+    #[test]
+    fn close_multiple_in_any_order() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let main = open_thread(&mut manager, &mut session, WindowType::Main);
+        let about = open_window(&mut manager, &mut session, WindowType::About, main);
+        let information = open_window(&mut manager, &mut session, WindowType::Information, about);
+        assert_eq!(manager.is_enabled(&main), Some(false));
+        assert_eq!(manager.is_enabled(&about), Some(false));
+
+        // The parent is provided before its child.
+        let mut ids = vec![about, information];
+        manager.sort_for_closing(&mut ids);
+        assert_eq!(ids, vec![information, about]);
+        let _ = manager.close_multiple(vec![about, information]).unwrap();
+        for id in ids {
+            manager.window_closed(id).unwrap();
+        }
+        assert_eq!(manager.is_enabled(&main), Some(true));
+        assert_eq!(manager.thread_list(), vec![main]);
+        assert_eq!(manager.states.len(), 1);
+    }
+
+    #[test]
+    fn reparent_moves_window_between_threads() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        let about = open_window(&mut manager, &mut session, WindowType::About, first);
+        assert_eq!(manager.is_enabled(&first), Some(false));
+        manager.reparent(about, second).unwrap();
+        assert_eq!(manager.parent(&about), Some(second));
+        assert_eq!(manager.is_enabled(&first), Some(true));
+        assert_eq!(manager.is_enabled(&second), Some(false));
+        assert!(manager.is_same_thread(&about, &second));
+        assert!(!manager.is_same_thread(&about, &first));
+        assert_eq!(manager.thread_position(&about), Some(1));
+    }
+
+    #[test]
+    fn reparent_rejects_invalid_target() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        let second = open_thread(&mut manager, &mut session, WindowType::Main);
+        let about = open_window(&mut manager, &mut session, WindowType::About, first);
+        let information = open_window(&mut manager, &mut session, WindowType::Information, about);
+
+        // Unknown windows, then a window that isn't the newest of its thread, a root window, and a
+        // target within the window's own thread.
+        let unknown = window::Id::unique();
+        assert!(matches!(
+            manager.reparent(unknown, second),
+            Err(CoreError::WindowIdNotFound(..))
+        ));
+        assert!(matches!(
+            manager.reparent(information, unknown),
+            Err(CoreError::WindowIdNotFound(..))
+        ));
+        for (id, new_parent) in [(about, second), (second, information), (information, first)] {
+            assert!(matches!(
+                manager.reparent(id, new_parent),
+                Err(CoreError::InvalidReparent(..))
+            ));
+        }
+
+        // The rejections leave the windows unchanged.
+        assert_eq!(manager.parent(&information), Some(about));
+        assert_eq!(manager.is_enabled(&about), Some(false));
+        assert_eq!(manager.is_enabled(&second), Some(true));
+        assert_eq!(manager.thread_position(&information), Some(2));
+    }
+
+
+    #[test]
+    fn on_close_is_called_once_per_closed_window() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        let main = open_thread(&mut manager, &mut session, WindowType::Main);
+        let hooks = Rc::new(Cell::new((0, 0)));
+        let about = created(&mut manager, |manager| {
+            let state = TestState::hooked(WindowType::About, false, Rc::clone(&hooks));
+            manager.try_create_window(&mut session, state, main)
+        });
+        manager.window_closed(about).unwrap();
+        assert_eq!(hooks.get(), (1, 0));
+
+        // A repeated close event of the same window is an error, without calling `on_close()`.
+        assert!(manager.window_closed(about).is_err());
+        assert_eq!(hooks.get(), (1, 0));
+
+        // A reusable state is reset instead, and cached.
+        let reusable = Rc::new(Cell::new((0, 0)));
+        let default = created(&mut manager, |manager| {
+            let state = TestState::hooked(WindowType::Default, true, Rc::clone(&reusable));
+            manager.try_create_thread(&mut session, state)
+        });
+        manager.window_closed(default).unwrap();
+        assert_eq!(reusable.get(), (0, 1));
+        let cached = manager.reusable.get(&WindowType::Default).unwrap();
+        assert!(cached.as_any().downcast_ref::<TestState>().is_some());
+    }
+
+
+    #[test]
+    fn cascade_offsets_from_position() {
+        let monitors = [monitor(0f32, 0f32, 1920f32, 1080f32)];
+        let size = (800f32, 600f32);
+        assert_eq!(cascade_position((100f32, 100f32), size, 0, &monitors), (100f32, 100f32));
+        assert_eq!(cascade_position((100f32, 100f32), size, 1, &monitors), (130f32, 130f32));
+        assert_eq!(cascade_position((100f32, 100f32), size, 3, &monitors), (190f32, 190f32));
+
+        // Without known monitors there is no wrapping.
+        assert_eq!(cascade_position((2000f32, 2000f32), size, 2, &[]), (2060f32, 2060f32));
+    }
+
+    #[test]
+    fn cascade_wraps_within_monitor() {
+        let monitors = [
+            monitor(0f32, 0f32, 1920f32, 1080f32),
+            monitor(1920f32, 0f32, 1280f32, 1024f32),
+        ];
+        let size = (800f32, 600f32);
+
+        // The bottom edge of the second step would be beyond the monitor at 1080.
+        assert_eq!(cascade_position((100f32, 430f32), size, 1, &monitors), (130f32, 460f32));
+        assert_eq!(cascade_position((100f32, 430f32), size, 2, &monitors), (0f32, 0f32));
+        assert_eq!(cascade_position((100f32, 430f32), size, 3, &monitors), (30f32, 30f32));
+
+        // Wrapping is to the monitor containing the position.
+        assert_eq!(cascade_position((2400f32, 100f32), size, 1, &monitors), (1920f32, 0f32));
+    }
+
+
+    #[test]
+    fn window_limit_reached() {
+        assert!(within_limit(WindowType::Main, 0, Some(2)).is_ok());
+        assert!(within_limit(WindowType::Main, 1, Some(2)).is_ok());
+        assert!(matches!(
+            within_limit(WindowType::Main, 2, Some(2)),
+            Err(CoreError::WindowLimitReached(WindowType::Main, 2))
+        ));
+        assert!(matches!(
+            within_limit(WindowType::About, 3, Some(1)),
+            Err(CoreError::WindowLimitReached(WindowType::About, 1))
+        ));
+        assert!(within_limit(WindowType::Main, 100, None).is_ok());
+    }
+
+    #[test]
+    fn count_by_type_counts_opened_windows() {
+        let mut manager = Manager::try_new().unwrap();
+        let mut session = Session::default();
+        assert_eq!(manager.count_by_type(WindowType::Main), 0);
+        let first = open_thread(&mut manager, &mut session, WindowType::Main);
+        open_thread(&mut manager, &mut session, WindowType::Main);
+        open_window(&mut manager, &mut session, WindowType::About, first);
+        assert_eq!(manager.count_by_type(WindowType::Main), 2);
+        assert_eq!(manager.count_by_type(WindowType::About), 1);
+
+        // The window types are unlimited.
+        assert!(manager.check_limit(WindowType::Main).is_ok());
+    }
 }
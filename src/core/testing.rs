@@ -0,0 +1,50 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The helpers shared by the unit tests.
+
+use crate::{application::constants, core::localisation::Localisation};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A new empty directory within the temporary directory, unique to the test and the process. The
+/// directory and its contents are removed when dropped, even when the test fails.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(test: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!(
+            "{}_{}_{}",
+            constants::PACKAGE_NAME,
+            test,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// The directory of the crate, containing the `l10n` directory of the localisation database.
+pub fn crate_path() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// The localisation of the crate's localisation database for the language.
+pub fn localisation(language: &str) -> Localisation {
+    Localisation::try_new_with_path(crate_path(), language).unwrap()
+}
@@ -30,6 +30,9 @@ use crate::iced_aw::widgets::sidebar::TabLabel;
 #[cfg(not(feature = "iced_aw"))]
 use iced_aw::sidebar::TabLabel;
 
+#[cfg(any(test, feature = "debug_snapshot"))]
+use serde::Serialize;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
@@ -44,6 +47,18 @@ use std::sync::Arc as RefCount;
 // ----- Window state traits
 //
 
+/// A serialisable summary of the visible state of a window, for asserting the results of the
+/// manager operations without a running `iced` event loop.
+#[cfg(any(test, feature = "debug_snapshot"))]
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WindowSnapshot {
+    pub window_type: WindowType,
+    pub enabled: bool, // Set by `Manager::debug_snapshot()`, as the manager tracks it.
+    pub title: String,
+    pub is_unsaved: bool,
+    pub is_first_use: bool,
+}
+
 /// Supertrait for Any
 pub trait AnyWindowTrait: Any + WindowTrait {
     fn as_any(&self) -> &dyn Any;
@@ -55,6 +70,10 @@ pub trait AnyWindowTrait: Any + WindowTrait {
 pub trait WindowTrait {
     // `iced` specific methods
     /// Returns a string for the window title bar (contains the windows decorations).
+    ///
+    /// Windows having a constant title return `StringCache::title()`, thus the title follows the
+    /// language changes. Windows having a title with instance data, such as a file name, return
+    /// their own title, which `try_localise()` must localise again.
     fn title<'a>(&'a self, string_cache: &'a StringCache) -> &String;
 
     /// The update method called by the applications main `update()` function to handle messages
@@ -92,13 +111,53 @@ pub trait WindowTrait {
         false
     }
 
+    /// Release the resources held by the state, such as database connections and temporary files,
+    /// once the window has been closed. Called exactly once by `Manager::window_closed()`, before
+    /// the state is dropped. Not called for reusable states, see `reset()`.
+    fn on_close(&mut self) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    /// Reset a reusable state for its next use, once the window has been closed. Called by
+    /// `Manager::window_closed()` instead of `on_close()`, before the state is cached.
+    fn reset(&mut self) {}
+
+    /// Called when the window gains the focus, such as to refresh data that may have been changed
+    /// externally. A failure is displayed as a warning toast.
+    fn on_focus(&mut self) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    /// Called when the window loses the focus.
+    fn on_blur(&mut self) {}
+
+    /// Indicates whether only a single window of this window type may be opened at a time.
+    /// When such a window is already opened, `Manager::try_create_window()` focuses the opened
+    /// window instead of creating another.
+    fn is_singleton(&self) -> bool {
+        false
+    }
+
+    /// Informs the state that files are being dragged over the window, or are no longer being
+    /// dragged over the window. Windows accepting dropped files can highlight themselves.
+    #[allow(unused_variables)]
+    fn set_file_hovered(&mut self, hovered: bool) {}
+
+    /// The navigation of the state, for the mouse back and forward buttons. `None` ignores the
+    /// buttons, thus states with a navigation history return themselves.
+    fn as_navigable(&mut self) -> Option<&mut dyn NavigableTrait> {
+        None
+    }
+
     /// Indicates whether the window being displayed disables all windows or just the parent
     /// window of the window thread.
     fn is_global_disable(&self) -> bool {
         false
     }
 
-    /// Try to update dynamic localised strings stored in the state itself.
+    /// Try to update dynamic localised strings stored in the state itself, called for every
+    /// window after the language has changed and the `StringCache` has been updated. Must
+    /// localise again any title returned by `title()` that is owned by the state.
     /// 
     /// Note: All data must be present within the state, that is required for the updating
     /// of the localised strings 
@@ -109,6 +168,19 @@ pub trait WindowTrait {
     ) -> Result<(), ApplicationError> {
         Ok(())
     }
+
+    /// A summary of the visible state of the window. The window is reported as enabled, use
+    /// `Manager::debug_snapshot()` for the actual enabled state.
+    #[cfg(any(test, feature = "debug_snapshot"))]
+    fn debug_snapshot(&self, string_cache: &StringCache) -> WindowSnapshot {
+        WindowSnapshot {
+            window_type: self.window_type(),
+            enabled: true,
+            title: self.title(string_cache).clone(),
+            is_unsaved: false,
+            is_first_use: false,
+        }
+    }
 }
 
 /// Trait of methods to be implemented for window states having saveable data.
@@ -128,6 +200,30 @@ pub trait SaveDataTrait {
     }
 }
 
+/// Trait of methods to be implemented for window states having an undo history, usually kept in
+/// an `UndoStack`.
+pub trait UndoableTrait {
+    /// Revert the most recent change, if any.
+    fn undo(&mut self);
+
+    /// Apply again the most recently reverted change, if any.
+    fn redo(&mut self);
+
+    fn can_undo(&self) -> bool;
+
+    fn can_redo(&self) -> bool;
+}
+
+/// Trait of methods to be implemented for window states having a navigation history, such as the
+/// visited locations of a document. The methods do nothing by default.
+pub trait NavigableTrait {
+    /// Navigate to the previous location, if any.
+    fn navigate_back(&mut self) {}
+
+    /// Navigate to the next location, if any.
+    fn navigate_forward(&mut self) {}
+}
+
 //
 // ----- Localisation traits
 //
@@ -140,11 +236,19 @@ pub trait AnyLocalisedTrait: Any + LocalisedTrait + Debug {
 
 /// Trait for localisations of the windows.
 pub trait LocalisedTrait {
+    /// Update the localised strings when the default language has changed.
     #[allow(unused_variables)]
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         Ok(())
     }
 
+    /// Reload the localised strings regardless of the default language, such as when the
+    /// fallback chain has changed.
+    #[allow(unused_variables)]
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        Ok(())
+    }
+
     fn title(&self) -> &String;
 
     fn string(&self, index: usize) -> &String;
@@ -166,6 +270,11 @@ pub trait TabTrait {
         TabLabel::Text(self.title(string_cache))
     }
 
+    /// The localised labels of the tab's settings, searched by the search field of the window.
+    fn searchable_labels(&self, string_cache: &StringCache) -> Vec<String> {
+        vec![self.title(string_cache)]
+    }
+
     /// To display the tab area.
     fn view<'a>(
         &'a self,
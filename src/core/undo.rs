@@ -0,0 +1,180 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! A bounded undo stack, with a marker of the position of the last save.
+
+use std::collections::VecDeque;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The changes of a window state, where the changes before the current position can be undone,
+/// and the changes after it can be redone. Pushing a change discards the changes that could be
+/// redone. When the depth is exceeded, the oldest change is discarded.
+#[derive(Debug)]
+pub struct UndoStack<T> {
+    changes: VecDeque<T>,
+    position: usize, // The number of changes currently applied.
+    depth: usize,
+    saved: Option<usize>, // The position at the last save, `None` if no longer reachable.
+}
+
+impl<T> UndoStack<T> {
+    /// Create an empty stack, being in the saved state. A `depth` of 0 is treated as 1.
+    pub fn new(depth: usize) -> Self {
+        UndoStack {
+            changes: VecDeque::new(),
+            position: 0,
+            depth: depth.max(1),
+            saved: Some(0),
+        }
+    }
+
+    /// Record an applied change.
+    pub fn push(&mut self, change: T) {
+        self.changes.truncate(self.position);
+        if self.saved.is_some_and(|saved| saved > self.position) {
+            self.saved = None;
+        }
+        self.changes.push_back(change);
+        self.position += 1;
+        if self.changes.len() > self.depth {
+            self.changes.pop_front();
+            self.position -= 1;
+            self.saved = self.saved.and_then(|saved| saved.checked_sub(1));
+        }
+    }
+
+    /// Returns the change to be reverted, if any.
+    pub fn undo(&mut self) -> Option<&T> {
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        self.changes.get(self.position)
+    }
+
+    /// Returns the change to be applied again, if any.
+    pub fn redo(&mut self) -> Option<&T> {
+        if self.position == self.changes.len() {
+            return None;
+        }
+        self.position += 1;
+        self.changes.get(self.position - 1)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position < self.changes.len()
+    }
+
+    /// Mark the current position as saved.
+    pub fn mark_saved(&mut self) {
+        self.saved = Some(self.position);
+    }
+
+    /// Indicates whether the current position differs from the last saved position.
+    pub fn is_unsaved(&self) -> bool {
+        self.saved != Some(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_changes() {
+        let mut stack = UndoStack::<u32>::new(10);
+        assert!(!stack.can_undo() && !stack.can_redo());
+        assert_eq!(stack.undo(), None);
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(stack.undo(), Some(&2));
+        assert_eq!(stack.undo(), Some(&1));
+        assert_eq!(stack.undo(), None);
+        assert_eq!(stack.redo(), Some(&1));
+        assert!(stack.can_undo() && stack.can_redo());
+
+        // Pushing discards the changes that could be redone.
+        stack.push(3);
+        assert!(!stack.can_redo());
+        assert_eq!(stack.redo(), None);
+        assert_eq!(stack.undo(), Some(&3));
+        assert_eq!(stack.undo(), Some(&1));
+    }
+
+    #[test]
+    fn history_is_bounded_by_depth() {
+        let mut stack = UndoStack::<u32>::new(3);
+        for change in 1..=5 {
+            stack.push(change);
+        }
+        assert_eq!(stack.undo(), Some(&5));
+        assert_eq!(stack.undo(), Some(&4));
+        assert_eq!(stack.undo(), Some(&3));
+        assert_eq!(stack.undo(), None);
+
+        // A depth of 0 keeps a single change.
+        let mut stack = UndoStack::<u32>::new(0);
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(stack.undo(), Some(&2));
+        assert!(!stack.can_undo());
+    }
+
+    #[test]
+    fn saved_marker_follows_position() {
+        let mut stack = UndoStack::<u32>::new(10);
+        assert!(!stack.is_unsaved());
+        stack.push(1);
+        assert!(stack.is_unsaved());
+        stack.undo();
+        assert!(!stack.is_unsaved());
+        stack.redo();
+        stack.push(2);
+        stack.mark_saved();
+        assert!(!stack.is_unsaved());
+        stack.undo();
+        assert!(stack.is_unsaved());
+        stack.redo();
+        assert!(!stack.is_unsaved());
+
+        // The saved position is no longer reachable once its changes are discarded.
+        stack.undo();
+        stack.push(3);
+        assert!(stack.is_unsaved());
+        stack.undo();
+        stack.redo();
+        assert!(stack.is_unsaved());
+    }
+
+    #[test]
+    fn saved_marker_discarded_with_oldest_change() {
+        let mut stack = UndoStack::<u32>::new(2);
+        stack.push(1);
+        stack.mark_saved();
+        stack.push(2);
+        stack.undo();
+        assert!(!stack.is_unsaved());
+
+        // The saved position shifts with the discarded oldest change.
+        stack.redo();
+        stack.push(3);
+        stack.undo();
+        stack.undo();
+        assert!(!stack.is_unsaved());
+
+        // Discarding the change of the saved position makes it unreachable.
+        stack.redo();
+        stack.redo();
+        stack.push(4);
+        stack.undo();
+        stack.undo();
+        assert!(stack.is_unsaved());
+        assert!(!stack.can_undo());
+    }
+}
@@ -4,12 +4,106 @@
 //! Add new window directory to the list.
 
 pub mod about;
+pub mod command_palette;
 pub mod confirm_exit;
 pub mod fatal_error;
 pub mod information;
+pub mod log_viewer;
 pub mod main_common; // For common localisation.
 pub mod default;
 pub mod main;
 pub mod preferences;
+pub mod recoverable_error;
 pub mod unsaved_data;
 
+use crate::{
+    application::{ApplicationError, StringGroup},
+    core::{error::CoreError, localisation::Localisation, traits::AnyLocalisedTrait},
+};
+
+/// The registry of the `Strings` constructors of the string groups, for when the group is only
+/// known at runtime, such as for the `--check-localisation` command line option.
+pub fn try_strings(
+    string_group: &StringGroup,
+    localisation: &Localisation,
+) -> Result<Box<dyn AnyLocalisedTrait>, CoreError> {
+    Ok(match string_group {
+        // Core windows
+        StringGroup::ConfirmExit => Box::new(confirm_exit::Strings::try_new(localisation)?),
+        StringGroup::FatalError => {
+            // Never fails, as it falls back to English. Any error will do for the check.
+            let error = ApplicationError::Core(CoreError::ApplicationPath);
+            Box::new(fatal_error::Strings::new(localisation, error))
+        }
+        StringGroup::RecoverableError => {
+            Box::new(recoverable_error::Strings::try_new(localisation)?)
+        }
+        StringGroup::Information => Box::new(information::Strings::try_new(localisation)?),
+        StringGroup::CommandPalette => {
+            Box::new(command_palette::Strings::try_new(localisation)?)
+        }
+        StringGroup::Preferences => Box::new(preferences::Strings::try_new(localisation)?),
+        StringGroup::About => Box::new(about::Strings::try_new(localisation)?),
+        StringGroup::UnsavedData => Box::new(unsaved_data::Strings::try_new(localisation)?),
+        StringGroup::LogViewer => Box::new(log_viewer::Strings::try_new(localisation)?),
+
+        // Main windows
+        StringGroup::MainCommon => Box::new(main_common::Strings::try_new(localisation)?),
+        StringGroup::Default => Box::new(default::Strings::try_new(localisation)?),
+        StringGroup::Main => Box::new(main::Strings::try_new(localisation)?),
+
+        // Application windows
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::localisation;
+
+    /// The position of the string group within `StringGroup::all()`. The match is exhaustive, thus
+    /// a new variant fails to compile until it is added here, to `all()` and to `try_strings()`.
+    fn position(string_group: &StringGroup) -> usize {
+        match string_group {
+            // Core windows
+            StringGroup::ConfirmExit => 0,
+            StringGroup::FatalError => 1,
+            StringGroup::RecoverableError => 2,
+            StringGroup::Information => 3,
+            StringGroup::Preferences => 4,
+            StringGroup::About => 5,
+            StringGroup::UnsavedData => 6,
+            StringGroup::LogViewer => 7,
+            StringGroup::CommandPalette => 8,
+
+            // Main windows
+            StringGroup::MainCommon => 9,
+            StringGroup::Default => 10,
+            StringGroup::Main => 11,
+
+            // Application windows
+        }
+    }
+
+    const STRING_GROUPS: usize = 12;
+
+    #[test]
+    fn all_string_groups_have_strings() {
+        let all = StringGroup::all();
+        assert_eq!(all.len(), STRING_GROUPS);
+        for (index, string_group) in all.iter().enumerate() {
+            assert_eq!(position(string_group), index, "{}", string_group.as_str());
+        }
+        for language in ["en-ZA", "it"] {
+            let localisation = localisation(language);
+            for string_group in all {
+                assert!(
+                    try_strings(string_group, &localisation).is_ok(),
+                    "{} of {}",
+                    string_group.as_str(),
+                    language
+                );
+            }
+        }
+    }
+}
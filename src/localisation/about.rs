@@ -27,6 +27,8 @@ pub enum Index {
     Contributors,
     Ok,
     Localisation,
+    Dependencies,
+    CheckForUpdates,
 }
 
 #[derive(Debug)]
@@ -54,15 +56,20 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating About UI localisation.");
-
-            let (language_tag, strings) = localise(localisation)?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating About UI localisation.");
+
+        let (language_tag, strings) = localise(localisation)?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -112,8 +119,21 @@ fn localise(
         );
         localisation.format_with_defaults("application", "add_colon_format", &values)?
     }.0;
+    let dependencies = {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        let localised = localisation.literal_with_defaults("word", "dependencies_ip")?;
+        values.insert(
+            "phrase".to_string(),
+            PlaceholderValue::Localised(localised.0, localised.1),
+        );
+        localisation.format_with_defaults("application", "add_colon_format", &values)?
+    }.0;
+    let check_for_updates = localisation
+        .literal_with_defaults("application", "check_for_updates")?.0;
     Ok((
         language_tag,
-        vec![title, contributors, ok, localisation_contributors],
+        vec![
+            title, contributors, ok, localisation_contributors, dependencies, check_for_updates,
+        ],
     ))
 }
@@ -27,6 +27,8 @@ pub enum Index {
     ConfirmExit,
     Exit,
     Cancel,
+    SaveAndExit,
+    ExitWithoutSaving,
 }
 
 #[derive(Debug)]
@@ -54,15 +56,20 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Confirm exit UI localisation.");
-
-            let (language_tag, strings) = localise(localisation)?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Confirm exit UI localisation.");
+
+        let (language_tag, strings) = localise(localisation)?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -133,5 +140,12 @@ fn localise(
     }.0;
     let cancel = localisation
         .literal_with_defaults("word", "cancel_i")?.0;
-    Ok((language_tag, vec![title, confirm_exit, exit, cancel]))
+    let save_and_exit = localisation
+        .literal_with_defaults("application", "save_and_exit")?.0;
+    let exit_without_saving = localisation
+        .literal_with_defaults("application", "exit_without_saving")?.0;
+    Ok((
+        language_tag,
+        vec![title, confirm_exit, exit, cancel, save_and_exit, exit_without_saving],
+    ))
 }
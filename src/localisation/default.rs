@@ -29,6 +29,7 @@ use std::sync::Arc as RefCount;
 pub enum Index {
     Title,
     Exit,
+    Welcome,
 }
 
 #[derive(Debug)]
@@ -56,15 +57,20 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Main UI localisation.");
-
-            let (language_tag, strings) = localise(localisation)?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Main UI localisation.");
+
+        let (language_tag, strings) = localise(localisation)?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -112,8 +118,57 @@ fn localise(
         localisation.literal_with_defaults("word", "exit_i")?
     }.0;
 
+    let welcome = {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert(
+            "application".to_string(),
+            PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
+        );
+        localisation.format_with_defaults("application", "welcome", &values)?
+    }.0;
+
     Ok((
         language_tag,
-        vec![title, exit],
+        vec![title, exit, welcome],
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::testing::localisation, localisation::main_common};
+
+    #[test]
+    fn welcome_resolves_in_all_languages() {
+        let mut localisation = localisation("en-ZA");
+        let mut strings = Strings::try_new(&localisation).unwrap();
+        let languages = localisation
+            .available_languages()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(!languages.is_empty());
+        for language in languages {
+            localisation.change_default_language(RefCount::clone(&language)).unwrap();
+
+            // The cached group is re-localised in place, as done for the reusable window.
+            strings.try_update(&localisation).unwrap();
+            assert_eq!(strings.language_tag(), &language);
+            assert!(!strings.title().is_empty(), "{}", language.as_str());
+            assert!(!strings.string(Index::Exit as usize).is_empty(), "{}", language.as_str());
+            let welcome = strings.string(Index::Welcome as usize);
+            assert!(welcome.contains(APPLICATION_NAME_SHORT), "{}: {}", language.as_str(), welcome);
+
+            // The labels of the quick action buttons.
+            let common = main_common::Strings::try_new(&localisation).unwrap();
+            for index in [
+                main_common::Index::New,
+                main_common::Index::Open,
+                main_common::Index::Preferences,
+                main_common::Index::About,
+            ] {
+                assert!(!common.string(index as usize).is_empty(), "{}", language.as_str());
+            }
+        }
+    }
+}
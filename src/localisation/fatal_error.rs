@@ -25,6 +25,7 @@ pub enum Index {
     Title,
     UncaughtError,
     Exit,
+    CopyDetails,
 }
 
 #[derive(Debug)]
@@ -134,5 +135,9 @@ fn localise(
             Err(_) => RefCount::new("Exit".to_string()),
         }
     };
-    (language_tag, vec![title, uncaught_error, exit])
+    let copy_details = match localisation.literal_with_defaults("application", "copy_details") {
+        Ok(value) => value.0,
+        Err(_) => RefCount::new("Copy details".to_string()),
+    };
+    (language_tag, vec![title, uncaught_error, exit, copy_details])
 }
@@ -52,15 +52,20 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Information UI localisation.");
-
-            let (language_tag, strings) = localise(localisation)?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Information UI localisation.");
+
+        let (language_tag, strings) = localise(localisation)?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
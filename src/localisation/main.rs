@@ -25,6 +25,9 @@ pub enum Index {
     Save,
     Close,
     CloseAll,
+    SaveAs,
+    Undo,
+    Redo,
 }
 
 #[derive(Debug)]
@@ -54,17 +57,22 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Main UI localisation.");
-
-            let (language_tag, strings) = localise(
-                localisation,
-            )?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Main UI localisation.");
+
+        let (language_tag, strings) = localise(
+            localisation,
+        )?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -91,8 +99,16 @@ fn localise(
         .literal_with_defaults("word", "close_i")?.0;
     let close_all = localisation
         .literal_with_defaults("application", "close_all")?.0;
+    let save_as = localisation
+        .literal_with_defaults("application", "save_as")?.0;
+
+    // Edit menu
+    let undo = localisation
+        .literal_with_defaults("word", "undo_i")?.0;
+    let redo = localisation
+        .literal_with_defaults("word", "redo_i")?.0;
     Ok((
         language_tag,
-        vec![title, save, close, close_all],
+        vec![title, save, close, close_all, save_as, undo, redo],
     ))
 }
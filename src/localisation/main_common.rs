@@ -31,6 +31,11 @@ pub enum Index {
     Preferences,
     Help,
     About,
+    LogViewer,
+    OpenRecent,
+    NoRecent,
+    Window,
+    OpenReadOnly,
 }
 
 #[derive(Debug)]
@@ -58,15 +63,20 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Main UI localisation.");
-
-            let (language_tag, strings) = localise(localisation)?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Main UI localisation.");
+
+        let (language_tag, strings) = localise(localisation)?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -94,6 +104,12 @@ fn localise(
         .literal_with_defaults("word", "new_i")?.0;
     let open = localisation
         .literal_with_defaults("word", "open_i")?.0;
+    let open_recent = localisation
+        .literal_with_defaults("application", "open_recent")?.0;
+    let no_recent = localisation
+        .literal_with_defaults("application", "no_recent")?.0;
+    let open_read_only = localisation
+        .literal_with_defaults("application", "open_read_only")?.0;
 
     // Edit menu
     let edit = localisation
@@ -108,14 +124,23 @@ fn localise(
         localisation.format_with_defaults("application", "add_elipsis_format", &values)?
     }.0;
 
+    // Window menu
+    let window = localisation
+        .literal_with_defaults("word", "window_i")?.0;
+
     // Help menu
     let help = localisation
         .literal_with_defaults("word", "help_i")?.0;
     let about = localisation
         .literal_with_defaults("word", "about_i")?.0;
+    let log_viewer = localisation
+        .literal_with_defaults("application", "log_viewer")?.0;
 
     Ok((
         language_tag,
-        vec![title, file_, new, open, edit, preferences, help, about],
+        vec![
+            title, file_, new, open, edit, preferences, help, about, log_viewer, open_recent,
+            no_recent, window, open_read_only,
+        ],
     ))
 }
@@ -5,6 +5,7 @@ use crate::{
     application::{
         constants::APPLICATION_NAME_SHORT,
         log::LogLevel,
+        theme::ThemePreference,
     },
     core::{
         error::CoreError,
@@ -40,6 +41,22 @@ pub enum Index {
     LogLevelIced,
     LogLevelI18n,
     LogPlaceholder,
+    LogToFile,
+    Theme,
+    ThemeUi,
+    ThemePlaceholder,
+    AccentUi,
+    Backup,
+    BackupUi,
+    ExportSettings,
+    ImportSettings,
+    RestoreDefaults,
+    IncludeWindows,
+    RestoreDefaultsTitle,
+    RestoreDefaultsStatement,
+    Restore,
+    Search,
+    NoResults,
 }
 
 #[derive(Debug)]
@@ -52,6 +69,9 @@ pub struct Strings {
     log_list: Vec<String>,
     log_map_to_level: HashMap<String, LogLevel>,
     log_map_to_string: HashMap<LogLevel, String>,
+    theme_list: Vec<String>,
+    theme_map_to_preference: HashMap<String, ThemePreference>,
+    theme_map_to_string: HashMap<ThemePreference, String>,
 }
 
 impl Strings {
@@ -61,6 +81,10 @@ impl Strings {
             localise_i18n(localisation, &mut strings)?;
         let (log_list, log_map_to_level, log_map_to_string) =
             localise_log(localisation, &mut strings)?;
+        let (theme_list, theme_map_to_preference, theme_map_to_string) =
+            localise_theme(localisation, &mut strings)?;
+        localise_backup(localisation, &mut strings)?;
+        localise_search(localisation, &mut strings)?;
         Ok(Strings {
             language_tag,
             strings,
@@ -70,6 +94,9 @@ impl Strings {
             log_list,
             log_map_to_level,
             log_map_to_string,
+            theme_list,
+            theme_map_to_preference,
+            theme_map_to_string,
         })
     }
 
@@ -96,6 +123,18 @@ impl Strings {
     pub fn log_map_to_string(&self, level: &LogLevel) -> Option<&String> {
         self.log_map_to_string.get(level)
     }
+
+    pub fn theme_list(&self) -> &Vec<String> {
+        &self.theme_list
+    }
+
+    pub fn theme_map_to_preference(&self, string: &String) -> Option<&ThemePreference> {
+        self.theme_map_to_preference.get(string)
+    }
+
+    pub fn theme_map_to_string(&self, preference: &ThemePreference) -> Option<&String> {
+        self.theme_map_to_string.get(preference)
+    }
 }
 
 impl AnyLocalisedTrait for Strings {
@@ -107,29 +146,41 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating Preference UI localisation.");
-
-            // Obtain localised strings
-            let (language_tag, mut strings) =
-                localise(localisation)?;
-            let (language_list, language_map_to_tag, language_map_to_string) =
-                localise_i18n(localisation, &mut strings)?;
-            let (log_list, log_map_to_level, log_map_to_string) =
-                localise_log(localisation, &mut strings)?;
-
-            // Store localised strings
-            self.language_tag = language_tag;
-            self.strings = strings;
-            self.language_list = language_list;
-            self.language_map_to_tag = language_map_to_tag;
-            self.language_map_to_string = language_map_to_string;
-            self.log_list = log_list;
-            self.log_map_to_level = log_map_to_level;
-            self.log_map_to_string = log_map_to_string;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating Preference UI localisation.");
+
+        // Obtain localised strings
+        let (language_tag, mut strings) =
+            localise(localisation)?;
+        let (language_list, language_map_to_tag, language_map_to_string) =
+            localise_i18n(localisation, &mut strings)?;
+        let (log_list, log_map_to_level, log_map_to_string) =
+            localise_log(localisation, &mut strings)?;
+        let (theme_list, theme_map_to_preference, theme_map_to_string) =
+            localise_theme(localisation, &mut strings)?;
+        localise_backup(localisation, &mut strings)?;
+        localise_search(localisation, &mut strings)?;
+
+        // Store localised strings
+        self.language_tag = language_tag;
+        self.strings = strings;
+        self.language_list = language_list;
+        self.language_map_to_tag = language_map_to_tag;
+        self.language_map_to_string = language_map_to_string;
+        self.log_list = log_list;
+        self.log_map_to_level = log_map_to_level;
+        self.log_map_to_string = log_map_to_string;
+        self.theme_list = theme_list;
+        self.theme_map_to_preference = theme_map_to_preference;
+        self.theme_map_to_string = theme_map_to_string;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -181,7 +232,7 @@ fn localise_i18n(
     let mut map_to_tag = HashMap::<String, RefCount<LanguageTag>>::new();
     let mut map_to_string = HashMap::<RefCount<LanguageTag>, String>::new();
     let mut list = Vec::<String>::new();
-    for (tag, (_layout, ratio)) in localisation.available_languages().iter() {
+    for (tag, ratio) in localisation.language_completion() {
         let mut values = HashMap::<String, PlaceholderValue>::new();
         let language_string = tag.as_str().to_string();
         values.insert(
@@ -202,8 +253,8 @@ fn localise_i18n(
             .as_str()
             .to_string();
         list.push(text.clone());
-        map_to_tag.insert(text.clone(), RefCount::clone(tag));
-        map_to_string.insert(RefCount::clone(tag), text);
+        map_to_tag.insert(text.clone(), RefCount::clone(&tag));
+        map_to_string.insert(tag, text);
     }
     strings.push(
         localisation
@@ -274,40 +325,180 @@ fn localise_log(
         localisation
             .literal_with_defaults("application", "placeholder_log_level")?.0
     );
-    let default = localisation
-        .literal_with_defaults("word", "default_i")?.0.as_str().to_string();
-    list.push(default.clone());
-    map_to_level.insert(default.clone(), LogLevel::Default);
-    map_to_string.insert(LogLevel::Default, default);
-    let off = localisation
-        .literal_with_defaults("word", "off_i")?.0.as_str().to_string();
-    list.push(off.clone());
-    map_to_level.insert(off.clone(), LogLevel::Off);
-    map_to_string.insert(LogLevel::Off, off);
-    let error = localisation
-        .literal_with_defaults("word", "error_i")?.0.as_str().to_string();
-    list.push(error.clone());
-    map_to_level.insert(error.clone(), LogLevel::Error);
-    map_to_string.insert(LogLevel::Error, error);
-    let warning = localisation
-        .literal_with_defaults("word", "warning_i")?.0.as_str().to_string();
-    list.push(warning.clone());
-    map_to_level.insert(warning.clone(), LogLevel::Warn);
-    map_to_string.insert(LogLevel::Warn, warning);
-    let information = localisation
-        .literal_with_defaults("word", "information_i")?.0.as_str().to_string();
-    list.push(information.clone());
-    map_to_level.insert(information.clone(), LogLevel::Info);
-    map_to_string.insert(LogLevel::Info, information);
-    let debug = localisation
-        .literal_with_defaults("word", "debug_i")?.0.as_str().to_string();
-    list.push(debug.clone());
-    map_to_level.insert(debug.clone(), LogLevel::Debug);
-    map_to_string.insert(LogLevel::Debug, debug);
-    let trace = localisation
-        .literal_with_defaults("word", "trace_i")?.0.as_str().to_string();
-    list.push(trace.clone());
-    map_to_level.insert(trace.clone(), LogLevel::Trace);
-    map_to_string.insert(LogLevel::Trace, trace);
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "log_to_file")?.0
+    );
+    // Each log level is mapped once, in the order of the list.
+    let levels = [
+        ("default_i", LogLevel::Default),
+        ("off_i", LogLevel::Off),
+        ("error_i", LogLevel::Error),
+        ("warning_i", LogLevel::Warn),
+        ("information_i", LogLevel::Info),
+        ("debug_i", LogLevel::Debug),
+        ("trace_i", LogLevel::Trace),
+    ];
+    for (identifier, level) in levels {
+        let string = localisation
+            .literal_with_defaults("word", identifier)?.0.as_str().to_string();
+        list.push(string.clone());
+        map_to_level.insert(string.clone(), level);
+        map_to_string.insert(level, string);
+    }
     Ok((list, map_to_level, map_to_string))
 }
+
+#[allow(clippy::type_complexity)]
+fn localise_theme(
+    localisation: &Localisation,
+    strings: &mut Vec<RefCount<String>>,
+) -> Result<
+    (
+        Vec<String>,
+        HashMap<String, ThemePreference>,
+        HashMap<ThemePreference, String>,
+    ),
+    CoreError,
+> {
+    let mut map_to_preference = HashMap::<String, ThemePreference>::new();
+    let mut map_to_string = HashMap::<ThemePreference, String>::new();
+    let mut list = Vec::<String>::new();
+    strings.push(
+        localisation
+            .literal_with_defaults("word", "theme_i")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "ui_theme")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "placeholder_theme")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "ui_accent")?.0
+    );
+    let light = localisation
+        .literal_with_defaults("word", "light_i")?.0.as_str().to_string();
+    list.push(light.clone());
+    map_to_preference.insert(light.clone(), ThemePreference::Light);
+    map_to_string.insert(ThemePreference::Light, light);
+    let dark = localisation
+        .literal_with_defaults("word", "dark_i")?.0.as_str().to_string();
+    list.push(dark.clone());
+    map_to_preference.insert(dark.clone(), ThemePreference::Dark);
+    map_to_string.insert(ThemePreference::Dark, dark);
+    let system = localisation
+        .literal_with_defaults("word", "system_i")?.0.as_str().to_string();
+    list.push(system.clone());
+    map_to_preference.insert(system.clone(), ThemePreference::System);
+    map_to_string.insert(ThemePreference::System, system);
+    let high_contrast = localisation
+        .literal_with_defaults("word", "high_contrast_i")?.0.as_str().to_string();
+    list.push(high_contrast.clone());
+    map_to_preference.insert(high_contrast.clone(), ThemePreference::HighContrast);
+    map_to_string.insert(ThemePreference::HighContrast, high_contrast);
+    Ok((list, map_to_preference, map_to_string))
+}
+
+fn localise_backup(
+    localisation: &Localisation,
+    strings: &mut Vec<RefCount<String>>,
+) -> Result<(), CoreError> {
+    strings.push(
+        localisation
+            .literal_with_defaults("word", "backup_i")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "ui_backup")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "export_settings")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "import_settings")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "restore_defaults")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "include_windows")?.0
+    );
+
+    // Restore defaults dialogue
+    let title = {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert(
+            "application".to_string(),
+            PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
+        );
+        let localised = localisation.literal_with_defaults("application", "restore_defaults")?;
+        values.insert(
+            "window".to_string(),
+            PlaceholderValue::Localised(localised.0, localised.1),
+        );
+        localisation.format_with_defaults("application", "window_title_format", &values)?
+    }.0;
+    strings.push(title);
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "restore_defaults_statement")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("word", "restore_i")?.0
+    );
+    Ok(())
+}
+
+fn localise_search(
+    localisation: &Localisation,
+    strings: &mut Vec<RefCount<String>>,
+) -> Result<(), CoreError> {
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "preferences_search")?.0
+    );
+    strings.push(
+        localisation
+            .literal_with_defaults("application", "no_results")?.0
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::localisation;
+
+    #[test]
+    fn log_levels_round_trip() {
+        let levels = [
+            LogLevel::Default,
+            LogLevel::Off,
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ];
+        for language in ["en-ZA", "it"] {
+            let localisation = localisation(language);
+            let strings = Strings::try_new(&localisation).unwrap();
+            assert_eq!(strings.log_list().len(), levels.len());
+            for (index, level) in levels.iter().enumerate() {
+                let string = strings.log_map_to_string(level).unwrap();
+                assert!(!string.is_empty());
+                assert_eq!(&strings.log_list()[index], string);
+                assert_eq!(strings.log_map_to_level(string), Some(level), "{}", string);
+            }
+        }
+    }
+}
@@ -25,6 +25,9 @@ pub enum Index {
     Save,
     Discard,
     Cancel,
+    //BatchStatement,
+    SaveAll,
+    DiscardAll,
 }
 
 #[derive(Debug)]
@@ -54,17 +57,22 @@ impl AnyLocalisedTrait for Strings {
 impl LocalisedTrait for Strings {
     fn try_update(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
         if self.language_tag != localisation.default_language().into() {
-            debug!("Updating UnsavedData UI localisation.");
-
-            let (language_tag, strings) = localise(
-                localisation,
-            )?;
-            self.language_tag = language_tag;
-            self.strings = strings;
+            self.try_reload(localisation)?;
         }
         Ok(())
     }
 
+    fn try_reload(&mut self, localisation: &Localisation) -> Result<(), CoreError> {
+        debug!("Updating UnsavedData UI localisation.");
+
+        let (language_tag, strings) = localise(
+            localisation,
+        )?;
+        self.language_tag = language_tag;
+        self.strings = strings;
+        Ok(())
+    }
+
     fn title(&self) -> &String {
         &self.strings[0]
     }
@@ -93,8 +101,21 @@ fn localise(
         .literal_with_defaults("word", "discard_i")?.0;//"application", "discard_and_close"
     let cancel = localisation
         .literal_with_defaults("word", "cancel_i")?.0;
+
+    // Multiple windows having unsaved data
+    /*
+    let batch_statement = localisation
+        .literal_with_defaults("application", "unsaved_data_batch_statement")?.0;
+    */
+    let save_all = localisation
+        .literal_with_defaults("application", "save_all")?.0;
+    let discard_all = localisation
+        .literal_with_defaults("application", "discard_all")?.0;
     Ok((
         language_tag,
-        vec![title, /*unsaved_data, */save, discard, cancel],
+        vec![
+            title, /*unsaved_data, */save, discard, cancel, /*batch_statement, */save_all,
+            discard_all,
+        ],
     ))
 }
@@ -8,13 +8,36 @@ use iced::{
     settings::Settings,
     Pixels,
 };
-use iced_af::application::State;
+use iced_af::application::{
+    clap::{check_localisation, print_version, Clap},
+    font::startup_font,
+    State,
+};
+use clap::Parser;
 
 fn main() -> iced::Result {
+    let clap = Clap::parse();
+    if clap.version {
+        print_version();
+        return Ok(());
+    }
+    if clap.check_localisation {
+        std::process::exit(if check_localisation() { 0 } else { 1 });
+    }
+    #[cfg(feature = "single_instance")]
+    match iced_af::application::single_instance::try_acquire() {
+        Ok(true) => {}
+        Ok(false) => return Ok(()), // Arguments were forwarded to the running instance.
+        Err(error) => eprintln!("Single instance check failed: {}", error),
+    }
+    let default_font = startup_font(&clap);
     daemon(State::title, State::update, State::view)
     .subscription(State::subscription)
+    .theme(State::theme)
+    .scale_factor(State::scale_factor)
     .settings(Settings {
         default_text_size: Pixels(12.0),
+        default_font,
         ..Default::default()
     })
     .run_with(State::new)
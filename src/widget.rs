@@ -5,5 +5,7 @@
 
 //pub mod column; // A column that ensures column's children have the same width.
 pub mod event_control; // Allows for the disabling of windows from event queue.
+pub mod spinner; // An indeterminate or determinate progress spinner.
+pub mod toast; // A stack of dismissible toasts on top of the content.
 //pub mod row;
 //pub mod sidebar; // A sidebar of tabs on one side of the content pane. // A row that ensures row's children have the same height.
@@ -43,6 +43,10 @@ pub struct Container<
     content: Element<'a, Message, Theme, Renderer>,
     class: Theme::Class<'a>,
     events_enabled: bool,
+    dim_when_disabled: bool,
+    dim_color: Color,
+    disabled_cursor: Option<mouse::Interaction>,
+    border_scale: f32,
 }
 
 impl<'a, Message, Theme, Renderer> Container<'a, Message, Theme, Renderer>
@@ -71,9 +75,40 @@ where
             class: Theme::default(),
             content,
             events_enabled,
+            dim_when_disabled: true,
+            dim_color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+            disabled_cursor: None,
+            border_scale: 1.0,
         }
     }
 
+    /// Sets the mouse cursor displayed over the [`Container`] when events are disabled, such as
+    /// [`mouse::Interaction::Wait`]. By default the content's cursor is displayed.
+    pub fn disabled_cursor(mut self, interaction: mouse::Interaction) -> Self {
+        self.disabled_cursor = Some(interaction);
+        self
+    }
+
+    /// Sets whether a scrim is drawn over the content of the [`Container`], when events are
+    /// disabled. Defaults to `true`.
+    pub fn dim_when_disabled(mut self, dim_when_disabled: bool) -> Self {
+        self.dim_when_disabled = dim_when_disabled;
+        self
+    }
+
+    /// Sets the [`Color`] of the scrim, including its alpha. Defaults to a subtle black.
+    pub fn dim_color(mut self, dim_color: impl Into<Color>) -> Self {
+        self.dim_color = dim_color.into();
+        self
+    }
+
+    /// Sets the multiplier of the border width of the [`Container`]'s style, such as for thicker
+    /// borders of a high contrast theme. Defaults to `1.0`.
+    pub fn border_scale(mut self, border_scale: f32) -> Self {
+        self.border_scale = border_scale;
+        self
+    }
+
     /// Sets the [`Id`] of the [`Container`].
     pub fn id(mut self, id: Id) -> Self {
         self.id = Some(id);
@@ -255,6 +290,10 @@ where
             self.id.as_ref().map(|id| &id.0),
             layout.bounds(),
             &mut |operation| {
+                // The content is skipped while disabled, so focus can't land on the content.
+                if !self.events_enabled {
+                    return;
+                }
                 self.content.as_widget().operate(
                     tree,
                     layout.children().next().unwrap(),
@@ -299,6 +338,13 @@ where
         viewport: &Rectangle,
         renderer: &Renderer,
     ) -> mouse::Interaction {
+        if !self.events_enabled {
+            if let Some(interaction) = self.disabled_cursor {
+                if cursor.is_over(layout.bounds()) {
+                    return interaction;
+                }
+            }
+        }
         self.content.as_widget().mouse_interaction(
             tree,
             layout.children().next().unwrap(),
@@ -319,7 +365,8 @@ where
         viewport: &Rectangle,
     ) {
         let bounds = layout.bounds();
-        let style = theme.style(&self.class);
+        let mut style = theme.style(&self.class);
+        style.border.width *= self.border_scale;
 
         if let Some(clipped_viewport) = bounds.intersection(viewport) {
             draw_background(renderer, &style, bounds);
@@ -341,6 +388,19 @@ where
                     viewport
                 },
             );
+
+            // The scrim is only drawn, thus events are handled as before.
+            if !self.events_enabled && self.dim_when_disabled {
+                renderer.with_layer(clipped_viewport, |renderer| {
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds,
+                            ..renderer::Quad::default()
+                        },
+                        self.dim_color,
+                    );
+                });
+            }
         }
     }
 
@@ -655,3 +715,203 @@ pub fn bordered_box(theme: &Theme) -> Style {
         ..Style::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::{advanced::widget::operation::Focusable, Transformation};
+
+    /// A renderer recording the backgrounds of the filled quads.
+    #[derive(Default)]
+    struct Recorder {
+        backgrounds: Vec<Background>,
+    }
+
+    impl iced::advanced::Renderer for Recorder {
+        fn start_layer(&mut self, _bounds: Rectangle) {}
+
+        fn end_layer(&mut self) {}
+
+        fn start_transformation(&mut self, _transformation: Transformation) {}
+
+        fn end_transformation(&mut self) {}
+
+        fn fill_quad(&mut self, _quad: renderer::Quad, background: impl Into<Background>) {
+            self.backgrounds.push(background.into());
+        }
+
+        fn clear(&mut self) {
+            self.backgrounds.clear();
+        }
+    }
+
+    /// A widget reporting itself as focusable to the operations.
+    struct Focus {
+        focused: bool,
+    }
+
+    impl Focusable for Focus {
+        fn is_focused(&self) -> bool {
+            self.focused
+        }
+
+        fn focus(&mut self) {
+            self.focused = true;
+        }
+
+        fn unfocus(&mut self) {
+            self.focused = false;
+        }
+    }
+
+    impl Widget<(), Theme, Recorder> for Focus {
+        fn size(&self) -> Size<Length> {
+            Size {
+                width: Length::Fill,
+                height: Length::Fill,
+            }
+        }
+
+        fn layout(
+            &self,
+            _tree: &mut Tree,
+            _renderer: &Recorder,
+            limits: &layout::Limits,
+        ) -> layout::Node {
+            layout::Node::new(limits.max())
+        }
+
+        fn operate(
+            &self,
+            _tree: &mut Tree,
+            _layout: Layout<'_>,
+            _renderer: &Recorder,
+            operation: &mut dyn Operation<()>,
+        ) {
+            operation.focusable(&mut Focus { focused: false }, None);
+        }
+
+        fn mouse_interaction(
+            &self,
+            _tree: &Tree,
+            _layout: Layout<'_>,
+            _cursor: mouse::Cursor,
+            _viewport: &Rectangle,
+            _renderer: &Recorder,
+        ) -> mouse::Interaction {
+            mouse::Interaction::Pointer
+        }
+
+        fn draw(
+            &self,
+            _tree: &Tree,
+            _renderer: &mut Recorder,
+            _theme: &Theme,
+            _style: &renderer::Style,
+            _layout: Layout<'_>,
+            _cursor: mouse::Cursor,
+            _viewport: &Rectangle,
+        ) {
+        }
+    }
+
+    /// Counts the focusable widgets.
+    struct Count(usize);
+
+    impl Operation<()> for Count {
+        fn container(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<()>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn focusable(&mut self, _state: &mut dyn Focusable, _id: Option<&widget::Id>) {
+            self.0 += 1;
+        }
+    }
+
+    const SIZE: Size = Size::new(100f32, 100f32);
+
+    /// The container of the focusable widget, with its tree and layout.
+    fn container(
+        events_enabled: bool,
+    ) -> (Container<'static, (), Theme, Recorder>, Tree, layout::Node) {
+        let container = Container::new(Element::new(Focus { focused: false }), events_enabled);
+        let mut tree = Tree::new(&container as &dyn Widget<(), Theme, Recorder>);
+        let node = container.layout(
+            &mut tree,
+            &Recorder::default(),
+            &layout::Limits::new(Size::ZERO, SIZE),
+        );
+        (container, tree, node)
+    }
+
+    fn focusables(events_enabled: bool) -> usize {
+        let (container, mut tree, node) = container(events_enabled);
+        let mut count = Count(0);
+        container.operate(&mut tree, Layout::new(&node), &Recorder::default(), &mut count);
+        count.0
+    }
+
+    #[test]
+    fn no_focusable_while_disabled() {
+        assert_eq!(focusables(true), 1);
+        assert_eq!(focusables(false), 0);
+    }
+
+    /// The backgrounds of the quads drawn by the container.
+    fn drawn(events_enabled: bool, dim_when_disabled: bool) -> (Vec<Background>, Color) {
+        let (container, tree, node) = container(events_enabled);
+        let container = container.dim_when_disabled(dim_when_disabled);
+        let mut renderer = Recorder::default();
+        container.draw(
+            &tree,
+            &mut renderer,
+            &Theme::Light,
+            &renderer::Style {
+                text_color: Color::BLACK,
+            },
+            Layout::new(&node),
+            mouse::Cursor::Unavailable,
+            &Rectangle::with_size(SIZE),
+        );
+        (renderer.backgrounds, container.dim_color)
+    }
+
+    #[test]
+    fn scrim_drawn_only_while_disabled() {
+        let (backgrounds, dim_color) = drawn(false, true);
+        assert_eq!(backgrounds, vec![Background::Color(dim_color)]);
+        let (backgrounds, _) = drawn(true, true);
+        assert!(backgrounds.is_empty());
+        let (backgrounds, _) = drawn(false, false);
+        assert!(backgrounds.is_empty());
+    }
+
+    /// The mouse interaction of the container having a disabled cursor, with the cursor at the
+    /// position.
+    fn interaction(events_enabled: bool, position: Point) -> mouse::Interaction {
+        let (container, tree, node) = container(events_enabled);
+        container.disabled_cursor(mouse::Interaction::NotAllowed).mouse_interaction(
+            &tree,
+            Layout::new(&node),
+            mouse::Cursor::Available(position),
+            &Rectangle::with_size(SIZE),
+            &Recorder::default(),
+        )
+    }
+
+    #[test]
+    fn enabled_delegates_mouse_interaction() {
+        let inside = Point::new(50f32, 50f32);
+        assert_eq!(interaction(true, inside), mouse::Interaction::Pointer);
+
+        // The disabled cursor only applies over the disabled container.
+        assert_eq!(interaction(false, inside), mouse::Interaction::NotAllowed);
+        let outside = Point::new(150f32, 50f32);
+        assert_eq!(interaction(false, outside), mouse::Interaction::Pointer);
+    }
+}
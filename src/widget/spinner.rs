@@ -0,0 +1,187 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! A spinner for long operations, being either indeterminate, or determinate when the progress is
+//! known.
+//!
+//! The indeterminate spinner is animated using the window's redraw requests, thus no subscription
+//! is required. The colour is the primary colour of the theme's palette.
+//!
+//! For example, in the `view()` of a future `Connecting` window state:
+//!
+//! ```ignore
+//! let spinner = match self.progress {
+//!     None => Spinner::new(),
+//!     Some(progress) => Spinner::new().progress(progress),
+//! };
+//! column![spinner, text(strings.string(Index::Connecting as usize))]
+//!     .align_x(Alignment::Center)
+//!     .into()
+//! ```
+
+use iced::{
+    advanced::{
+        layout, mouse, renderer,
+        widget::tree::{self, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    border::Border,
+    event::{self, Event},
+    time::Instant,
+    window, Color, Element, Length, Point, Rectangle, Size, Theme,
+};
+use std::f32::consts::PI;
+
+const DOTS: usize = 12; // The number of dots of the ring.
+const CYCLE: f32 = 1.0; // The seconds for a rotation of the indeterminate spinner.
+
+/// A ring of dots, either rotating when indeterminate, or filled to the progress.
+#[allow(missing_debug_implementations)]
+pub struct Spinner {
+    size: f32,
+    progress: Option<f32>,
+}
+
+impl Spinner {
+    /// Creates an indeterminate [`Spinner`].
+    pub fn new() -> Self {
+        Spinner {
+            size: 40.0,
+            progress: None,
+        }
+    }
+
+    /// Sets the diameter of the [`Spinner`].
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Makes the [`Spinner`] determinate, with the progress clamped to `0.0..=1.0`.
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.progress = Some(progress.clamp(0.0, 1.0));
+        self
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Spinner::new()
+    }
+}
+
+/// The animation state of the indeterminate spinner.
+struct State {
+    start: Instant,
+    now: Instant,
+}
+
+impl<Message, Renderer> Widget<Message, Theme, Renderer> for Spinner
+where
+    Renderer: iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        let now = Instant::now();
+        tree::State::new(State { start: now, now })
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(self.size), Length::Fixed(self.size))
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, self.size, self.size)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        if self.progress.is_none() {
+            if let Event::Window(window::Event::RedrawRequested(now)) = event {
+                tree.state.downcast_mut::<State>().now = now;
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+        }
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let color = theme.extended_palette().primary.base.color;
+        let diameter = bounds.width.min(bounds.height);
+        let dot = diameter * 0.15;
+        let radius = (diameter - dot) / 2.0;
+        let centre = bounds.center();
+
+        // The leading dot of the indeterminate spinner.
+        let state = tree.state.downcast_ref::<State>();
+        let elapsed = state.now.duration_since(state.start).as_secs_f32();
+        let head = ((elapsed % CYCLE) / CYCLE * DOTS as f32) as usize;
+
+        for index in 0..DOTS {
+            let alpha = match self.progress {
+                Some(progress) => {
+                    if (index as f32) < progress * DOTS as f32 {
+                        1.0
+                    } else {
+                        0.2
+                    }
+                }
+                None => 1.0 - ((head + DOTS - index) % DOTS) as f32 / DOTS as f32,
+            };
+
+            // Clockwise from the top.
+            let angle = index as f32 / DOTS as f32 * 2.0 * PI - PI / 2.0;
+            let position = Point::new(
+                centre.x + radius * angle.cos() - dot / 2.0,
+                centre.y + radius * angle.sin() - dot / 2.0,
+            );
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle::new(position, Size::new(dot, dot)),
+                    border: Border {
+                        radius: (dot / 2.0).into(),
+                        ..Border::default()
+                    },
+                    ..renderer::Quad::default()
+                },
+                Color { a: color.a * alpha, ..color },
+            );
+        }
+    }
+}
+
+impl<'a, Message, Renderer> From<Spinner> for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: iced::advanced::Renderer + 'a,
+{
+    fn from(spinner: Spinner) -> Self {
+        Element::new(spinner)
+    }
+}
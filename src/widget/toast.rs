@@ -0,0 +1,404 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! A stack of dismissible toasts displayed in a corner of the window, on top of the content.
+//!
+//! The toasts are displayed using an overlay, thus the layout of the content is not affected.
+use iced::{
+    advanced::{
+        layout::{self, Layout},
+        mouse, overlay, renderer,
+        widget::{tree::Tree, Operation},
+        Clipboard, Shell, Widget,
+    },
+    event::{self, Event},
+    widget::{button, container, row, text},
+    Border, Element, Length, Point, Rectangle, Renderer, Size, Theme, Vector,
+};
+
+const WIDTH: f32 = 300.0; // The width of a toast.
+const PADDING: f32 = 10.0; // The distance of the toasts from the window edges.
+const SPACING: f32 = 5.0; // The distance between the toasts.
+
+/// The severity of a toast, determining the icon and colour.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Severity {
+    Information,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn icon(&self) -> &'static str {
+        match self {
+            Severity::Information => "ℹ",
+            Severity::Warning => "⚠",
+            Severity::Error => "✖",
+        }
+    }
+}
+
+/// The corner of the window where the toasts are displayed.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Position {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// A toast to be displayed.
+#[derive(Clone, Debug)]
+pub struct Item {
+    pub id: usize, // Passed to the dismiss closure.
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// The content with the toasts displayed on top of it.
+#[allow(missing_debug_implementations)]
+pub struct Toasts<'a, Message> {
+    content: Element<'a, Message>,
+    items: Vec<Item>,
+    position: Position,
+    on_dismiss: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+}
+
+impl<'a, Message> Toasts<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    /// Creates [`Toasts`] displaying the items on top of the content.
+    pub fn new(content: impl Into<Element<'a, Message>>, items: Vec<Item>) -> Self {
+        Toasts {
+            content: content.into(),
+            items,
+            position: Position::default(),
+            on_dismiss: None,
+        }
+    }
+
+    /// Sets the corner of the window where the toasts are displayed.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets the message produced when the close button of a toast is pressed, receiving the
+    /// [`Item`]'s `id`. Without it the toasts can't be dismissed.
+    pub fn on_dismiss(mut self, on_dismiss: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_dismiss = Some(Box::new(on_dismiss));
+        self
+    }
+}
+
+impl<'a, Message> From<Toasts<'a, Message>> for Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    fn from(toasts: Toasts<'a, Message>) -> Self {
+        let cards = toasts
+            .items
+            .iter()
+            .map(|item| card(item, toasts.on_dismiss.as_deref()))
+            .collect();
+        Element::new(Manager {
+            content: toasts.content,
+            cards,
+            position: toasts.position,
+        })
+    }
+}
+
+/// A toast as a card.
+fn card<'a, Message>(
+    item: &Item,
+    on_dismiss: Option<&(dyn Fn(usize) -> Message + 'a)>,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let severity = item.severity;
+    container(
+        row![
+            text(severity.icon()),
+            text(item.message.clone()).width(Length::Fill),
+            button(text("✕"))
+                .padding([0, 5])
+                .style(button::text)
+                .on_press_maybe(on_dismiss.map(|on_dismiss| on_dismiss(item.id))),
+        ]
+        .spacing(5),
+    )
+    .width(WIDTH)
+    .padding(5)
+    .style(move |theme: &Theme| {
+        let palette = theme.extended_palette();
+        let pair = match severity {
+            Severity::Information => palette.primary.weak,
+            Severity::Warning => palette.secondary.base,
+            Severity::Error => palette.danger.weak,
+        };
+        container::Style {
+            background: Some(pair.color.into()),
+            text_color: Some(pair.text),
+            border: Border {
+                radius: 4.0.into(),
+                ..Border::default()
+            },
+            ..container::Style::default()
+        }
+    })
+    .into()
+}
+
+/// The widget holding the content and the cards, where the cards are provided as an overlay.
+struct Manager<'a, Message> {
+    content: Element<'a, Message>,
+    cards: Vec<Element<'a, Message>>,
+    position: Position,
+}
+
+impl<'a, Message> Widget<Message, Theme, Renderer> for Manager<'a, Message> {
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        std::iter::once(Tree::new(&self.content))
+            .chain(self.cards.iter().map(Tree::new))
+            .collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(
+            &std::iter::once(&self.content)
+                .chain(self.cards.iter())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<()>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let (content_tree, card_trees) = tree.children.split_at_mut(1);
+        let content = self.content.as_widget_mut().overlay(
+            &mut content_tree[0],
+            layout,
+            renderer,
+            translation,
+        );
+        let cards = (!self.cards.is_empty()).then(|| {
+            overlay::Element::new(Box::new(Overlay {
+                cards: &mut self.cards,
+                trees: card_trees,
+                position: self.position,
+            }))
+        });
+        let overlays = content.into_iter().chain(cards).collect::<Vec<_>>();
+        (!overlays.is_empty()).then(|| overlay::Group::with_children(overlays).overlay())
+    }
+}
+
+/// The overlay of the cards, placed in the corner of the window.
+struct Overlay<'a, 'b, Message> {
+    cards: &'b mut [Element<'a, Message>],
+    trees: &'b mut [Tree],
+    position: Position,
+}
+
+impl<'a, 'b, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a, 'b, Message> {
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, Size::new(WIDTH, bounds.height));
+        let nodes = self
+            .cards
+            .iter()
+            .zip(self.trees.iter_mut())
+            .map(|(card, tree)| card.as_widget().layout(tree, renderer, &limits))
+            .collect::<Vec<layout::Node>>();
+        let height = nodes.iter().map(|node| node.size().height + SPACING).sum::<f32>();
+        let mut y = match self.position {
+            Position::TopLeft | Position::TopRight => PADDING,
+            Position::BottomLeft | Position::BottomRight => {
+                bounds.height - PADDING - height + SPACING
+            }
+        };
+        let nodes = nodes
+            .into_iter()
+            .map(|node| {
+                let size = node.size();
+                let x = match self.position {
+                    Position::TopLeft | Position::BottomLeft => PADDING,
+                    Position::TopRight | Position::BottomRight => {
+                        bounds.width - PADDING - size.width
+                    }
+                };
+                let node = node.move_to(Point::new(x, y));
+                y += size.height + SPACING;
+                node
+            })
+            .collect();
+        layout::Node::with_children(bounds, nodes)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        for ((card, tree), layout) in self
+            .cards
+            .iter()
+            .zip(self.trees.iter())
+            .zip(layout.children())
+        {
+            card.as_widget()
+                .draw(tree, renderer, theme, style, layout, cursor, &layout.bounds());
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.cards
+            .iter_mut()
+            .zip(self.trees.iter_mut())
+            .zip(layout.children())
+            .map(|((card, tree), layout)| {
+                card.as_widget_mut().on_event(
+                    tree,
+                    event.clone(),
+                    layout,
+                    cursor,
+                    renderer,
+                    clipboard,
+                    shell,
+                    &layout.bounds(),
+                )
+            })
+            .fold(event::Status::Ignored, event::Status::merge)
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.cards
+            .iter()
+            .zip(self.trees.iter())
+            .zip(layout.children())
+            .map(|((card, tree), layout)| {
+                card.as_widget()
+                    .mouse_interaction(tree, layout, cursor, &layout.bounds(), renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn is_over(&self, layout: Layout<'_>, _renderer: &Renderer, cursor_position: Point) -> bool {
+        layout
+            .children()
+            .any(|layout| layout.bounds().contains(cursor_position))
+    }
+}
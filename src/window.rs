@@ -4,10 +4,16 @@
 //! Add new window directory to the list.
 
 pub mod about;
+pub mod command_palette;
+pub mod confirm;
 pub mod confirm_exit;
 pub mod fatal_error;
 pub mod information;
+pub mod log_viewer;
 pub mod default;
 pub mod main;
 pub mod unsaved_data;
+pub mod unsaved_data_batch;
 pub mod preferences;
+pub mod recoverable_error;
+pub mod restore_defaults;
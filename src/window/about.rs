@@ -10,7 +10,7 @@ use crate::{
         localisation::{Localisation, StringCache},
         traits::{AnyWindowTrait, WindowTrait},
     },
-    localisation::about::{Index, Strings},
+    localisation::about::Index,
 };
 use iced::{
     widget::{button, column, row, scrollable, text},
@@ -20,6 +20,15 @@ use std::any::Any;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+/// A dependency of the build, as listed by the build script from `cargo metadata`.
+pub struct Dependency {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub license: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/dependencies.rs"));
+
 pub struct State {
     contributors: Vec<String>,
     localisation_contributors: Vec<String>,
@@ -57,8 +66,7 @@ impl WindowTrait for State {
     }
 
     fn title<'a>(&'a self, string_cache: &'a StringCache) -> &String {
-        let strings = string_cache.get(&StringGroup::About).unwrap();
-        strings.title()
+        string_cache.title(&StringGroup::About)
     }
 
     fn view<'a>(
@@ -136,6 +144,31 @@ impl WindowTrait for State {
                 .align_x(align_start)
                 .into(),
         );
+        let mut dependencies: Vec<Element<application::Message>> =
+            Vec::<Element<application::Message>>::new();
+        for dependency in DEPENDENCIES {
+            let mut entry: Vec<Element<application::Message>> = vec![
+                text("  ").into(), // Indentation space
+                text(dependency.name).into(),
+                text(dependency.version).into(),
+                text(dependency.license).into(),
+            ];
+            if reverse_words {
+                entry.reverse();
+            }
+            dependencies.push(row(entry).spacing(10).into());
+        }
+        if reverse_lines {
+            dependencies.reverse();
+        }
+        body.push(" ".into()); // Paragraph separation
+        body.push(text(strings.string(Index::Dependencies as usize)).into());
+        body.push(
+            column(dependencies)
+                .width(Length::Fill)
+                .align_x(align_start)
+                .into(),
+        );
         if reverse_lines {
             body.reverse();
         }
@@ -147,14 +180,30 @@ impl WindowTrait for State {
         );
         content.push(" ".into()); // Paragraph separation
 
-        // OK button
-        content.push(
-            column![button(text(strings.string(Index::Ok as usize)))
+        // Buttons
+        #[allow(unused_mut)]
+        let mut buttons: Vec<Element<application::Message>> = vec![
+            button(text(strings.string(Index::Ok as usize)))
                 .padding([5, 10])
-                .on_press(application::Message::Close(id))]
-            .width(Length::Fill)
-            .align_x(Alignment::Center)
-            .into(),
+                .on_press(application::Message::Close(id))
+                .into(),
+        ];
+        #[cfg(feature = "updates")]
+        buttons.insert(
+            0,
+            button(text(strings.string(Index::CheckForUpdates as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::CheckForUpdates)
+                .into(),
+        );
+        if reverse_words {
+            buttons.reverse();
+        }
+        content.push(
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
         );
         if reverse_lines {
             content.reverse();
@@ -170,6 +219,10 @@ impl WindowTrait for State {
         true
     }
 
+    fn is_singleton(&self) -> bool {
+        true
+    }
+
     fn is_global_disable(&self) -> bool {
         true
     }
@@ -179,12 +232,9 @@ pub fn display(
     application: &mut application::State,
     parent: window::Id,
 ) -> Result<Task<application::Message>, ApplicationError> {
-    if !application.string_cache.exists(&StringGroup::About) {
-        application.string_cache.insert(
-            StringGroup::About,
-            Box::new(Strings::try_new(&application.localisation)?),
-        );
-    }
+    application
+        .string_cache
+        .try_ensure(StringGroup::About, &application.localisation)?;
     let state: Box<dyn AnyWindowTrait> = match application.manager.use_reusable(WindowType::About) {
         None => Box::new(State::try_new(&application.localisation)?),
         Some(value) => value,
@@ -193,3 +243,31 @@ pub fn display(
         .manager
         .try_create_window(&mut application.session, state, parent)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependencies_are_listed_sorted() {
+        assert!(!DEPENDENCIES.is_empty(), "`cargo metadata` failed for the build script.");
+        for dependency in DEPENDENCIES {
+            assert!(!dependency.name.is_empty());
+            assert!(
+                dependency.version.split('.').count() >= 3,
+                "{} {}",
+                dependency.name,
+                dependency.version
+            );
+        }
+        assert!(DEPENDENCIES
+            .windows(2)
+            .all(|pair| (pair[0].name, pair[0].version) <= (pair[1].name, pair[1].version)));
+
+        // The direct dependencies are listed, but not the application itself.
+        let iced = DEPENDENCIES.iter().find(|dependency| dependency.name == "iced").unwrap();
+        assert!(iced.version.starts_with("0.13."));
+        assert_eq!(iced.license, "MIT");
+        assert!(!DEPENDENCIES.iter().any(|dependency| dependency.name == env!("CARGO_PKG_NAME")));
+    }
+}
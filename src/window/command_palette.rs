@@ -0,0 +1,305 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The command palette, listing the actions of the menu bar and of the registered accelerators,
+//! see `actions()`, filtered by the search text. The selected action is executed on Enter, or by
+//! clicking it, and is dispatched to the parent window as `application::Message::Accelerator`,
+//! thus the palette, the menus and the accelerators perform the same actions.
+
+#![allow(clippy::single_match)]
+
+use crate::{
+    application::{
+        self,
+        accelerator::{Accelerators, Action},
+        ApplicationError, WindowType, StringGroup,
+    },
+    core::{
+        error::CoreError,
+        localisation::{Localisation, StringCache},
+        traits::{AnyWindowTrait, WindowTrait},
+    },
+    localisation::command_palette::Index,
+    window::default::menu_bar,
+};
+use iced::{
+    widget::{button, column, scrollable, text, text_input},
+    window, Task, Element, Length,
+};
+use std::any::Any;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+const SEARCH_ID: &str = "command_palette_search"; // The text input of the search text.
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Search(String),
+    Previous, // Arrow up pressed.
+    Next,     // Arrow down pressed.
+    Execute(Option<Action>), // `None` executes the selected action, such as when Enter is pressed.
+}
+
+pub struct State {
+    actions: Vec<Action>, // All the listed actions, in the displayed order.
+    search: String,
+    selected: usize, // Index into the filtered actions.
+}
+
+impl State {
+    pub fn new(accelerators: &Accelerators) -> Self {
+        State {
+            actions: actions(accelerators),
+            search: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// The actions whose label contains the search text, ignoring case. All the actions for an
+    /// empty search text.
+    fn filtered(&self, string_cache: &StringCache) -> Vec<Action> {
+        let strings = string_cache.get(&StringGroup::CommandPalette).unwrap();
+        let search = self.search.to_lowercase();
+        self.actions
+            .iter()
+            .filter(|action| {
+                label(action).is_some_and(|index| {
+                    strings
+                        .string(index as usize)
+                        .to_lowercase()
+                        .contains(search.as_str())
+                })
+            })
+            .copied()
+            .collect()
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::new(&Accelerators::new())
+    }
+}
+
+impl AnyWindowTrait for State {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl WindowTrait for State {
+    fn window_type(&self) -> WindowType {
+        WindowType::CommandPalette
+    }
+
+    fn title<'a>(&'a self, string_cache: &'a StringCache) -> &'a String {
+        string_cache.title(&StringGroup::CommandPalette)
+    }
+
+    fn try_update(
+        &mut self,
+        message: application::Message,
+        string_cache: &StringCache,
+    ) -> Result<Task<application::Message>, ApplicationError> {
+        match message {
+            application::Message::CommandPalette(_id, ref inner_message) => {
+                let count = self.filtered(string_cache).len();
+                match inner_message {
+                    Message::Search(search) => {
+                        self.search = search.clone();
+                        self.selected = 0;
+                    }
+                    Message::Previous => self.selected = self.selected.saturating_sub(1),
+                    Message::Next => self.selected = (self.selected + 1).min(count.saturating_sub(1)),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(Task::none())
+    }
+
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let align_start = localisation.layout_data().align_words_start;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let strings = string_cache.get(&StringGroup::CommandPalette).unwrap();
+
+        // Actions
+        let actions = self.filtered(string_cache);
+        let mut items: Vec<Element<application::Message>> = actions
+            .iter()
+            .enumerate()
+            .map(|(index, action)| {
+                let label = label(action).unwrap_or(Index::Title);
+                button(text(strings.string(label as usize)))
+                    .width(Length::Fill)
+                    .padding([5, 10])
+                    .style(if index == self.selected {
+                        button::primary
+                    } else {
+                        button::text
+                    })
+                    .on_press(application::Message::CommandPalette(
+                        id,
+                        Message::Execute(Some(*action)),
+                    ))
+                    .into()
+            })
+            .collect();
+        if items.is_empty() {
+            items.push(text(strings.string(Index::NoResults as usize)).into());
+        }
+
+        let mut content: Vec<Element<application::Message>> = vec![
+            text_input(strings.string(Index::Search as usize), self.search.as_str())
+                .id(text_input::Id::new(SEARCH_ID))
+                .on_input(move |search| application::Message::CommandPalette(id, Message::Search(search)))
+                .on_submit(application::Message::CommandPalette(id, Message::Execute(None)))
+                .padding(5)
+                .into(),
+            scrollable(column(items).width(Length::Fill).align_x(align_start))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        ];
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .spacing(5)
+            .padding(2)
+            .into()
+    }
+}
+
+/// The string index of the action's label, `None` for the actions not listed in the palette.
+pub fn label(action: &Action) -> Option<Index> {
+    match action {
+        Action::New => Some(Index::New),
+        Action::Preferences => Some(Index::Preferences),
+        Action::LogViewer => Some(Index::LogViewer),
+        Action::About => Some(Index::About),
+        Action::Close => Some(Index::Close),
+        Action::Exit => Some(Index::Exit),
+        Action::CommandPalette
+        | Action::Undo
+        | Action::Redo
+        | Action::ZoomIn
+        | Action::ZoomOut
+        | Action::ZoomReset => None,
+    }
+}
+
+/// The actions listed in the palette: the actions of the menu bar in menu order, followed by the
+/// remaining actions of the registered accelerators, thus the palette stays in sync with both.
+/// Only the actions having a label are listed.
+pub fn actions(accelerators: &Accelerators) -> Vec<Action> {
+    let mut actions = Vec::<Action>::new();
+    for action in menu_bar::actions().into_iter().chain(accelerators.actions()) {
+        if label(&action).is_some() && !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+    actions
+}
+
+/// Display the command palette, which disables only the parent window.
+pub fn display(
+    application: &mut application::State,
+    parent: window::Id,
+) -> Result<Task<application::Message>, ApplicationError> {
+    application
+        .string_cache
+        .try_ensure(StringGroup::CommandPalette, &application.localisation)?;
+    let state = State::new(application.accelerators());
+    let tasks = application.manager.try_create_window(
+        &mut application.session,
+        Box::new(state),
+        parent,
+    )?;
+    Ok(tasks.chain(text_input::focus(text_input::Id::new(SEARCH_ID))))
+}
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut tasks = Task::none();
+    match message {
+        application::Message::CommandPalette(id, ref inner_message) => {
+            let Some(state) = application.manager.state_mut(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            tasks = state.try_update(message.clone(), &application.string_cache)?;
+
+            // Post internal update
+            match inner_message {
+                Message::Execute(action) => {
+                    let action = match action {
+                        Some(action) => Some(*action),
+                        None => {
+                            let actual = state.as_any().downcast_ref::<State>().unwrap();
+                            actual
+                                .filtered(&application.string_cache)
+                                .get(actual.selected)
+                                .copied()
+                        }
+                    };
+                    // Nothing to execute when there are no results.
+                    let Some(action) = action else {
+                        return Ok(tasks);
+                    };
+                    let Some(parent) = application.manager.parent(&id) else {
+                        return Err(CoreError::ExpectedWindowParent(WindowType::CommandPalette))?;
+                    };
+                    tasks = application
+                        .manager
+                        .close_window(id)?
+                        .chain(Task::done(application::Message::Accelerator(parent, action)));
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::accelerator::KeyCombination;
+    use iced::keyboard::Modifiers;
+
+    #[test]
+    fn actions_follow_menu_bar_and_accelerators() {
+        let mut accelerators = Accelerators::new();
+        assert_eq!(
+            actions(&accelerators),
+            vec![
+                Action::New,
+                Action::Exit,
+                Action::Preferences,
+                Action::LogViewer,
+                Action::About,
+                Action::Close,
+            ]
+        );
+
+        // Close is only reachable through its accelerator.
+        accelerators.unregister(&KeyCombination::new("w", Modifiers::COMMAND));
+        assert!(!actions(&accelerators).contains(&Action::Close));
+    }
+}
@@ -0,0 +1,8 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! A generic modal dialogue, having a title, a statement and a row of buttons. Used by the
+//! ConfirmExit and UnsavedData windows.
+
+pub mod state;
+pub use state::*;
@@ -0,0 +1,287 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+use crate::{
+    application::{self, ApplicationError, StringGroup, WindowType},
+    core::{
+        localisation::{Localisation, StringCache},
+        traits::{AnyWindowTrait, WindowTrait},
+    },
+};
+use iced::{
+    widget::{button, column, row, text},
+    window, Alignment, Element, Length,
+};
+use std::any::Any;
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// A localised text of the dialogue.
+pub enum Text {
+    /// A string of the cached string group, thus updated when the language changes. Index 0 is
+    /// the title of the string group.
+    Cached(StringGroup, usize),
+
+    /// A string localised when the dialogue was created, such as when having placeholders.
+    Owned(RefCount<String>),
+}
+
+impl Text {
+    fn as_str<'a>(&'a self, string_cache: &'a StringCache) -> &'a str {
+        match self {
+            Text::Cached(string_group, index) => {
+                let strings = string_cache.get(string_group).unwrap();
+                strings.string(*index).as_str()
+            }
+            Text::Owned(string) => string.as_str(),
+        }
+    }
+}
+
+/// The message of a button, created with the window Id of the dialogue.
+pub type OnPress = fn(window::Id) -> application::Message;
+
+/// Localises the owned title and statement again, after the language has changed.
+pub type Relocalise = Box<dyn Fn(&Localisation) -> Result<(Text, Option<Text>), ApplicationError>>;
+
+pub struct State {
+    window_type: WindowType,
+    title: Text,
+    statement: Option<Text>,
+    buttons: Vec<(Text, OnPress)>,
+    padding: u16,
+    global_disable: bool,
+    reusable: bool,
+    relocalise: Option<Relocalise>,
+}
+
+impl State {
+    /// Creates the dialogue for the window type, which only disables its parent window.
+    pub fn new(window_type: WindowType, title: Text) -> Self {
+        State {
+            window_type,
+            title,
+            statement: None,
+            buttons: Vec::<(Text, OnPress)>::new(),
+            padding: 2,
+            global_disable: false,
+            reusable: false,
+            relocalise: None,
+        }
+    }
+
+    /// Sets the statement displayed above the buttons.
+    pub fn statement(mut self, statement: Text) -> Self {
+        self.statement = Some(statement);
+        self
+    }
+
+    /// Adds a button, where the buttons are displayed in the order added, or reversed for right
+    /// to left languages.
+    pub fn button(mut self, label: Text, on_press: OnPress) -> Self {
+        self.buttons.push((label, on_press));
+        self
+    }
+
+    /// Sets the padding of the content.
+    pub fn padding(mut self, padding: u16) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets whether all the windows are disabled, instead of only the parent window.
+    pub fn global_disable(mut self, global_disable: bool) -> Self {
+        self.global_disable = global_disable;
+        self
+    }
+
+    /// Sets the function for localising the owned title and statement again, required when
+    /// using `Text::Owned`.
+    pub fn relocalise(mut self, relocalise: Relocalise) -> Self {
+        self.relocalise = Some(relocalise);
+        self
+    }
+
+    /// Sets whether the state is cached for reuse when the window is closed.
+    pub fn reusable(mut self, reusable: bool) -> Self {
+        self.reusable = reusable;
+        self
+    }
+}
+
+impl AnyWindowTrait for State {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl WindowTrait for State {
+    fn window_type(&self) -> WindowType {
+        self.window_type.clone()
+    }
+
+    fn title<'a>(&'a self, string_cache: &'a StringCache) -> &'a String {
+        match &self.title {
+            Text::Cached(string_group, index) => {
+                string_cache.get(string_group).unwrap().string(*index)
+            }
+            Text::Owned(string) => string,
+        }
+    }
+
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let reverse_words = localisation.layout_data().reverse_words;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let mut content = Vec::<Element<application::Message>>::new();
+
+        // Statement
+        if let Some(statement) = &self.statement {
+            content.push(
+                column![text(statement.as_str(string_cache))]
+                    .width(Length::Fill)
+                    .align_x(Alignment::Center)
+                    .into(),
+            );
+        }
+        content.push(text(" ").height(Length::Fill).into()); // Paragraph separation
+
+        // Buttons
+        let mut buttons = self
+            .buttons
+            .iter()
+            .map(|(label, on_press)| {
+                button(text(label.as_str(string_cache)))
+                    .padding([5, 10])
+                    .on_press(on_press(id))
+                    .into()
+            })
+            .collect::<Vec<Element<application::Message>>>();
+        if reverse_words {
+            buttons.reverse();
+        }
+        content.push(
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
+        );
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(self.padding)
+            .into()
+    }
+
+    fn is_reusable(&self) -> bool {
+        self.reusable
+    }
+
+    fn is_global_disable(&self) -> bool {
+        self.global_disable
+    }
+
+    fn try_localise(
+        &mut self,
+        localisation: &Localisation,
+    ) -> Result<(), ApplicationError> {
+        if let Some(relocalise) = &self.relocalise {
+            let (title, statement) = relocalise(localisation)?;
+            self.title = title;
+            self.statement = statement;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::confirm_exit;
+
+    /// The labels of the owned texts and the debug output of the messages, of the buttons.
+    fn buttons(state: &State, id: window::Id) -> Vec<(String, String)> {
+        let string_cache = StringCache::new();
+        state
+            .buttons
+            .iter()
+            .map(|(label, on_press)| {
+                let label = match label {
+                    Text::Owned(_) => label.as_str(&string_cache).to_string(),
+                    Text::Cached(_, index) => index.to_string(),
+                };
+                (label, format!("{:?}", on_press(id)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn three_buttons_emit_their_messages() {
+        let id = window::Id::unique();
+        let owned = |string: &str| Text::Owned(RefCount::new(string.to_string()));
+        let state = State::new(WindowType::ConfirmExit, owned("Title"))
+            .statement(owned("Statement"))
+            .button(owned("Save"), |_| application::Message::SaveAndExit)
+            .button(owned("Exit"), |_| application::Message::Exit)
+            .button(owned("Cancel"), application::Message::Close);
+        assert_eq!(
+            buttons(&state, id),
+            vec![
+                ("Save".to_string(), format!("{:?}", application::Message::SaveAndExit)),
+                ("Exit".to_string(), format!("{:?}", application::Message::Exit)),
+                ("Cancel".to_string(), format!("{:?}", application::Message::Close(id))),
+            ]
+        );
+        assert!(!state.is_global_disable());
+        assert!(!state.is_reusable());
+    }
+
+    #[test]
+    fn confirm_exit_with_unsaved_data() {
+        let id = window::Id::unique();
+        let messages = |state: &State| {
+            buttons(state, id).into_iter().map(|(_, message)| message).collect::<Vec<String>>()
+        };
+        let state = confirm_exit::new(true);
+        assert_eq!(
+            messages(&state),
+            vec![
+                format!("{:?}", application::Message::SaveAndExit),
+                format!("{:?}", application::Message::Exit),
+                format!("{:?}", application::Message::Close(id)),
+            ]
+        );
+        assert!(state.is_global_disable());
+        assert!(!state.is_reusable());
+
+        // Without unsaved data, the dialogue is reusable.
+        let state = confirm_exit::new(false);
+        assert_eq!(
+            messages(&state),
+            vec![
+                format!("{:?}", application::Message::Exit),
+                format!("{:?}", application::Message::Close(id)),
+            ]
+        );
+        assert!(state.is_reusable());
+    }
+}
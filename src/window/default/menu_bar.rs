@@ -2,7 +2,7 @@
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
 use crate::{
-    application::{StringGroup, WindowType,},
+    application::{accelerator::Action, StringGroup, WindowType,},
     core::localisation::StringCache,
     localisation::{default, main_common},
 };
@@ -16,8 +16,7 @@ use iced::{
 
 #[cfg(not(feature = "iced_aw"))]
 use iced_aw::{
-    menu::{self, Item, Menu},
-    menu_bar, menu_items,
+    menu::{self, Item, Menu, MenuBar},
     widgets::InnerBounds,
     quad,
     style::{menu_bar::primary, Status},
@@ -27,14 +26,12 @@ use iced_aw::{
 use crate::{
     iced_aw::{
         widgets::{
-            menu::{self, Item, Menu},
+            menu::{self, Item, Menu, MenuBar},
             InnerBounds,
             quad,
         },
         style::{menu_bar::primary, Status},
     },
-    menu_bar,
-    menu_items,
 };
 
 #[allow(unused_imports)]
@@ -44,14 +41,95 @@ use log::{debug, error, info, trace, warn};
 pub enum Message {
     None, // Used for the menu bar button, and buttons that open sub menus to the side.
     New(WindowType),
-    //Open(WindowType),
+    Open,
+    OpenReadOnly,
     Exit,
     Preferences,
     About,
+    LogViewer,
+}
+
+impl Message {
+    /// The action performed by the menu item, if any.
+    pub fn action(&self) -> Option<Action> {
+        match self {
+            Message::New(WindowType::Main) => Some(Action::New),
+            Message::Exit => Some(Action::Exit),
+            Message::Preferences => Some(Action::Preferences),
+            Message::About => Some(Action::About),
+            Message::LogViewer => Some(Action::LogViewer),
+            _ => None,
+        }
+    }
+}
+
+/// A menu entry: the item with the string group and index of its label, or a separator.
+enum Entry {
+    Item(StringGroup, usize, Message),
+    Separator,
+}
+
+/// The menus of the menu bar, with the string index of the menu title.
+static MENUS: [(usize, &[Entry]); 3] = [
+    // Database menu
+    (
+        main_common::Index::File as usize,
+        &[
+            Entry::Item(
+                StringGroup::MainCommon,
+                main_common::Index::New as usize,
+                Message::New(WindowType::Main),
+            ),
+            Entry::Item(StringGroup::MainCommon, main_common::Index::Open as usize, Message::Open),
+            Entry::Item(
+                StringGroup::MainCommon,
+                main_common::Index::OpenReadOnly as usize,
+                Message::OpenReadOnly,
+            ),
+            Entry::Separator,
+            Entry::Item(StringGroup::Default, default::Index::Exit as usize, Message::Exit),
+        ],
+    ),
+    // Edit menu
+    (
+        main_common::Index::Edit as usize,
+        &[Entry::Item(
+            StringGroup::MainCommon,
+            main_common::Index::Preferences as usize,
+            Message::Preferences,
+        )],
+    ),
+    // Help menu
+    (
+        main_common::Index::Help as usize,
+        &[
+            Entry::Item(
+                StringGroup::MainCommon,
+                main_common::Index::LogViewer as usize,
+                Message::LogViewer,
+            ),
+            Entry::Item(
+                StringGroup::MainCommon,
+                main_common::Index::About as usize,
+                Message::About,
+            ),
+        ],
+    ),
+];
+
+/// The actions of the menu items, in menu order, such as for the command palette.
+pub fn actions() -> Vec<Action> {
+    MENUS
+        .iter()
+        .flat_map(|(_title, entries)| entries.iter())
+        .filter_map(|entry| match entry {
+            Entry::Item(_group, _index, message) => message.action(),
+            Entry::Separator => None,
+        })
+        .collect()
 }
 
 pub fn view(_id: window::Id, string_cache: &StringCache) -> Element<'_, Message> {
-    let default = string_cache.get(&StringGroup::Default).unwrap();
     let common = string_cache.get(&StringGroup::MainCommon).unwrap();
     let menu_type_1 = |items| Menu::new(items).max_width(180.0).offset(15.0).spacing(5.0);
 
@@ -60,42 +138,26 @@ pub fn view(_id: window::Id, string_cache: &StringCache) -> Element<'_, Message>
     |items| Menu::new(items).max_width(180.0).offset(0.0).spacing(5.0);
     */
 
-    let bar = menu_bar!(
-        // Database menu
-        (
-            labeled_button(common.string(main_common::Index::File as usize), Message::None),
-            menu_type_1(menu_items!(
-                (labeled_button(
-                    common.string(main_common::Index::New as usize),
-                    Message::New(WindowType::Main)
-                ))
-                /*
-                (labeled_button(
-                    common.string(main_common::Index::Open as usize),
-                    Message::Open(WindowType::Main)
-                ))
-                */
-                (separator())
-                (labeled_button(default.string(default::Index::Exit as usize), Message::Exit))
-            ))
-        )
-
-        // Edit menu
-        (
-            labeled_button(common.string(main_common::Index::Edit as usize), Message::None),
-            menu_type_1(menu_items!(
-                (labeled_button(common.string(main_common::Index::Preferences as usize), Message::Preferences))
-            ))
-        )
-
-        // Help menu
-        (
-            labeled_button(common.string(main_common::Index::Help as usize), Message::None),
-            menu_type_1(menu_items!(
-                (labeled_button(common.string(main_common::Index::About as usize), Message::About))
-            ))
-        )
-    )
+    let menus = MENUS
+        .iter()
+        .map(|(title, entries)| {
+            let items = entries
+                .iter()
+                .map(|entry| match entry {
+                    Entry::Item(group, index, message) => Item::new(labeled_button(
+                        string_cache.get(group).unwrap().string(*index),
+                        message.clone(),
+                    )),
+                    Entry::Separator => Item::new(separator()),
+                })
+                .collect();
+            Item::with_menu(
+                labeled_button(common.string(*title), Message::None),
+                menu_type_1(items),
+            )
+        })
+        .collect();
+    let bar = MenuBar::new(menus)
     .draw_path(menu::DrawPath::Backdrop)
     .style(|theme:&iced::Theme, status: Status | menu::Style{
         path_border: Border{
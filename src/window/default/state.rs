@@ -7,15 +7,18 @@ use crate::{
         localisation::{Localisation, StringCache},
         traits::{AnyWindowTrait, WindowTrait},
     },
-    localisation,
-    window::{about, default::menu_bar, preferences},
+    localisation::{default::Index, main_common},
+    window::{about, default::menu_bar, log_viewer, preferences},
 };
 use iced::{
-    widget::{column, text},
-    window, Task, Element, Length,
+    widget::{button, column, container, row, text},
+    window, Alignment, Border, Task, Element, Length, Theme,
 };
 use std::any::Any;
 
+#[cfg(any(test, feature = "debug_snapshot"))]
+use crate::core::traits::WindowSnapshot;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
@@ -33,12 +36,16 @@ pub enum Message {
     //FatalError,
 }
 
-/// An empty state, as window just contains a menu bar.
-pub struct State {}
+/// An almost empty state, as window just contains a menu bar, and a welcome with the quick actions.
+pub struct State {
+    file_hovered: bool, // Files are being dragged over the window.
+}
 
 impl State {
     pub fn new() -> Self {
-        State {}
+        State {
+            file_hovered: false,
+        }
     }
 }
 
@@ -58,8 +65,19 @@ impl WindowTrait for State {
     }
 
     fn title<'a>(&'a self, string_cache: &'a StringCache) -> &String {
-        let strings = string_cache.get(&StringGroup::Default).unwrap();
-        strings.title()
+        string_cache.title(&StringGroup::Default)
+    }
+
+    // Add any additional state flags of the window to the snapshot.
+    #[cfg(any(test, feature = "debug_snapshot"))]
+    fn debug_snapshot(&self, string_cache: &StringCache) -> WindowSnapshot {
+        WindowSnapshot {
+            window_type: WindowType::Default,
+            enabled: true,
+            title: self.title(string_cache).clone(),
+            is_unsaved: false,
+            is_first_use: false,
+        }
     }
 
     fn view<'a>(
@@ -68,11 +86,10 @@ impl WindowTrait for State {
         localisation: &Localisation,
         string_cache: &'a StringCache,
     ) -> Element<application::Message> {
+        let reverse_words = localisation.layout_data().reverse_words;
         let reverse_lines = localisation.layout_data().reverse_lines;
-        //let common = string_cache.get(&WindowType::MainCommon).unwrap();
-        //let common_actual = common.as_any().downcast_ref::<main_common::Strings>().unwrap();
-        //let strings = string_cache.get(&StringGroup::Default).unwrap();
-        //let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        let common = string_cache.get(&StringGroup::MainCommon).unwrap();
+        let strings = string_cache.get(&StringGroup::Default).unwrap();
         let mut content: Vec<Element<application::Message>> =
             Vec::<Element<application::Message>>::new();
 
@@ -83,8 +100,36 @@ impl WindowTrait for State {
             }),
         );
 
-        // Content
-        content.push(text("Test message - Temporary.").into());
+        // Welcome and the quick actions, which are the same actions as the menu bar.
+        let mut actions: Vec<Element<application::Message>> = [
+            (main_common::Index::New, menu_bar::Message::New(WindowType::Main)),
+            (main_common::Index::Open, menu_bar::Message::Open),
+            (main_common::Index::Preferences, menu_bar::Message::Preferences),
+            (main_common::Index::About, menu_bar::Message::About),
+        ]
+        .into_iter()
+        .map(|(index, message)| {
+            button(text(common.string(index as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::Default(id, Message::MenuBar(message)))
+                .into()
+        })
+        .collect();
+        if reverse_words {
+            actions.reverse();
+        }
+        content.push(
+            column![
+                text(strings.string(Index::Welcome as usize)).size(24),
+                row(actions).spacing(10),
+            ]
+            .spacing(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .padding(20)
+            .into(),
+        );
 
         // Temporary testing content
         // Uncomment to test FatalError window
@@ -102,37 +147,42 @@ impl WindowTrait for State {
         if reverse_lines {
             content.reverse();
         }
-        column(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(2)
+        let file_hovered = self.file_hovered;
+        container(column(content).width(Length::Fill).height(Length::Fill).padding(2))
+            .style(move |theme: &Theme| container::Style {
+                // Highlight the window while files are dragged over it.
+                border: Border {
+                    color: theme.palette().primary,
+                    width: if file_hovered { 2.0 } else { 0.0 },
+                    radius: 0.0.into(),
+                },
+                ..container::Style::default()
+            })
             .into()
     }
 
+    fn set_file_hovered(&mut self, hovered: bool) {
+        self.file_hovered = hovered;
+    }
+
     fn is_reusable(&self) -> bool {
         true
     }
+
+    fn reset(&mut self) {
+        self.file_hovered = false;
+    }
 }
 
 pub fn display(
     application: &mut application::State,
 ) -> Result<Task<application::Message>, ApplicationError> {
-    if !application.string_cache.exists(&StringGroup::Default) {
-        application.string_cache.insert(
-            StringGroup::Default,
-            Box::new(
-                localisation::default::Strings::try_new(&application.localisation)?
-            ),
-        );
-    }
-    if !application.string_cache.exists(&StringGroup::MainCommon) {
-        application.string_cache.insert(
-            StringGroup::MainCommon,
-            Box::new(
-                localisation::main_common::Strings::try_new(&application.localisation)?
-            ),
-        );
-    }
+    application
+        .string_cache
+        .try_ensure(StringGroup::Default, &application.localisation)?;
+    application
+        .string_cache
+        .try_ensure(StringGroup::MainCommon, &application.localisation)?;
     let state: Box<dyn AnyWindowTrait> = match application.manager.use_reusable(WindowType::Default) {
         None => Box::new(State::new()),
         Some(value) => value,
@@ -149,11 +199,17 @@ pub fn try_update(
         application::Message::Default(id, main_message) => match main_message {
             Message::MenuBar(menubar_message) => match menubar_message {
                 menu_bar::Message::None => {} // No action.
-                menu_bar::Message::New(window_type) => tasks = application.open_thread(window_type)?,
-                //menu_bar::Message::Open(window_type) => tasks = application.open_thread(window_type)?,
+                menu_bar::Message::New(window_type) => tasks = application.open_thread(window_type, None, false)?,
+                menu_bar::Message::Open => {
+                    tasks = Task::done(application::Message::OpenFileDialog(id, false))
+                }
+                menu_bar::Message::OpenReadOnly => {
+                    tasks = Task::done(application::Message::OpenFileDialog(id, true))
+                }
                 menu_bar::Message::Exit => tasks = application.close_thread(id)?,
                 menu_bar::Message::Preferences => tasks = preferences::display(application, id)?,
                 menu_bar::Message::About => tasks = about::display(application, id)?,
+                menu_bar::Message::LogViewer => tasks = log_viewer::display(application, id)?,
             },
 
             // Temporary testing content
@@ -1,16 +1,24 @@
 // This file is part of `iced_af` crate. For the terms of use, please see the file
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
+#![allow(clippy::single_match)]
+
 use crate::{
-    application::{self, ApplicationError, WindowType, StringGroup},
+    application::{
+        self,
+        constants::{APPLICATION_NAME, GIT_COMMIT, VERSION},
+        ApplicationError, WindowType, StringGroup,
+    },
     core::{
         localisation::{Localisation, StringCache},
         traits::{AnyWindowTrait, WindowTrait},
     },
     localisation::fatal_error::{Index, Strings},
 };
+use i18n::utility::{LocalisationData, LocalisationTrait, PlaceholderValue};
 use iced::{
-    widget::{button, column, scrollable, text},
+    clipboard,
+    widget::{button, column, row, scrollable, text},
     window, Alignment, Task, Element, Length,
 };
 use std::any::Any;
@@ -18,12 +26,59 @@ use std::any::Any;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
-pub struct State {}
+#[derive(Debug, Clone)]
+pub enum Message {
+    CopyDetails, // Copy the error details to the clipboard, such as for a bug report.
+}
+
+pub struct State {
+    error: ApplicationError, // The original error, for the details.
+    chain: String, // The nested error variants, one per line.
+}
 
 impl State {
-    pub fn new() -> Self {
-        State {}
+    pub fn new(error: ApplicationError) -> Self {
+        let chain = error_chain(&error.localisation_data()).join("\n");
+        State { error, chain }
+    }
+
+    /// The details of the error: the fully expanded localised error, the nested error variants,
+    /// the non-localised error, and the version of the application.
+    pub fn details(&self, localisation: &Localisation) -> String {
+        let localised =
+            match localisation.format_localisation_data_with_defaults(&self.error.localisation_data()) {
+                Ok(value) => value.0.to_string(),
+                Err(_) => self.error.to_string(),
+            };
+        format!(
+            "{}\n\n{}\n\n{}\n\n{} {} ({})",
+            localised, self.chain, self.error, APPLICATION_NAME, VERSION, GIT_COMMIT
+        )
+    }
+}
+
+/// The `type::variant` of each nested error of the localisation data, from the outermost error,
+/// with each nested error indented further. The nesting ends at the first error without an
+/// embedded `error` value.
+fn error_chain(data: &LocalisationData) -> Vec<String> {
+    let mut chain = Vec::<String>::new();
+    let mut current = Some(data);
+    while let Some(data) = current {
+        let Some(values) = data.values.as_ref() else {
+            break;
+        };
+        match (values.get("type"), values.get("variant")) {
+            (Some(PlaceholderValue::String(type_name)), Some(PlaceholderValue::String(variant))) => {
+                chain.push(format!("{}{}::{}", "  ".repeat(chain.len()), type_name, variant))
+            }
+            _ => break,
+        }
+        current = match values.get("error") {
+            Some(PlaceholderValue::LocalisationData(data)) => Some(data),
+            _ => None,
+        };
     }
+    chain
 }
 
 impl AnyWindowTrait for State {
@@ -42,38 +97,53 @@ impl WindowTrait for State {
     }
 
     fn title<'a>(&'a self, string_cache: &'a StringCache) -> &String {
-        let strings = string_cache.get(&StringGroup::FatalError).unwrap();
-        strings.title()
+        string_cache.title(&StringGroup::FatalError)
     }
 
     fn view<'a>(
         &'a self,
-        _id: window::Id,
+        id: window::Id,
         localisation: &Localisation,
         string_cache: &'a StringCache,
     ) -> Element<application::Message> {
         let strings = string_cache.get(&StringGroup::FatalError).unwrap();
         let align_start = localisation.layout_data().align_words_start;
 
+        let mut buttons: Vec<Element<application::Message>> = vec![
+            button(text(strings.string(Index::CopyDetails as usize).as_str()))
+                .padding([5, 10])
+                .on_press(application::Message::FatalError(id, Message::CopyDetails))
+                .into(),
+            button(text(strings.string(Index::Exit as usize).as_str()))
+                .padding([5, 10])
+                .on_press(application::Message::Terminate)
+                .into(),
+        ];
+        if localisation.layout_data().reverse_words {
+            buttons.reverse();
+        }
+
         #[allow(unused_mut)]
         let mut content: Vec<Element<application::Message>> = vec![
             // Message - scrollable
             scrollable(
-                column![text(strings.string(Index::UncaughtError as usize).as_str())]
-                    .width(Length::Fill)
-                    .align_x(align_start),
+                column![
+                    text(strings.string(Index::UncaughtError as usize).as_str()),
+                    text(self.chain.as_str()),
+                ]
+                .spacing(10)
+                .width(Length::Fill)
+                .align_x(align_start),
             )
             .width(Length::Fill)
             .height(Length::Fill)
             .into(),
             " ".into(), // Paragraph separation
-            // Exit button
-            column![button(text(strings.string(Index::Exit as usize).as_str()))
-                .padding([5, 10])
-                .on_press(application::Message::Terminate)]
-            .width(Length::Fill)
-            .align_x(Alignment::Center)
-            .into(),
+            // Buttons
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
         ];
         if localisation.layout_data().reverse_lines {
             content.reverse();
@@ -97,8 +167,71 @@ pub fn display(
     {
         application.string_cache.insert(
             StringGroup::FatalError,
-            Box::new(Strings::new(&application.localisation, error)),
+            Box::new(Strings::new(&application.localisation, error.clone())),
         );
     }
-    application.manager.create_fatal_error_window(&mut application.session)
+    application.manager.create_fatal_error_window(&mut application.session, error)
+}
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut tasks = Task::none();
+    match message {
+        application::Message::FatalError(id, ref inner_message) => match inner_message {
+            Message::CopyDetails => {
+                if let Some(state) = application.manager.state(&id) {
+                    let actual = state.as_any().downcast_ref::<State>().unwrap();
+                    tasks = clipboard::write(actual.details(&application.localisation));
+                }
+            }
+        },
+        _ => {}
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{error::CoreError, testing::localisation};
+    use i18n::{
+        localiser::LocaliserError,
+        provider::{ProviderError, ProviderErrorTrait},
+        provider_sqlite3::ProviderSqlite3Error,
+    };
+    use std::path::PathBuf;
+
+    #[cfg(not(feature = "sync"))]
+    use std::rc::Rc as RefCount;
+
+    #[cfg(feature = "sync")]
+    use std::sync::Arc as RefCount;
+
+    #[test]
+    fn details_contain_nested_error_chain() {
+        let sqlite3: Box<dyn ProviderErrorTrait> =
+            Box::new(ProviderSqlite3Error::NotExists(PathBuf::from("missing.sqlite3")));
+        let error = ApplicationError::Core(CoreError::Localiser(LocaliserError::Provider(
+            ProviderError::Custom(RefCount::new(sqlite3)),
+        )));
+        let state = State::new(error);
+        let chain = "ApplicationError::Core\n  \
+                     CoreError::Localiser\n    \
+                     LocaliserError::Provider\n      \
+                     ProviderError::Custom\n        \
+                     ProviderSqlite3Error::NotExists";
+        assert_eq!(state.chain, chain);
+
+        // The localised error is expanded down to the innermost error.
+        let localisation = localisation("en-ZA");
+        let details = state.details(&localisation);
+        let (localised, rest) = details.split_once("\n\n").unwrap();
+        assert!(localised.starts_with("ApplicationError::Core: [CoreError::Localiser: "));
+        assert!(localised.contains("ProviderSqlite3Error::NotExists"), "{}", localised);
+        assert!(localised.contains("missing.sqlite3"), "{}", localised);
+        assert!(rest.starts_with(chain));
+        assert!(details.ends_with(&format!("{} {} ({})", APPLICATION_NAME, VERSION, GIT_COMMIT)));
+    }
 }
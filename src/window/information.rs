@@ -1,9 +1,14 @@
 // This file is part of `iced_af` crate. For the terms of use, please see the file
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
+#![allow(clippy::single_match)]
+
 use crate::{
-    application::{self, ApplicationError, constants::APPLICATION_NAME_SHORT, WindowType, StringGroup},
+    application::{
+        self, ApplicationError, constants::APPLICATION_NAME_SHORT, timer, WindowType, StringGroup,
+    },
     core::{
+        error::CoreError,
         localisation::{Localisation, StringCache},
         traits::{AnyWindowTrait, LocalisedTrait, WindowTrait},
     },
@@ -12,11 +17,12 @@ use crate::{
 use i18n::utility::PlaceholderValue;
 use iced::{
     widget::{button, column, scrollable, text},
-    window, Alignment, Task, Element, Length,
+    window, Alignment, Subscription, Task, Element, Length,
 };
 use std::{
     any::Any,
     collections::HashMap,
+    time::Duration,
 };
 
 #[cfg(not(feature = "sync"))]
@@ -29,10 +35,17 @@ use std::sync::Arc as RefCount;
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+#[derive(Debug, Clone)]
+pub enum Message {
+    Close, // Close button was pressed, or the window has expired.
+}
+
 pub struct State {
     information_type: InformationType,
+    name: String, // The localised window name provided by the caller.
     title: RefCount<String>,
     message: String,
+    ttl: Option<Duration>, // Automatically close the window after this duration.
 }
 
 impl State {
@@ -41,25 +54,9 @@ impl State {
         title: String,
         message: String,
         strings: &Strings,
+        ttl: Option<Duration>,
     ) -> Result<State, ApplicationError> {
-        let title = {
-            let mut values = HashMap::<String, PlaceholderValue>::new();
-            values.insert(
-                "application".to_string(),
-                PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
-            );
-            values.insert(
-                "type".to_string(),
-                PlaceholderValue::String(strings.string(Index::Information as usize).to_string()),
-            );
-            values.insert("window".to_string(), PlaceholderValue::String(title));
-            localisation.format_with_defaults("application", "window_type_title_format", &values)?
-        }.0;
-        Ok(State {
-            information_type: InformationType::Information,
-            title,
-            message,
-        })
+        State::try_new(localisation, InformationType::Information, title, message, strings, ttl)
     }
 
     pub fn try_warning(
@@ -67,25 +64,9 @@ impl State {
         title: String,
         message: String,
         strings: &Strings,
+        ttl: Option<Duration>,
     ) -> Result<State, ApplicationError> {
-        let title = {
-            let mut values = HashMap::<String, PlaceholderValue>::new();
-            values.insert(
-                "application".to_string(),
-                PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
-            );
-            values.insert(
-                "type".to_string(),
-                PlaceholderValue::String(strings.string(Index::Warning as usize).to_string()),
-            );
-            values.insert("window".to_string(), PlaceholderValue::String(title));
-            localisation.format_with_defaults("application", "window_type_title_format", &values)?
-        }.0;
-        Ok(State {
-            information_type: InformationType::Warning,
-            title,
-            message,
-        })
+        State::try_new(localisation, InformationType::Warning, title, message, strings, ttl)
     }
 
     pub fn try_error(
@@ -93,24 +74,26 @@ impl State {
         title: String,
         message: String,
         strings: &Strings,
+        ttl: Option<Duration>,
     ) -> Result<State, ApplicationError> {
-        let title = {
-            let mut values = HashMap::<String, PlaceholderValue>::new();
-            values.insert(
-                "application".to_string(),
-                PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
-            );
-            values.insert(
-                "type".to_string(),
-                PlaceholderValue::String(strings.string(Index::Warning as usize).to_string()),
-            );
-            values.insert("window".to_string(), PlaceholderValue::String(title));
-            localisation.format_with_defaults("application", "window_type_title_format", &values)?
-        }.0;
+        State::try_new(localisation, InformationType::Error, title, message, strings, ttl)
+    }
+
+    fn try_new(
+        localisation: &Localisation,
+        information_type: InformationType,
+        name: String,
+        message: String,
+        strings: &Strings,
+        ttl: Option<Duration>,
+    ) -> Result<State, ApplicationError> {
+        let title = localise(localisation, strings, &information_type, name.as_str())?;
         Ok(State {
-            information_type: InformationType::Error,
+            information_type,
+            name,
             title,
             message,
+            ttl,
         })
     }
 }
@@ -134,6 +117,17 @@ impl WindowTrait for State {
         &self.title
     }
 
+    // The window name and message are localised by the caller, thus only the information type
+    // of the title is updated.
+    fn try_localise(
+        &mut self,
+        localisation: &Localisation,
+    ) -> Result<(), ApplicationError> {
+        let strings = Strings::try_new(localisation)?;
+        self.title = localise(localisation, &strings, &self.information_type, self.name.as_str())?;
+        Ok(())
+    }
+
     fn view<'a>(
         &'a self,
         id: window::Id,
@@ -159,7 +153,7 @@ impl WindowTrait for State {
             // Close button
             column![button(text(strings.string(Index::Close as usize)))
                 .padding([5, 10])
-                .on_press(application::Message::Close(id))]
+                .on_press(application::Message::Information(id, Message::Close))]
             .width(Length::Fill)
             .align_x(Alignment::Center)
             .into(),
@@ -183,22 +177,19 @@ pub enum InformationType {
     Information, // General purpose
 }
 
+/// Display the information window, which disables only its parent window. With a `ttl` the
+/// window closes automatically after the duration.
 pub fn display(
     application: &mut application::State,
+    parent: window::Id,
     title: String,
     message: String,
     information_type: InformationType,
-    parent: window::Id,
+    ttl: Option<Duration>,
 ) -> Result<Task<application::Message>, ApplicationError> {
-    if !application
+    application
         .string_cache
-        .exists(&StringGroup::Information)
-    {
-        application.string_cache.insert(
-            StringGroup::Information,
-            Box::new(Strings::try_new(&application.localisation)?),
-        );
-    }
+        .try_ensure(StringGroup::Information, &application.localisation)?;
     let strings = application
         .string_cache
         .get(&StringGroup::Information)
@@ -206,16 +197,87 @@ pub fn display(
     let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
     let state = match information_type {
         InformationType::Information => {
-            State::try_information(&application.localisation, title, message, actual)
+            State::try_information(&application.localisation, title, message, actual, ttl)
         }
         InformationType::Warning => {
-            State::try_warning(&application.localisation, title, message, actual)
+            State::try_warning(&application.localisation, title, message, actual, ttl)
         }
         InformationType::Error => {
-            State::try_error(&application.localisation, title, message, actual)
+            State::try_error(&application.localisation, title, message, actual, ttl)
         }
     }?;
     Ok(application
         .manager
         .try_create_window(&mut application.session, Box::new(state), parent)?)
 }
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut tasks = Task::none();
+    match message {
+        // The window may have been closed before it expired.
+        application::Message::Information(id, Message::Close)
+            if application.manager.state(&id).is_some() =>
+        {
+            tasks = close(application, id)?;
+        }
+        _ => {}
+    }
+    Ok(tasks)
+}
+
+/// Close the information window, and return the focus to the parent window.
+pub fn close(
+    application: &mut application::State,
+    id: window::Id,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let Some(parent) = application.manager.parent(&id) else {
+        return Err(CoreError::ExpectedWindowParent(WindowType::Information))?;
+    };
+    Ok(application.manager.close_window(id)?.chain(window::gain_focus(parent)))
+}
+
+/// The expiry timers of the opened information windows having a `ttl`.
+pub fn subscription(application: &application::State) -> Subscription<application::Message> {
+    let mut subscriptions = Vec::<Subscription<application::Message>>::new();
+    for id in application.manager.ids_of_type(WindowType::Information) {
+        let Some(state) = application.manager.state(&id) else {
+            continue;
+        };
+        let actual = state.as_any().downcast_ref::<State>().unwrap();
+        if let Some(ttl) = actual.ttl {
+            subscriptions.push(
+                Subscription::run_with_id(id, timer::after(ttl, id))
+                    .map(|id| application::Message::Information(id, Message::Close)),
+            );
+        }
+    }
+    Subscription::batch(subscriptions)
+}
+
+/// The title, consisting of the localised information type and the window name.
+fn localise(
+    localisation: &Localisation,
+    strings: &Strings,
+    information_type: &InformationType,
+    name: &str,
+) -> Result<RefCount<String>, CoreError> {
+    let index = match information_type {
+        InformationType::Error => Index::Error,
+        InformationType::Warning => Index::Warning,
+        InformationType::Information => Index::Information,
+    };
+    let mut values = HashMap::<String, PlaceholderValue>::new();
+    values.insert(
+        "application".to_string(),
+        PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
+    );
+    values.insert(
+        "type".to_string(),
+        PlaceholderValue::String(strings.string(index as usize).to_string()),
+    );
+    values.insert("window".to_string(), PlaceholderValue::String(name.to_string()));
+    Ok(localisation.format_with_defaults("application", "window_type_title_format", &values)?.0)
+}
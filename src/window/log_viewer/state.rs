@@ -0,0 +1,246 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! Displays the most recent log records of the log buffer, filtered by the selected log level.
+
+#![allow(clippy::single_match)]
+
+use crate::{
+    application::{
+        self,
+        log::{LogBuffer, LogLevel, LogRecord},
+        ApplicationError, StringGroup, WindowType,
+    },
+    core::{
+        error::CoreError,
+        localisation::{Localisation, StringCache},
+        traits::{AnyWindowTrait, WindowTrait},
+    },
+    localisation::{
+        log_viewer::Index,
+        preferences,
+    },
+};
+use iced::{
+    widget::{button, column, combo_box, row, scrollable, text},
+    window, Element, Font, Length, Task,
+};
+use std::any::Any;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    LogLevelSelected(String),
+    Refresh,
+}
+
+pub struct State {
+    log_buffer: LogBuffer,
+    records: Vec<LogRecord>, // Snapshot of the log buffer.
+    list: combo_box::State<String>,
+    selected: LogLevel,
+}
+
+impl State {
+    pub fn new(log_buffer: &LogBuffer, string_cache: &StringCache) -> Self {
+        // The log level list is shared with the Preferences window.
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<preferences::Strings>().unwrap();
+        let mut state = State {
+            log_buffer: LogBuffer::clone(log_buffer),
+            records: Vec::<LogRecord>::new(),
+            list: combo_box::State::new(actual.log_list().to_vec()),
+            selected: LogLevel::Trace,
+        };
+        state.refresh();
+        state
+    }
+
+    /// Take a snapshot of the log buffer.
+    ///
+    /// Note: Logging must not be done while the log buffer is locked.
+    pub fn refresh(&mut self) {
+        if let Ok(buffer) = self.log_buffer.lock() {
+            self.records = buffer.iter().cloned().collect();
+        }
+    }
+}
+
+impl AnyWindowTrait for State {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl WindowTrait for State {
+    fn window_type(&self) -> WindowType {
+        WindowType::LogViewer
+    }
+
+    fn title<'a>(&'a self, string_cache: &'a StringCache) -> &'a String {
+        string_cache.title(&StringGroup::LogViewer)
+    }
+
+    fn try_update(
+        &mut self,
+        message: application::Message,
+        string_cache: &StringCache,
+    ) -> Result<Task<application::Message>, ApplicationError> {
+        match message {
+            application::Message::LogViewer(_id, message) => match message {
+                Message::LogLevelSelected(string) => {
+                    let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+                    let actual = strings.as_any().downcast_ref::<preferences::Strings>().unwrap();
+                    if let Some(log_level) = actual.log_map_to_level(&string) {
+                        self.selected = *log_level;
+                    }
+                    self.refresh();
+                }
+                Message::Refresh => self.refresh(),
+            },
+            _ => {}
+        }
+        Ok(Task::none())
+    }
+
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let align_start = localisation.layout_data().align_words_start;
+        let align_end = localisation.layout_data().align_words_end;
+        let reverse_words = localisation.layout_data().reverse_words;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let strings = string_cache.get(&StringGroup::LogViewer).unwrap();
+        let preferences_strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = preferences_strings
+            .as_any()
+            .downcast_ref::<preferences::Strings>()
+            .unwrap();
+        let mut content: Vec<Element<application::Message>> =
+            Vec::<Element<application::Message>>::new();
+
+        // Log level filter
+        let mut filter: Vec<Element<application::Message>> = vec![
+            text(strings.string(Index::LogLevel as usize)).into(),
+            text(" ").into(),
+            combo_box(
+                &self.list,
+                preferences_strings.string(preferences::Index::LogPlaceholder as usize),
+                actual.log_map_to_string(&self.selected),
+                move |string| {
+                    application::Message::LogViewer(id, Message::LogLevelSelected(string))
+                },
+            )
+            .width(100)
+            .into(),
+        ];
+        if reverse_words {
+            filter.reverse();
+        }
+        content.push(row(filter).into());
+
+        // Body - scrollable
+        let level_filter = self.selected.to_level_filter();
+        let mut body: Vec<Element<application::Message>> = self
+            .records
+            .iter()
+            .filter(|record| record.level <= level_filter)
+            .map(|record| text(record.line.as_str()).font(Font::MONOSPACE).size(12).into())
+            .collect();
+        if reverse_lines {
+            body.reverse();
+        }
+        content.push(
+            scrollable(column(body).width(Length::Fill).align_x(align_start))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        );
+        content.push(" ".into()); // Paragraph separation
+
+        // Buttons
+        let mut buttons: Vec<Element<application::Message>> = vec![
+            button(text(strings.string(Index::Refresh as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::LogViewer(id, Message::Refresh))
+                .into(),
+            button(text(strings.string(Index::Close as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::Close(id))
+                .into(),
+        ];
+        if reverse_words {
+            buttons.reverse();
+        }
+        content.push(
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(align_end)
+                .into(),
+        );
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(2)
+            .into()
+    }
+
+    fn is_singleton(&self) -> bool {
+        true
+    }
+
+    fn try_localise(
+        &mut self,
+        localisation: &Localisation,
+    ) -> Result<(), ApplicationError> {
+        // The localised log level list of the combo box.
+        let strings = preferences::Strings::try_new(localisation)?;
+        self.list = combo_box::State::new(strings.log_list().to_vec());
+        Ok(())
+    }
+}
+
+pub fn display(
+    application: &mut application::State,
+    parent: window::Id,
+) -> Result<Task<application::Message>, ApplicationError> {
+    application
+        .string_cache
+        .try_ensure(StringGroup::LogViewer, &application.localisation)?;
+    application
+        .string_cache
+        .try_ensure(StringGroup::Preferences, &application.localisation)?;
+    let state = State::new(&application.environment.log_buffer, &application.string_cache);
+    Ok(application
+        .manager
+        .try_create_window(&mut application.session, Box::new(state), parent)?)
+}
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut task = Task::none();
+    match message {
+        application::Message::LogViewer(id, _) => {
+            let Some(&mut ref mut state) = application.manager.state_mut(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            task = state.try_update(message, &application.string_cache)?;
+        }
+        _ => {}
+    }
+    Ok(task)
+}
@@ -4,16 +4,17 @@
 
 use crate::{
     application::{StringGroup, WindowType},
-    core::localisation::StringCache,
+    core::localisation::{Localisation, StringCache},
     localisation::{main, main_common},
 };
 use iced::{
     alignment,
     border::Radius,
-    widget::{button, container, text},
+    widget::{button, container, row, text},
     window,
     Border, Color, Element, Length
 };
+use std::path::Path;
 
 #[cfg(not(feature = "iced_aw"))]
 use iced_aw::{
@@ -45,22 +46,81 @@ use log::{debug, error, info, trace, warn};
 pub enum Message {
     None, // Used for the menu bar button, and buttons that open sub menus to the side.
     New(WindowType),
-    //Open(WindowType),
+    Open,
+    OpenReadOnly,
+    SaveAs,
+    Undo,
+    Redo,
+    OpenRecent(String),
     Close(window::Id),
     CloseAll,
     Preferences,
     About,
+    LogViewer,
+    FocusThread(window::Id), // Focus the main window of the window thread.
 }
 
-pub fn view(id: window::Id, string_cache: &StringCache) -> Element<'_, Message> {
+/// The state of the application that determines which menu items are enabled, computed by the
+/// Main window for each view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MenuContext {
+    pub has_unsaved: bool, // The window has unsaved data.
+    pub read_only: bool, // The window's data is opened read-only.
+    pub thread_count: usize, // The number of open window threads.
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+impl MenuContext {
+    /// Save As is only meaningful when there is unsaved data, and never for read-only data.
+    pub fn can_save(&self) -> bool {
+        self.has_unsaved && !self.read_only
+    }
+
+    /// Close All is only meaningful when more than the current window thread is open.
+    pub fn can_close_all(&self) -> bool {
+        self.thread_count > 1
+    }
+}
+
+const SUBMENU_ARROW: char = '›'; // Indicates a sub menu, mirrored for right to left languages.
+
+pub fn view<'a>(
+    id: window::Id,
+    localisation: &Localisation,
+    string_cache: &'a StringCache,
+    recent: &'a [String],
+    documents: &'a [(window::Id, String)],
+    context: MenuContext,
+) -> Element<'a, Message> {
     let main = string_cache.get(&StringGroup::Main).unwrap();
     let common = string_cache.get(&StringGroup::MainCommon).unwrap();
     let menu_type_1 = |items| Menu::new(items).max_width(180.0).offset(15.0).spacing(5.0);
+    let menu_type_2 = |items| Menu::new(items).max_width(180.0).offset(0.0).spacing(5.0);
+    let submenu_arrow = localisation.mirror_icon(SUBMENU_ARROW);
 
-    /*
-    let menu_type_2 =
-    |items| Menu::new(items).max_width(180.0).offset(0.0).spacing(5.0);
-    */
+    // Recent submenu
+    let mut recent_items: Vec<Item<'a, Message, iced::Theme, iced::Renderer>> = recent
+        .iter()
+        .map(|path| Item::new(labeled_button(path.as_str(), Message::OpenRecent(path.clone()))))
+        .collect();
+    if recent_items.is_empty() {
+        recent_items.push(Item::new(labeled_button(
+            common.string(main_common::Index::NoRecent as usize),
+            Message::None,
+        )));
+    }
+
+    // Window menu, listing the file name of the opened documents, the current one is checked.
+    let document_items: Vec<Item<'a, Message, iced::Theme, iced::Renderer>> = documents
+        .iter()
+        .map(|(thread_id, name)| {
+            Item::new(base_button(
+                text(document_label(name, *thread_id == id)).align_y(alignment::Vertical::Center),
+                Some(Message::FocusThread(*thread_id)),
+            ))
+        })
+        .collect();
 
     let bar = menu_bar!(
         // File menu
@@ -71,15 +131,30 @@ pub fn view(id: window::Id, string_cache: &StringCache) -> Element<'_, Message>
                     common.string(main_common::Index::New as usize),
                     Message::New(WindowType::Main)
                 ))
-                /*
+                (labeled_button(common.string(main_common::Index::Open as usize), Message::Open))
                 (labeled_button(
-                    common.string(main_common::Index::Open as usize),
-                    Message::Open(WindowType::Main)
+                    common.string(main_common::Index::OpenReadOnly as usize),
+                    Message::OpenReadOnly
+                ))
+                (
+                    submenu_button(
+                        common.string(main_common::Index::OpenRecent as usize),
+                        submenu_arrow,
+                        localisation.layout_data().reverse_words,
+                    ),
+                    menu_type_2(recent_items)
+                )
+                (separator())
+                (labeled_button_maybe(
+                    main.string(main::Index::SaveAs as usize),
+                    context.can_save().then_some(Message::SaveAs)
                 ))
-                */
                 (separator())
                 (labeled_button(main.string(main::Index::Close as usize), Message::Close(id)))
-                (labeled_button(main.string(main::Index::CloseAll as usize), Message::CloseAll))
+                (labeled_button_maybe(
+                    main.string(main::Index::CloseAll as usize),
+                    context.can_close_all().then_some(Message::CloseAll)
+                ))
             ))
         )
 
@@ -87,14 +162,30 @@ pub fn view(id: window::Id, string_cache: &StringCache) -> Element<'_, Message>
         (
             labeled_button(common.string(main_common::Index::Edit as usize), Message::None),
             menu_type_1(menu_items!(
+                (labeled_button_maybe(
+                    main.string(main::Index::Undo as usize),
+                    context.can_undo.then_some(Message::Undo)
+                ))
+                (labeled_button_maybe(
+                    main.string(main::Index::Redo as usize),
+                    context.can_redo.then_some(Message::Redo)
+                ))
+                (separator())
                 (labeled_button(common.string(main_common::Index::Preferences as usize), Message::Preferences))
             ))
         )
 
+        // Window menu
+        (
+            labeled_button(common.string(main_common::Index::Window as usize), Message::None),
+            menu_type_1(document_items)
+        )
+
         // Help menu
         (
             labeled_button(common.string(main_common::Index::Help as usize), Message::None),
             menu_type_1(menu_items!(
+                (labeled_button(common.string(main_common::Index::LogViewer as usize), Message::LogViewer))
                 (labeled_button(common.string(main_common::Index::About as usize), Message::About))
             ))
         )
@@ -113,17 +204,25 @@ pub fn view(id: window::Id, string_cache: &StringCache) -> Element<'_, Message>
 
 fn base_button<'a>(
     content: impl Into<Element<'a, Message, iced::Theme, iced::Renderer>>,
-    message: Message,
+    message: Option<Message>,
 ) -> button::Button<'a, Message, iced::Theme, iced::Renderer> {
     button(content)
         .padding([4, 8])
         //.style(iced::theme::Button::Custom(Box::new(ButtonStyle {})))
-        .on_press(message)
+        .on_press_maybe(message)
 }
 
 fn labeled_button<'a>(
     label: &'a str,
     message: Message,
+) -> button::Button<'a, Message, iced::Theme, iced::Renderer> {
+    labeled_button_maybe(label, Some(message))
+}
+
+/// Without a message the button is displayed as disabled, and emits nothing when pressed.
+fn labeled_button_maybe<'a>(
+    label: &'a str,
+    message: Option<Message>,
 ) -> button::Button<'a, Message, iced::Theme, iced::Renderer> {
     base_button(
         text(label)
@@ -134,6 +233,26 @@ fn labeled_button<'a>(
     )
 }
 
+/// A button opening a sub menu to the side, displaying the arrow after the label.
+fn submenu_button<'a>(
+    label: &'a str,
+    arrow: char,
+    reverse_words: bool,
+) -> button::Button<'a, Message, iced::Theme, iced::Renderer> {
+    let mut content: Vec<Element<'a, Message>> = vec![
+        text(label).width(Length::Fill).into(),
+        text(arrow.to_string()).into(),
+    ];
+    if reverse_words {
+        content.reverse();
+    }
+    base_button(
+        row(content).align_y(alignment::Vertical::Center),
+        Some(Message::None),
+    )
+    .width(Length::Fill)
+}
+
 fn separator() -> quad::Quad {
     quad::Quad {
         quad_color: Color::from([0.5; 3]).into(),
@@ -146,3 +265,65 @@ fn separator() -> quad::Quad {
         ..Default::default()
     }
 }
+
+/// The label of the opened document in the Window menu, being the file name of the document,
+/// checked when the document is of the current window thread.
+fn document_label(name: &str, checked: bool) -> String {
+    let label = Path::new(name)
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or(name);
+    match checked {
+        true => format!("✓ {}", label),
+        false => format!("   {}", label),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_as_enabled_for_unsaved_writable_data() {
+        let context = MenuContext::default();
+        assert!(!context.can_save());
+        let context = MenuContext {
+            has_unsaved: true,
+            ..MenuContext::default()
+        };
+        assert!(context.can_save());
+        let context = MenuContext {
+            has_unsaved: true,
+            read_only: true,
+            ..MenuContext::default()
+        };
+        assert!(!context.can_save());
+    }
+
+    #[test]
+    fn close_all_enabled_for_several_threads() {
+        for (thread_count, enabled) in [(0, false), (1, false), (2, true), (5, true)] {
+            let context = MenuContext {
+                thread_count,
+                ..MenuContext::default()
+            };
+            assert_eq!(context.can_close_all(), enabled, "{} threads", thread_count);
+        }
+    }
+
+    #[test]
+    fn document_labels_check_current_thread() {
+        let current = window::Id::unique();
+        let other = window::Id::unique();
+        let documents = [
+            (other, "/home/user/a.txt".to_string()),
+            (current, "/home/user/b.txt".to_string()),
+            (window::Id::unique(), "unnamed".to_string()),
+        ];
+        let labels = documents
+            .iter()
+            .map(|(thread_id, name)| document_label(name, *thread_id == current))
+            .collect::<Vec<String>>();
+        assert_eq!(labels, vec!["   a.txt", "✓ b.txt", "   unnamed"]);
+    }
+}
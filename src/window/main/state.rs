@@ -1,27 +1,41 @@
 // This file is part of `iced_af` crate. For the terms of use, please see the file
 // called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
 
+#![allow(clippy::single_match)]
 
 use crate::{
-    application::{self, ApplicationError, constants::APPLICATION_NAME_SHORT, StringGroup, WindowType},
+    application::{
+        self, ApplicationError, constants::{APPLICATION_NAME_SHORT, UNDO_DEPTH, UNSAVED_MARKER}, StringGroup,
+        session::{modified_time, DocumentMode}, toast::ToastLevel, WindowType,
+    },
     core::{
         error::CoreError,
         localisation::{Localisation, StringCache},
-        traits::{AnyWindowTrait, SaveDataTrait, WindowTrait},
+        traits::{AnyWindowTrait, SaveDataTrait, UndoableTrait, WindowTrait},
+        undo::UndoStack,
     },
     localisation,
-    window::{about, main::menu_bar, preferences, unsaved_data},
+    window::{about, log_viewer, main::menu_bar, preferences, unsaved_data},
 };
 use i18n::utility::PlaceholderValue;
 use iced::{
-    widget::{button, column, row, text},
-    window, Alignment, Task, Element, Length,
+    widget::{button, column, container, row, text},
+    window, Alignment, Border, Task, Element, Length, Theme,
 };
 use std::{
     any::Any,
     collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
 use chrono::prelude::*;
+use icu_datetime::options::length;
+
+#[cfg(feature = "iced_aw")]
+use crate::iced_aw::widgets::sidebar::{self, SidebarWithContent, TabLabel};
+
+#[cfg(not(feature = "iced_aw"))]
+use iced_aw::widgets::sidebar::{self, SidebarWithContent, TabLabel};
 
 #[cfg(not(feature = "sync"))]
 use std::rc::Rc as RefCount;
@@ -30,36 +44,212 @@ use std::rc::Rc as RefCount;
 #[cfg(target_has_atomic = "ptr")]
 use std::sync::Arc as RefCount;
 
+#[cfg(any(test, feature = "debug_snapshot"))]
+use crate::core::traits::WindowSnapshot;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
 #[derive(Debug, Clone)]
 pub enum Message {
     MenuBar(menu_bar::Message),
+    TabSelected(usize), // Index into the opened documents.
+    TabClosed(usize),   // Index into the opened documents.
 
     // temp
     Toggle,
 }
 
-pub struct State {
-    unsaved: bool,
+/// The changes recorded in the undo stack.
+#[derive(Debug)]
+enum Change {
+    Toggle, // Reverting is toggling again.
+}
+
+/// An opened document, which is a tab of the Main window in the `DocumentMode::Tabs` mode.
+struct Document {
+    toggled: bool, // temp: the data of the document.
+    history: UndoStack<Change>,
     path: String, // Change to PathBuf in actual program using files.
-    title: RefCount<String>,
+    created: Option<NaiveDateTime>, // Creation time of an unnamed document, displayed in the title.
+    read_only: bool, // The data can't be edited, thus never unsaved.
+    localised_title: RefCount<String>, // The title without the unsaved marker.
+    label: RefCount<String>, // The tab label, being the file name or the creation time.
+    modified: Option<SystemTime>, // The modification time of the file when opened or saved.
+}
+
+impl Document {
+    fn try_new(
+        localisation: &Localisation,
+        path: String,
+        created: Option<NaiveDateTime>,
+        read_only: bool,
+    ) -> Result<Document, ApplicationError> {
+        let mut strings = localise(localisation, path.clone(), created.as_ref(), read_only)?;
+        let label = strings.pop().unwrap();
+        let localised_title = strings.pop().unwrap();
+        let modified = modified_time(Path::new(path.as_str()));
+        Ok(Document {
+            toggled: false,
+            history: UndoStack::new(UNDO_DEPTH),
+            path,
+            created,
+            read_only,
+            localised_title,
+            label,
+            modified,
+        })
+    }
+
+    fn try_localise(&mut self, localisation: &Localisation) -> Result<(), ApplicationError> {
+        let mut strings =
+            localise(localisation, self.path.clone(), self.created.as_ref(), self.read_only)?;
+        self.label = strings.pop().unwrap();
+        self.localised_title = strings.pop().unwrap();
+        Ok(())
+    }
+
+    /// Read-only data is never unsaved, thus closing never displays the unsaved data dialogue.
+    fn is_unsaved(&self) -> bool {
+        !self.read_only && self.history.is_unsaved()
+    }
+
+    fn try_save(&mut self) -> Result<(), ApplicationError> {
+        println!("Saving data");
+        self.history.mark_saved();
+        self.modified = modified_time(Path::new(self.path.as_str()));
+        Ok(())
+    }
+
+    /// Indicates whether the file has been modified by another application since it was opened or
+    /// saved, and records the new modification time.
+    fn check_modified(&mut self) -> bool {
+        let modified = modified_time(Path::new(self.path.as_str()));
+        let changed = matches!((self.modified, modified), (Some(old), Some(new)) if new > old);
+        if changed {
+            self.modified = modified;
+        }
+        changed
+    }
+}
+
+pub struct State {
+    tabs: Vec<Document>, // The opened documents, at least one.
+    active: usize, // Index into `tabs` of the displayed document.
+    title: String, // The displayed title, see `update_title()`.
+    recent: Vec<String>, // Copy of the session's recent list for the menu bar.
+    file_hovered: bool, // Files are being dragged over the window.
+    thread_count: usize, // Copy of the manager's thread count for the menu bar.
+    documents: Vec<(window::Id, String)>, // Copy of the manager's thread names for the menu bar.
 }
 
 impl State {
     pub fn try_new(
         localisation: &Localisation,
+        path: String,
+        created: Option<NaiveDateTime>,
+        read_only: bool,
+        recent: Vec<String>,
     ) -> Result<State, ApplicationError> {
-        let local: DateTime<Local> = Local::now();
-        let name = local.format("%s").to_string();
-        let title = localise(localisation, name.clone())?.pop().unwrap();
+        let document = Document::try_new(localisation, path, created, read_only)?;
+        let title = document.localised_title.to_string();
         Ok(State {
-            unsaved: false,
-            path: name,
+            tabs: vec![document],
+            active: 0,
             title,
+            recent,
+            file_hovered: false,
+            thread_count: 0,
+            documents: Vec::<(window::Id, String)>::new(),
         })
     }
+
+    /// The displayed document.
+    fn document(&self) -> &Document {
+        &self.tabs[self.active]
+    }
+
+    /// The displayed document.
+    fn document_mut(&mut self) -> &mut Document {
+        &mut self.tabs[self.active]
+    }
+
+    /// Add the document as a new tab, and display it.
+    fn add_tab(&mut self, document: Document) {
+        self.tabs.push(document);
+        self.active = self.tabs.len() - 1;
+        self.update_title();
+    }
+
+    /// Indicates whether a document has the specified path opened.
+    pub fn has_path(&self, path: &str) -> bool {
+        self.tabs.iter().any(|document| document.path == path)
+    }
+
+    /// Display the document having the specified path, if opened.
+    pub fn select_path(&mut self, path: &str) {
+        if let Some(index) = self.tabs.iter().position(|document| document.path == path) {
+            self.active = index;
+            self.update_title();
+        }
+    }
+
+    /// The paths of all the opened documents, in tab order.
+    pub fn paths(&self) -> Vec<String> {
+        self.tabs.iter().map(|document| document.path.clone()).collect()
+    }
+
+    /// Compose the displayed title of the displayed document, prepending `UNSAVED_MARKER` when
+    /// it has unsaved data. Called whenever the displayed document, its localised title, or its
+    /// unsaved state changes.
+    fn update_title(&mut self) {
+        let document = self.document();
+        self.title = title_with_marker(document.localised_title.as_str(), document.is_unsaved());
+    }
+
+    /// The content of the document of the specified tab.
+    fn document_view<'a>(
+        &'a self,
+        index: usize,
+        id: window::Id,
+        reverse_lines: bool,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let common = string_cache.get(&StringGroup::MainCommon).unwrap();
+        let document = &self.tabs[index];
+        let unsaved = if document.is_unsaved() {
+            "Unsaved data."
+        } else {
+            "All data saved."
+        };
+        let mut content: Vec<Element<application::Message>> = vec![
+            text("Test message - Temporary.").into(),
+            column![row![
+                text("Button to the right toggles unsaved data."),
+                button(text(common.string(localisation::main_common::Index::Help as usize)))
+                .padding([5, 10])
+                .on_press_maybe(
+                    (!document.read_only).then_some(application::Message::Main(id, Message::Toggle))
+                ),
+                text(unsaved)
+            ]]
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+            .into(),
+        ];
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content).width(Length::Fill).into()
+    }
+}
+
+/// The title, with `UNSAVED_MARKER` prepended when `unsaved`.
+fn title_with_marker(title: &str, unsaved: bool) -> String {
+    match unsaved {
+        true => format!("{}{}", UNSAVED_MARKER, title),
+        false => title.to_string(),
+    }
 }
 
 impl AnyWindowTrait for State {
@@ -81,6 +271,17 @@ impl WindowTrait for State {
         &self.title
     }
 
+    #[cfg(any(test, feature = "debug_snapshot"))]
+    fn debug_snapshot(&self, _string_cache: &StringCache) -> WindowSnapshot {
+        WindowSnapshot {
+            window_type: WindowType::Main,
+            enabled: true,
+            title: self.title.clone(),
+            is_unsaved: self.is_unsaved(),
+            is_first_use: false,
+        }
+    }
+
     fn try_update(
         &mut self,
         message: application::Message,
@@ -90,7 +291,17 @@ impl WindowTrait for State {
         match message {
             application::Message::Main(_id, ref main_message) => {
                 match main_message {
-                    Message::Toggle => self.unsaved = !self.unsaved,
+                    // Editing is ignored for read-only data.
+                    Message::Toggle if !self.document().read_only => {
+                        let document = self.document_mut();
+                        document.toggled = !document.toggled;
+                        document.history.push(Change::Toggle);
+                        self.update_title();
+                    }
+                    Message::TabSelected(index) if *index < self.tabs.len() => {
+                        self.active = *index;
+                        self.update_title();
+                    }
                     _ => {}
                 }
             }
@@ -106,99 +317,239 @@ impl WindowTrait for State {
         string_cache: &'a StringCache,
     ) -> Element<application::Message> {
         let reverse_lines = localisation.layout_data().reverse_lines;
-        let common = string_cache.get(&StringGroup::MainCommon).unwrap();
-        //let common_actual = common.as_any().downcast_ref::<main_common::Strings>().unwrap();
-        //let strings = string_cache.get(&StringGroup::Main).unwrap();
-        //let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
         let mut content: Vec<Element<application::Message>> =
             Vec::<Element<application::Message>>::new();
 
         // Menubar
+        let context = menu_bar::MenuContext {
+            has_unsaved: self.document().is_unsaved(),
+            read_only: self.document().read_only,
+            thread_count: self.thread_count,
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+        };
         content.push(
-            menu_bar::view(id, string_cache).map(move |message: menu_bar::Message| {
+            menu_bar::view(id, localisation, string_cache, &self.recent, &self.documents, context).map(move |message: menu_bar::Message| {
                 application::Message::Main(id, Message::MenuBar(message))
             }),
         );
 
-        // Content
-        let unsaved = if self.unsaved {
-            "Unsaved data."
+        // Content, with a tab for each document when there are several documents.
+        if self.tabs.len() == 1 {
+            content.push(self.document_view(0, id, reverse_lines, string_cache));
         } else {
-            "All data saved."
-        };
-        content.push(text("Test message - Temporary.").into());
-        content.push(
-            column![row![
-                text("Button to the right toggles unsaved data."),
-                button(text(common.string(localisation::main_common::Index::Help as usize)))
-                .padding([5, 10])
-                .on_press(application::Message::Main(id, Message::Toggle)),
-                text(unsaved)
-            ]]
-            .width(Length::Fill)
-            .align_x(Alignment::Center)
-            .into(),
-        );
+            let tabs = self
+                .tabs
+                .iter()
+                .enumerate()
+                .map(|(index, document)| {
+                    (
+                        index,
+                        TabLabel::Text(document.label.to_string()),
+                        self.document_view(index, id, reverse_lines, string_cache),
+                    )
+                })
+                .collect::<Vec<(usize, TabLabel, Element<application::Message>)>>();
+            content.push(
+                SidebarWithContent::new_with_tabs(tabs, move |index| {
+                    application::Message::Main(id, Message::TabSelected(index))
+                })
+                .set_active_tab(&self.active)
+                .sidebar_position(sidebar::SidebarPosition::Start)
+                .align_tabs(Alignment::Start)
+                .on_close(move |index| application::Message::Main(id, Message::TabClosed(index)))
+                .tab_label_padding(0.0)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+            );
+        }
         if reverse_lines {
             content.reverse();
         }
-        column(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(2)
+        let file_hovered = self.file_hovered;
+        container(column(content).width(Length::Fill).height(Length::Fill).padding(2))
+            .style(move |theme: &Theme| container::Style {
+                // Highlight the window while files are dragged over it.
+                border: Border {
+                    color: theme.palette().primary,
+                    width: if file_hovered { 2.0 } else { 0.0 },
+                    radius: 0.0.into(),
+                },
+                ..container::Style::default()
+            })
             .into()
     }
 
+    fn set_file_hovered(&mut self, hovered: bool) {
+        self.file_hovered = hovered;
+    }
+
+    /// Report the documents whose files have been modified by another application, as the user may
+    /// wish to reopen them.
+    fn on_focus(&mut self) -> Result<(), ApplicationError> {
+        let changed = self
+            .tabs
+            .iter_mut()
+            .filter_map(|document| document.check_modified().then(|| document.path.clone()))
+            .collect::<Vec<String>>();
+        if changed.is_empty() {
+            return Ok(());
+        }
+        Err(ApplicationError::ModifiedExternally(changed.join(", ")))
+    }
+
     fn try_localise(
         &mut self,
         localisation: &Localisation,
     ) -> Result<(), ApplicationError> {
         println!("updating localised strings for Main");
-        self.title = localise(localisation, self.path.clone())?.pop().unwrap();
+        for document in self.tabs.iter_mut() {
+            document.try_localise(localisation)?;
+        }
+        self.update_title();
         Ok(())
     }
 }
 
+/// The data of all the documents is saved together, thus the window has unsaved data when any
+/// document has unsaved data. The name is of the displayed document.
 impl SaveDataTrait for State {
     fn try_save(&mut self) -> Result<(), ApplicationError> {
-        println!("Saving data");
-        self.unsaved = false;
+        for document in self.tabs.iter_mut().filter(|document| document.is_unsaved()) {
+            document.try_save()?;
+        }
+        self.update_title();
         Ok(())
     }
 
     fn is_unsaved(&self) -> bool {
-        self.unsaved
+        self.tabs.iter().any(|document| document.is_unsaved())
     }
 
     fn name(&self) -> &str {
-        self.path.as_str()
+        self.document().path.as_str()
+    }
+}
+
+/// The undo history is of the displayed document.
+impl UndoableTrait for State {
+    fn undo(&mut self) {
+        let document = self.document_mut();
+        if let Some(change) = document.history.undo() {
+            match change {
+                Change::Toggle => document.toggled = !document.toggled,
+            }
+            self.update_title();
+        }
+    }
+
+    fn redo(&mut self) {
+        let document = self.document_mut();
+        if let Some(change) = document.history.redo() {
+            match change {
+                Change::Toggle => document.toggled = !document.toggled,
+            }
+            self.update_title();
+        }
+    }
+
+    fn can_undo(&self) -> bool {
+        self.document().history.can_undo()
+    }
+
+    fn can_redo(&self) -> bool {
+        self.document().history.can_redo()
+    }
+}
+
+/// Inform the user that the data is already opened, and its window is focused.
+fn already_opened(application: &mut application::State, name: &str) {
+    let mut values = HashMap::<String, PlaceholderValue>::new();
+    values.insert("name".to_string(), PlaceholderValue::String(name.to_string()));
+    match application
+        .localisation
+        .format_with_defaults("application", "already_opened", &values)
+    {
+        Ok(value) => application.push_toast_message(value.0, ToastLevel::Information),
+        Err(error) => application.push_toast(&error.into(), ToastLevel::Warning),
     }
 }
 
 /// Returned result is tuple `(Task, bool)`, where `bool` of `True` indicates
 /// display() succeeded in creating the main window, `False` indicates failure
 /// with an information window displayed instead.
+///
+/// Without a path, a new name is created for the main window, and the title displays the
+/// localised creation time instead. Once opened, the path is added to the recent list, which
+/// excludes the new names. A path already opened
+/// in a main window, see `find_path()`, focuses that main window instead, and informs the user. A
+/// thread claiming the path without a main window having the path opened is a distinct attempt to
+/// open the same database, displaying `DatabaseAlreadyOpen` as a toast instead. Read-only data
+/// can't be edited nor saved, and is never unsaved.
+///
+/// In the `DocumentMode::Tabs` mode, the data is opened as a new tab of an opened main window, see
+/// `tab_target()`, and a new window thread is only created when no main window is opened.
 pub fn display(
     application: &mut application::State,
+    path: Option<String>,
+    read_only: bool,
 ) -> Result<(Task<application::Message>, bool), ApplicationError> {
-    let state: Box<dyn AnyWindowTrait> = Box::new(State::try_new(&application.localisation)?);
-    if !application.string_cache.exists(&StringGroup::Main) {
-        application.string_cache.insert(
-            StringGroup::Main,
-            Box::new(
-                localisation::main::Strings::try_new(&application.localisation)?
-            ),
-        );
+    let local: DateTime<Local> = Local::now();
+    let created = path.is_none().then(|| local.naive_local());
+    let path = path.unwrap_or_else(|| local.format("%s").to_string());
+    if created.is_none() {
+        if let Some(id) = find_path(application, path.as_str()) {
+            debug!("‘{}’ is already opened, focusing {:?}", path, id);
+            add_recent(application, path.as_str())?;
+            already_opened(application, path.as_str());
+            return Ok((focus_path(application, id, path.as_str()), true));
+        }
+        if application.manager.thread_named(path.as_str()).is_some() {
+            application.push_toast(&ApplicationError::DatabaseAlreadyOpen, ToastLevel::Error);
+            return Ok((Task::none(), false));
+        }
     }
-    if !application.string_cache.exists(&StringGroup::MainCommon) {
-        application.string_cache.insert(
-            StringGroup::MainCommon,
-            Box::new(
-                localisation::main_common::Strings::try_new(&application.localisation)?
-            ),
-        );
+    if application.session.settings.document_mode == DocumentMode::Tabs {
+        if let Some(id) = tab_target(application) {
+            debug!("Opening ‘{}’ as a tab of {:?}", path, id);
+            let name = path.clone();
+            let document = Document::try_new(&application.localisation, path, created, read_only)?;
+            let Some(state) = application.manager.state_mut(&id) else {
+                return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+            };
+            let actual = state.as_any_mut().downcast_mut::<State>().unwrap();
+            actual.add_tab(document);
+            if created.is_none() {
+                add_recent(application, name.as_str())?;
+            }
+            return Ok((window::gain_focus(id), true));
+        }
+    }
+    let name = path.clone();
+    let state: Box<dyn AnyWindowTrait> = Box::new(State::try_new(
+        &application.localisation,
+        path,
+        created,
+        read_only,
+        application.session.settings.recent.clone(),
+    )?);
+    application
+        .string_cache
+        .try_ensure(StringGroup::Main, &application.localisation)?;
+    application
+        .string_cache
+        .try_ensure(StringGroup::MainCommon, &application.localisation)?;
+    let tasks = application.manager.try_create_named_thread(
+        &mut application.session,
+        state,
+        Some(name.clone()),
+    )?;
+    if created.is_none() {
+        add_recent(application, name.as_str())?;
     }
-    Ok((application.manager.try_create_thread(&mut application.session, state)?, true))
+    update_threads(application)?;
+    Ok((tasks, true))
 }
 
 pub fn try_update(
@@ -217,13 +568,34 @@ pub fn try_update(
             match main_message {
                 Message::MenuBar(menubar_message) => match menubar_message {
                     menu_bar::Message::None => {} // No action.
-                    menu_bar::Message::New(window_type) => tasks = application.open_thread(window_type.clone())?,
-                    //menu_bar::Message::Open(window_type) => tasks = application.open_thread(window_type.clone())?,
+                    menu_bar::Message::New(window_type) => {
+                        tasks = application.open_thread(window_type.clone(), None, false)?
+                    }
+                    menu_bar::Message::Open => {
+                        tasks = Task::done(application::Message::OpenFileDialog(id, false))
+                    }
+                    menu_bar::Message::OpenReadOnly => {
+                        tasks = Task::done(application::Message::OpenFileDialog(id, true))
+                    }
+                    menu_bar::Message::SaveAs => tasks = Task::done(application::Message::SaveFileDialog(id)),
+                    menu_bar::Message::Undo => tasks = Task::done(application::Message::Undo(id)),
+                    menu_bar::Message::Redo => tasks = Task::done(application::Message::Redo(id)),
+                    menu_bar::Message::OpenRecent(path) => {
+                        tasks = application.open_thread(WindowType::Main, Some(path.clone()), false)?
+                    }
                     menu_bar::Message::Close(id) => tasks = try_to_close(application, *id)?,
                     menu_bar::Message::CloseAll => tasks = application.close_all()?,
                     menu_bar::Message::Preferences => tasks = preferences::display(application, id)?,
                     menu_bar::Message::About => tasks = about::display(application, id)?,
+                    menu_bar::Message::LogViewer => tasks = log_viewer::display(application, id)?,
+                    menu_bar::Message::FocusThread(thread_id) => {
+                        // The thread may have been closed since the menu was displayed.
+                        if application.manager.state(thread_id).is_some() {
+                            tasks = window::gain_focus(*thread_id);
+                        }
+                    }
                 },
+                Message::TabClosed(index) => tasks = try_close_tab(application, id, *index)?,
                 _ => {}
             };
         },
@@ -232,10 +604,163 @@ pub fn try_update(
     Ok(tasks)
 }
 
+/// Obtain the main window that has the path opened in any of its tabs.
+pub fn find_path(application: &application::State, path: &str) -> Option<window::Id> {
+    application.manager.ids_of_type(WindowType::Main).into_iter().find(|id| {
+        application.manager.state(id).is_some_and(|state| {
+            state.as_any().downcast_ref::<State>().is_some_and(|actual| actual.has_path(path))
+        })
+    })
+}
+
+/// Display the tab having the path opened in the main window, and focus the main window.
+pub fn focus_path(
+    application: &mut application::State,
+    id: window::Id,
+    path: &str,
+) -> Task<application::Message> {
+    if let Some(state) = application.manager.state_mut(&id) {
+        if let Some(actual) = state.as_any_mut().downcast_mut::<State>() {
+            actual.select_path(path);
+        }
+    }
+    window::gain_focus(id)
+}
+
+/// The main window to open the data in as a tab, for the `DocumentMode::Tabs` mode, being the
+/// focused main window, else the first opened main window. `None` when no main window is opened.
+fn tab_target(application: &application::State) -> Option<window::Id> {
+    let opened = application.manager.ids_of_type(WindowType::Main);
+    match application.focused() {
+        Some(focused) if opened.contains(&focused) => Some(focused),
+        _ => opened.first().copied(),
+    }
+}
+
+/// Close the tab of the document. Closing the only tab closes the window, see `try_to_close()`,
+/// while a document having unsaved data is displayed instead of being closed, informing the user.
+/// The window thread is named after the document of the first tab.
+fn try_close_tab(
+    application: &mut application::State,
+    id: window::Id,
+    index: usize,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let Some(state) = application.manager.state_mut(&id) else {
+        return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+    };
+    let actual = state.as_any_mut().downcast_mut::<State>().unwrap();
+    if actual.tabs.len() == 1 {
+        return try_to_close(application, id);
+    }
+    let Some(document) = actual.tabs.get(index) else {
+        return Ok(Task::none());
+    };
+    if document.is_unsaved() {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert("name".to_string(), PlaceholderValue::String(document.path.clone()));
+        actual.active = index;
+        actual.update_title();
+        match application
+            .localisation
+            .format_with_defaults("application", "tab_unsaved_data", &values)
+        {
+            Ok(value) => application.push_toast_message(value.0, ToastLevel::Warning),
+            Err(error) => application.push_toast(&error.into(), ToastLevel::Warning),
+        }
+        return Ok(Task::none());
+    }
+    actual.tabs.remove(index);
+    if actual.active > index || actual.active == actual.tabs.len() {
+        actual.active -= 1;
+    }
+    actual.update_title();
+    let name = actual.tabs[0].path.clone();
+    application.manager.set_thread_name(&id, Some(name));
+    update_threads(application)?;
+    Ok(Task::none())
+}
+
+/// Save the data of the displayed document to the path selected in the file save dialogue, and
+/// use the path as the document's name.
+pub fn try_save_as(
+    application: &mut application::State,
+    id: window::Id,
+    path: PathBuf,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let path = path.to_string_lossy().to_string();
+    let Some(state) = application.manager.state_mut(&id) else {
+        return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+    };
+    let actual = state.as_any_mut().downcast_mut::<State>().unwrap();
+    let document = actual.document_mut();
+    document.path = path.clone();
+    document.created = None;
+    document.try_localise(&application.localisation)?;
+    document.try_save()?;
+    actual.update_title();
+    let name = actual.tabs[0].path.clone();
+    application.manager.set_thread_name(&id, Some(name));
+    application.session.push_recent(path.as_str());
+    update_recent(application)?;
+    update_threads(application)?;
+    Ok(Task::none())
+}
+
+/// Update the recent list of all the Main windows, after the session's recent list has changed.
+/// Add the opened path to the recent list, and update the Open recent menus.
+fn add_recent(application: &mut application::State, path: &str) -> Result<(), ApplicationError> {
+    application.session.push_recent(path);
+    update_recent(application)
+}
+
+fn update_recent(application: &mut application::State) -> Result<(), ApplicationError> {
+    let recent = &application.session.settings.recent;
+    application.manager.for_each_state_mut(|_, state| {
+        if let Some(actual) = state.as_any_mut().downcast_mut::<State>() {
+            actual.recent = recent.clone();
+        }
+        Ok(())
+    })
+}
+
+/// Update the thread count and the opened documents of all the Main windows, after a window thread
+/// has been opened, closed, or renamed.
+pub fn update_threads(application: &mut application::State) -> Result<(), ApplicationError> {
+    let thread_count = application.manager.thread_list().len();
+    let documents = application.manager.thread_names();
+    application.manager.for_each_state_mut(|_, state| {
+        if let Some(actual) = state.as_any_mut().downcast_mut::<State>() {
+            actual.thread_count = thread_count;
+            actual.documents = documents.clone();
+        }
+        Ok(())
+    })
+}
+
+/// The title uses the localised creation time for an unnamed document, else the name, followed by
+/// the localised read-only suffix for read-only data. The tab label is the localised creation
+/// time, else the file name.
 fn localise(
     localisation: &Localisation,
     name: String,
+    created: Option<&NaiveDateTime>,
+    read_only: bool,
 ) -> Result<Vec<RefCount<String>>, CoreError> {
+    let name = match created {
+        Some(created) => localisation.format_datetime(
+            created,
+            length::Bag::from_date_time_style(length::Date::Medium, length::Time::Medium),
+        )?,
+        None => name,
+    };
+    let label = match created {
+        Some(_) => RefCount::new(name.clone()),
+        None => RefCount::new(
+            Path::new(name.as_str())
+                .file_name()
+                .map_or(name.clone(), |file_name| file_name.to_string_lossy().to_string()),
+        ),
+    };
     let title = {
         let mut values = HashMap::<String, PlaceholderValue>::new();
         values.insert(
@@ -253,7 +778,13 @@ fn localise(
         );
         localisation.format_with_defaults("application", "window_title_name_format", &values)?
     }.0;
-    Ok(vec![title])
+    if !read_only {
+        return Ok(vec![title, label]);
+    }
+    let mut values = HashMap::<String, PlaceholderValue>::new();
+    values.insert("title".to_string(), PlaceholderValue::String(title.to_string()));
+    let title = localisation.format_with_defaults("application", "read_only_title_format", &values)?.0;
+    Ok(vec![title, label])
 }
 
 pub fn try_to_close(
@@ -269,3 +800,101 @@ pub fn try_to_close(
     }
     Ok(application.close_thread(id)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::localisation;
+
+    fn toggle(state: &mut State, string_cache: &StringCache) {
+        let message = application::Message::Main(window::Id::unique(), Message::Toggle);
+        let _ = state.try_update(message, string_cache).unwrap();
+    }
+
+    #[test]
+    fn title_marker_follows_unsaved_flag() {
+        let localisation = localisation("en-ZA");
+        let string_cache = StringCache::new();
+        let mut state =
+            State::try_new(&localisation, "document.txt".to_string(), None, false, vec![])
+                .unwrap();
+        let saved = state.title(&string_cache).clone();
+        assert!(!saved.starts_with(UNSAVED_MARKER));
+        let unsaved = format!("{}{}", UNSAVED_MARKER, saved);
+
+        toggle(&mut state, &string_cache);
+        assert_eq!(state.title(&string_cache), &unsaved);
+        state.undo();
+        assert_eq!(state.title(&string_cache), &saved);
+        state.redo();
+        assert_eq!(state.title(&string_cache), &unsaved);
+
+        // The marker is kept when the title is localised again, and stripped when saved.
+        state.try_localise(&localisation).unwrap();
+        assert_eq!(state.title(&string_cache), &unsaved);
+        state.try_save().unwrap();
+        assert_eq!(state.title(&string_cache), &saved);
+    }
+
+    #[test]
+    fn read_only_title_has_suffix_without_marker() {
+        let localisation = localisation("en-ZA");
+        let string_cache = StringCache::new();
+        let mut state =
+            State::try_new(&localisation, "document.txt".to_string(), None, true, vec![])
+                .unwrap();
+        let title = state.title(&string_cache).clone();
+        let writable =
+            State::try_new(&localisation, "document.txt".to_string(), None, false, vec![])
+                .unwrap();
+        assert_ne!(&title, writable.title(&string_cache));
+        toggle(&mut state, &string_cache);
+        assert_eq!(state.title(&string_cache), &title);
+        assert!(!state.is_unsaved());
+    }
+
+
+    /// The paths opened in each main window.
+    fn opened(application: &application::State) -> Vec<Vec<String>> {
+        application
+            .manager
+            .ids_of_type(WindowType::Main)
+            .iter()
+            .map(|id| {
+                let state = application.manager.state(id).unwrap();
+                state.as_any().downcast_ref::<State>().unwrap().paths()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn document_mode_routes_opening() {
+        let mut application = application::State::new_for_test();
+        let _ = application.try_update(application::Message::Initialise).unwrap();
+        let open = |application: &mut application::State, path: &str| {
+            let _ = display(application, Some(path.to_string()), false).unwrap();
+        };
+
+        // Each document in its own window.
+        assert_eq!(application.session.settings.document_mode, DocumentMode::Windows);
+        open(&mut application, "a.txt");
+        open(&mut application, "b.txt");
+        assert_eq!(opened(&application), vec![vec!["a.txt"], vec!["b.txt"]]);
+
+        // The documents are added as tabs of the focused main window, else the first main window.
+        application.session.settings.document_mode = DocumentMode::Tabs;
+        open(&mut application, "c.txt");
+        assert_eq!(opened(&application), vec![vec!["a.txt", "c.txt"], vec!["b.txt"]]);
+        let second = application.manager.ids_of_type(WindowType::Main)[1];
+        let _ = application.try_update(application::Message::Focused(second)).unwrap();
+        open(&mut application, "d.txt");
+        assert_eq!(opened(&application), vec![vec!["a.txt", "c.txt"], vec!["b.txt", "d.txt"]]);
+
+        // Without an opened main window, a window is opened.
+        let mut application = application::State::new_for_test();
+        let _ = application.try_update(application::Message::Initialise).unwrap();
+        application.session.settings.document_mode = DocumentMode::Tabs;
+        open(&mut application, "a.txt");
+        assert_eq!(opened(&application), vec![vec!["a.txt"]]);
+    }
+}
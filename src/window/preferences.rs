@@ -3,5 +3,7 @@
 
 pub mod state;
 pub use state::*;
+pub mod backup;
 pub mod language;
 pub mod logs;
+pub mod theme;
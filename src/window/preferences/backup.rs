@@ -0,0 +1,161 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The Backup tab, for exporting the settings to a file, importing them from a file, and restoring
+//! the default settings. An imported file or the restored defaults replace the settings
+//! immediately, and close the Preferences window.
+
+use crate::{
+    application::{self, session::Setting, StringGroup},
+    core::{
+        localisation::{Localisation, StringCache},
+        traits::TabTrait,
+    },
+    localisation::preferences::Index,
+    window::preferences::{self, PreferenceTab, TabId, TabMessage},
+};
+use iced::{
+    widget::{button, checkbox, column, row, text},
+    window, Element, Length,
+};
+use std::any::Any;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+pub const TAB_ID: TabId = "backup";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Export,
+    Import,
+    IncludeWindows(bool),
+    RestoreDefaults,
+}
+
+pub struct Tab {
+    include_windows: bool, // Also restore the window geometries to their defaults.
+}
+
+impl Tab {
+    pub fn new() -> Self {
+        Tab {
+            include_windows: false,
+        }
+    }
+
+    pub fn include_windows(&self) -> bool {
+        self.include_windows
+    }
+}
+
+impl Default for Tab {
+    fn default() -> Self {
+        Tab::new()
+    }
+}
+
+impl PreferenceTab for Tab {
+    fn tab_id(&self) -> TabId {
+        TAB_ID
+    }
+
+    // The file dialogues and the confirm dialogue are displayed by `preferences::try_update()`.
+    fn selected(&mut self, message: &TabMessage, _string_cache: &StringCache) {
+        if let Some(Message::IncludeWindows(include_windows)) = message.downcast_ref::<Message>() {
+            self.include_windows = *include_windows;
+        }
+    }
+
+    // Nothing to accept, as an imported file or the restored defaults are applied immediately.
+    fn check_change(&self, _string_cache: &StringCache, _changed_settings: &mut Vec<Setting>) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TabTrait for Tab {
+    fn title(&self, string_cache: &StringCache) -> String {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        String::from(strings.string(Index::Backup as usize))
+    }
+
+    fn searchable_labels(&self, string_cache: &StringCache) -> Vec<String> {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        vec![
+            String::from(strings.string(Index::Backup as usize)),
+            String::from(strings.string(Index::BackupUi as usize)),
+            String::from(strings.string(Index::ExportSettings as usize)),
+            String::from(strings.string(Index::ImportSettings as usize)),
+            String::from(strings.string(Index::IncludeWindows as usize)),
+            String::from(strings.string(Index::RestoreDefaults as usize)),
+        ]
+    }
+
+    fn content<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let reverse_words = localisation.layout_data().reverse_words;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let mut setting: Vec<Element<application::Message>> = vec![
+            text(strings.string(Index::BackupUi as usize)).into(),
+            text("").width(Length::Fill).into(),
+            button(text(strings.string(Index::ExportSettings as usize)))
+                .padding([5, 10])
+                .on_press(preferences::tab_message(
+                    id,
+                    TAB_ID,
+                    Message::Export,
+                ))
+                .into(),
+            button(text(strings.string(Index::ImportSettings as usize)))
+                .padding([5, 10])
+                .on_press(preferences::tab_message(
+                    id,
+                    TAB_ID,
+                    Message::Import,
+                ))
+                .into(),
+        ];
+        let mut defaults: Vec<Element<application::Message>> = vec![
+            checkbox(strings.string(Index::IncludeWindows as usize), self.include_windows)
+                .on_toggle(move |include_windows| {
+                    preferences::tab_message(
+                        id,
+                        TAB_ID,
+                        Message::IncludeWindows(include_windows),
+                    )
+                })
+                .into(),
+            text("").width(Length::Fill).into(),
+            button(text(strings.string(Index::RestoreDefaults as usize)))
+                .padding([5, 10])
+                .on_press(preferences::tab_message(
+                    id,
+                    TAB_ID,
+                    Message::RestoreDefaults,
+                ))
+                .into(),
+        ];
+        if reverse_words {
+            setting.reverse();
+            defaults.reverse();
+        }
+        let mut content: Vec<Element<application::Message>> = vec![
+            row(setting).spacing(10).width(Length::Fill).into(),
+            row(defaults).spacing(10).width(Length::Fill).into(),
+        ];
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .spacing(10)
+            .width(Length::Fill)
+            .into()
+    }
+}
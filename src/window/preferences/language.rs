@@ -4,14 +4,14 @@
 #![allow(clippy::single_match)]
 
 use crate::{
-    application::{self, ApplicationError, session::Settings, StringGroup},
+    application::{self, ApplicationError, session::{Setting, Settings}, StringGroup},
     core::{
         error::CoreError,
         localisation::{Localisation, StringCache},
         traits::{AnyLocalisedTrait, TabTrait},
     },
     localisation::preferences::{Index, Strings},
-    window::preferences::{self, Setting,},
+    window::preferences::{self, PreferenceTab, TabId, TabMessage},
 };
 
 #[allow(unused_imports)]
@@ -20,6 +20,8 @@ use iced::{
     window, Alignment, Task, Element, Length, Point, Size,
 };
 
+use std::any::Any;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
@@ -29,6 +31,8 @@ use std::sync::Arc as RefCount;
 #[cfg(not(feature = "sync"))]
 use std::rc::Rc as RefCount;
 
+pub const TAB_ID: TabId = "language";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Message {
     LanguageSelected(String),
@@ -82,30 +86,29 @@ impl Tab {
         self.changed
     }
 
-    pub fn update_changed(&mut self) {
-        if self.original != self.selected {
-            self.changed = true;
-        }
+}
+
+impl PreferenceTab for Tab {
+    fn tab_id(&self) -> TabId {
+        TAB_ID
     }
 
-    pub fn selected(&mut self, message: Message) {
-        match message {
-            Message::LanguageSelected(language) => {
-                if self.selected != Some(language.clone()) {
-                    self.selected = Some(language);
+    fn selected(&mut self, message: &TabMessage, _string_cache: &StringCache) {
+        match message.downcast_ref::<Message>() {
+            Some(Message::LanguageSelected(language)) => {
+                if self.selected.as_ref() != Some(language) {
+                    self.selected = Some(language.clone());
                     self.update = true;
                 } else {
                     self.update = false;
                 }
             }
+            _ => {}
         }
     }
 
-    pub fn check_change(
-        &self,
-        strings: &Box<dyn AnyLocalisedTrait>,
-        changed_settings: &mut Vec<Setting>,
-    ) {
+    fn check_change(&self, string_cache: &StringCache, changed_settings: &mut Vec<Setting>) {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
         let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
         if self.original != self.selected {
             let language_selected_tag = actual
@@ -116,6 +119,16 @@ impl Tab {
             changed_settings.push(Setting::Language(RefCount::clone(language_selected_tag)));
         }
     }
+
+    fn cancelled(&mut self) {
+        if self.original != self.selected {
+            self.changed = true;
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl TabTrait for Tab {
@@ -124,6 +137,14 @@ impl TabTrait for Tab {
         String::from(strings.string(Index::Language as usize))
     }
 
+    fn searchable_labels(&self, string_cache: &StringCache) -> Vec<String> {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        vec![
+            String::from(strings.string(Index::Language as usize)),
+            String::from(strings.string(Index::LanguageUi as usize)),
+        ]
+    }
+
     fn content<'a>(
         &'a self,
         id: window::Id,
@@ -140,9 +161,10 @@ impl TabTrait for Tab {
                 strings.string(Index::LanguagePlaceholder as usize),
                 self.selected.as_ref(),
                 move |string| {
-                    application::Message::Preferences(
+                    preferences::tab_message(
                         id,
-                        preferences::Message::Language(Message::LanguageSelected(string)),
+                        TAB_ID,
+                        Message::LanguageSelected(string),
                     )
                 },
             )
@@ -152,6 +174,6 @@ impl TabTrait for Tab {
         if reverse_words {
             setting.reverse();
         }
-        row(setting).width(Length::Fill).height(Length::Fill).into()
+        row(setting).width(Length::Fill).into()
     }
 }
@@ -4,24 +4,34 @@
 #![allow(clippy::single_match)]
 
 use crate::{
-    application::{self, log::LogLevel, session::Settings, StringGroup},
+    application::{
+        self,
+        log::LogLevel,
+        session::{LogSetting, Setting, Settings},
+        StringGroup,
+    },
     core::{
         localisation::{Localisation, StringCache},
         traits::{AnyLocalisedTrait, TabTrait},
     },
     localisation::preferences::{Index, Strings},
-    window::preferences::{self, Setting},
+    window::preferences::{self, PreferenceTab, TabId, TabMessage},
 };
 
 #[allow(unused_imports)]
 use iced::{
-    widget::{button, column, combo_box, row, scrollable, text, Column, Row},
+    widget::{button, checkbox, column, combo_box, row, scrollable, text, Column, Row},
     window, Alignment, Task, Element, Length, Point, Size,
 };
 
+use i18n::utility::PlaceholderValue;
+use std::{any::Any, collections::HashMap};
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+pub const TAB_ID: TabId = "logs";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Message {
     LogLevelSelectedDefault(String),
@@ -29,15 +39,7 @@ pub enum Message {
     LogLevelSelectedOther(String),
     LogLevelSelectedIced(String),
     LogLevelSelectedI18n(String),
-}
-
-#[derive(PartialEq, Clone, Debug)]
-pub enum LogSetting {
-    LevelDefault(LogLevel),
-    LevelApplication(LogLevel),
-    LevelOther(LogLevel),
-    LevelIced(LogLevel),
-    LevelI18n(LogLevel),
+    LogToFile(bool),
 }
 
 pub struct Tab {
@@ -57,6 +59,8 @@ pub struct Tab {
     pub original_i18n: LogLevel,
     pub selected_i18n: LogLevel,
     pub selected_i18n_string: Option<String>,
+    pub original_to_file: bool,
+    pub selected_to_file: bool,
 }
 
 impl Tab {
@@ -90,6 +94,7 @@ impl Tab {
         let selected_i18n_string = actual
             .log_map_to_string(&settings.log_levels.i18n)
             .map(|x| x.to_string());
+        let original_to_file = settings.log_levels.log_to_file.is_some();
         Tab {
             list: combo_box::State::new(actual.log_list().to_vec()),
             original_default,
@@ -107,11 +112,57 @@ impl Tab {
             original_i18n,
             selected_i18n,
             selected_i18n_string,
+            original_to_file,
+            selected_to_file: original_to_file,
+        }
+    }
+
+    /// The resolved log level for a log level set to `Default`, else an empty text.
+    fn resolved<'a>(
+        &self,
+        level: LogLevel,
+        default: LogLevel,
+        localisation: &Localisation,
+        string_cache: &StringCache,
+    ) -> Element<'a, application::Message> {
+        if level != LogLevel::Default {
+            return text("").into();
         }
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        let Some(resolved) = actual.log_map_to_string(&level.resolve(default)) else {
+            return text("").into();
+        };
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert(
+            "level".to_string(),
+            PlaceholderValue::String(resolved.clone()),
+        );
+        match localisation.format_with_defaults("application", "resolved_log_level_format", &values)
+        {
+            Ok(value) => text(value.0.to_string()).into(),
+            Err(error) => {
+                warn!("Failed to localise the resolved log level: {}", error);
+                text(resolved.clone()).into()
+            }
+        }
+    }
+}
+
+impl PreferenceTab for Tab {
+    fn tab_id(&self) -> TabId {
+        TAB_ID
+    }
+
+    // The log settings are not needed on first use.
+    fn is_first_use_tab(&self) -> bool {
+        false
     }
 
     // Update localised combo box selection strings
-    pub fn update(&mut self, actual: &Strings) {
+    fn update_localisation(&mut self, string_cache: &StringCache) {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
         self.selected_default_string = actual
             .log_map_to_string(&self.selected_default)
             .map(|x| x.to_string());
@@ -129,37 +180,38 @@ impl Tab {
             .map(|x| x.to_string());
     }
 
-    pub fn selected(&mut self, message: Message, string_cache: &StringCache) {
+    fn selected(&mut self, message: &TabMessage, string_cache: &StringCache) {
         let strings = string_cache.get(&StringGroup::Preferences).unwrap();
         let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        let Some(message) = message.downcast_ref::<Message>() else {
+            return;
+        };
         match message {
             Message::LogLevelSelectedDefault(log_level) => {
-                self.selected_default = *actual.log_map_to_level(&log_level).unwrap();
-                self.selected_default_string = Some(log_level);
+                self.selected_default = *actual.log_map_to_level(log_level).unwrap();
+                self.selected_default_string = Some(log_level.clone());
             }
             Message::LogLevelSelectedApplication(log_level) => {
-                self.selected_application = *actual.log_map_to_level(&log_level).unwrap();
-                self.selected_application_string = Some(log_level);
+                self.selected_application = *actual.log_map_to_level(log_level).unwrap();
+                self.selected_application_string = Some(log_level.clone());
             }
             Message::LogLevelSelectedOther(log_level) => {
-                self.selected_other = *actual.log_map_to_level(&log_level).unwrap();
-                self.selected_other_string = Some(log_level);
+                self.selected_other = *actual.log_map_to_level(log_level).unwrap();
+                self.selected_other_string = Some(log_level.clone());
             }
             Message::LogLevelSelectedIced(log_level) => {
-                self.selected_iced = *actual.log_map_to_level(&log_level).unwrap();
-                self.selected_iced_string = Some(log_level);
+                self.selected_iced = *actual.log_map_to_level(log_level).unwrap();
+                self.selected_iced_string = Some(log_level.clone());
             }
             Message::LogLevelSelectedI18n(log_level) => {
-                self.selected_i18n = *actual.log_map_to_level(&log_level).unwrap();
-                self.selected_i18n_string = Some(log_level);
+                self.selected_i18n = *actual.log_map_to_level(log_level).unwrap();
+                self.selected_i18n_string = Some(log_level.clone());
             }
+            Message::LogToFile(enabled) => self.selected_to_file = *enabled,
         }
     }
 
-    pub fn check_change(
-        &self,
-        changed_settings: &mut Vec<Setting>,
-    ) {
+    fn check_change(&self, _string_cache: &StringCache, changed_settings: &mut Vec<Setting>) {
         if self.original_default != self.selected_default {
             changed_settings.push(Setting::Log(LogSetting::LevelDefault(
                 self.selected_default,
@@ -179,6 +231,13 @@ impl Tab {
         if self.original_i18n != self.selected_i18n {
             changed_settings.push(Setting::Log(LogSetting::LevelI18n(self.selected_i18n)));
         }
+        if self.original_to_file != self.selected_to_file {
+            changed_settings.push(Setting::Log(LogSetting::ToFile(self.selected_to_file)));
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
@@ -188,6 +247,19 @@ impl TabTrait for Tab {
         String::from(strings.string(Index::Logs as usize))
     }
 
+    fn searchable_labels(&self, string_cache: &StringCache) -> Vec<String> {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        vec![
+            String::from(strings.string(Index::Logs as usize)),
+            String::from(strings.string(Index::LogLevelDefault as usize)),
+            String::from(strings.string(Index::LogLevelApplication as usize)),
+            String::from(strings.string(Index::LogLevelOther as usize)),
+            String::from(strings.string(Index::LogLevelIced as usize)),
+            String::from(strings.string(Index::LogLevelI18n as usize)),
+            String::from(strings.string(Index::LogToFile as usize)),
+        ]
+    }
+
     fn content<'a>(
         &'a self,
         id: window::Id,
@@ -205,14 +277,16 @@ impl TabTrait for Tab {
         let mut setting: Vec<Element<application::Message>> = vec![
             text(strings.string(Index::LogLevelDefault as usize)).into(),
             text("").width(Length::Fill).into(),
+            self.resolved(self.selected_default, LogLevel::Default, localisation, string_cache),
             combo_box(
                 &self.list,
                 strings.string(Index::LogPlaceholder as usize),
                 self.selected_default_string.as_ref(),
                 move |string| {
-                    application::Message::Preferences(
+                    preferences::tab_message(
                         id,
-                        preferences::Message::Log(Message::LogLevelSelectedDefault(string)),
+                        TAB_ID,
+                        Message::LogLevelSelectedDefault(string),
                     )
                 },
             )
@@ -230,14 +304,21 @@ impl TabTrait for Tab {
             let mut setting: Vec<Element<application::Message>> = vec![
                 text(strings.string(Index::LogLevelApplication as usize)).into(),
                 text("").width(Length::Fill).into(),
+                self.resolved(
+                    self.selected_application,
+                    self.selected_default,
+                    localisation,
+                    string_cache,
+                ),
                 combo_box(
                     &self.list,
                     strings.string(Index::LogPlaceholder as usize),
                     self.selected_application_string.as_ref(),
                     move |string| {
-                        application::Message::Preferences(
+                        preferences::tab_message(
                             id,
-                            preferences::Message::Log(Message::LogLevelSelectedApplication(string)),
+                            TAB_ID,
+                            Message::LogLevelSelectedApplication(string),
                         )
                     },
                 )
@@ -253,14 +334,21 @@ impl TabTrait for Tab {
             let mut setting: Vec<Element<application::Message>> = vec![
                 text(strings.string(Index::LogLevelOther as usize)).into(),
                 text("").width(Length::Fill).into(),
+                self.resolved(
+                    self.selected_other,
+                    self.selected_default,
+                    localisation,
+                    string_cache,
+                ),
                 combo_box(
                     &self.list,
                     strings.string(Index::LogPlaceholder as usize),
                     self.selected_other_string.as_ref(),
                     move |string| {
-                        application::Message::Preferences(
+                        preferences::tab_message(
                             id,
-                            preferences::Message::Log(Message::LogLevelSelectedOther(string)),
+                            TAB_ID,
+                            Message::LogLevelSelectedOther(string),
                         )
                     },
                 )
@@ -276,14 +364,21 @@ impl TabTrait for Tab {
             let mut setting: Vec<Element<application::Message>> = vec![
                 text(strings.string(Index::LogLevelIced as usize)).into(),
                 text("").width(Length::Fill).into(),
+                self.resolved(
+                    self.selected_iced,
+                    self.selected_default,
+                    localisation,
+                    string_cache,
+                ),
                 combo_box(
                     &self.list,
                     strings.string(Index::LogPlaceholder as usize),
                     self.selected_iced_string.as_ref(),
                     move |string| {
-                        application::Message::Preferences(
+                        preferences::tab_message(
                             id,
-                            preferences::Message::Log(Message::LogLevelSelectedIced(string)),
+                            TAB_ID,
+                            Message::LogLevelSelectedIced(string),
                         )
                     },
                 )
@@ -299,14 +394,21 @@ impl TabTrait for Tab {
             let mut setting: Vec<Element<application::Message>> = vec![
                 text(strings.string(Index::LogLevelI18n as usize)).into(),
                 text("").width(Length::Fill).into(),
+                self.resolved(
+                    self.selected_i18n,
+                    self.selected_default,
+                    localisation,
+                    string_cache,
+                ),
                 combo_box(
                     &self.list,
                     strings.string(Index::LogPlaceholder as usize),
                     self.selected_i18n_string.as_ref(),
                     move |string| {
-                        application::Message::Preferences(
+                        preferences::tab_message(
                             id,
-                            preferences::Message::Log(Message::LogLevelSelectedI18n(string)),
+                            TAB_ID,
+                            Message::LogLevelSelectedI18n(string),
                         )
                     },
                 )
@@ -317,11 +419,29 @@ impl TabTrait for Tab {
                 setting.reverse();
             }
             settings.push(row(setting).into());
+
+            // Log to file
+            let mut setting: Vec<Element<application::Message>> = vec![
+                text(strings.string(Index::LogToFile as usize)).into(),
+                text("").width(Length::Fill).into(),
+                checkbox("", self.selected_to_file)
+                    .on_toggle(move |enabled| {
+                        preferences::tab_message(
+                            id,
+                            TAB_ID,
+                            Message::LogToFile(enabled),
+                        )
+                    })
+                    .into(),
+            ];
+            if reverse_words {
+                setting.reverse();
+            }
+            settings.push(row(setting).into());
         }
         Column::new()
             .push(column(settings))
             .width(Length::Fill)
-            .height(Length::Fill)
             .into()
 
     }
@@ -6,22 +6,26 @@
 use crate::{
     application::{
         self,
+        constants::{PACKAGE_NAME, TAB_HEADER_SIZE, TAB_PADDING},
         log::update_logger,
-        ApplicationError, session::Settings, WindowType, StringGroup},
+        ApplicationError, session::{Session, Setting, Settings, SideEffect}, WindowType, StringGroup},
     core::{
         error::CoreError,
         localisation::{Localisation, StringCache},
         traits::{AnyWindowTrait, TabTrait, WindowTrait},
     },
     localisation::preferences::{Index, Strings},
-    window::preferences::{language, logs,},
+    window::{
+        preferences::{backup, language, logs, theme,},
+        restore_defaults,
+    },
 };
-use i18n::utility::LanguageTag;
 use iced::{
     Alignment,
-    widget::{button, column, row,},
+    widget::{button, column, row, scrollable, scrollable::RelativeOffset, text, text_input},
     window, Task, Element, Length as Length,
 };
+use rfd::AsyncFileDialog;
 
 #[cfg(feature = "iced_aw")]
 use crate::iced_aw::widgets::sidebar::{self, SidebarWithContent, TabLabel};
@@ -31,32 +35,98 @@ use iced_aw::widgets::sidebar::{self, SidebarWithContent, TabLabel};
 
 use std::{
     any::Any,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    path::PathBuf,
     rc::Rc as RefCount,
+    sync::Arc,
 };
 
+#[cfg(any(test, feature = "debug_snapshot"))]
+use crate::core::traits::WindowSnapshot;
+
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Message {
     Accept,
     Cancel,
     TabSelected(TabId),
-    TabClosed(TabId), /// To be removed, testing new sidebar widget
-    Language(language::Message),
-    Log(logs::Message),
+    TabClosed(TabId),
+    Search(String),
+    Scrolled(RelativeOffset), // The tab content has been scrolled.
+    Tab(TabId, TabMessage), // A message of the tab's own message type, addressed to the tab.
+}
+
+/// The unique identifier of a tab, as stored in `Settings::last_preferences_tab`.
+pub type TabId = &'static str;
+
+/// A message of a tab, type-erased so that each tab defines its own message type. The tab
+/// retrieves its message using `downcast_ref()`.
+#[derive(Clone)]
+pub struct TabMessage(Arc<dyn TabPayload>);
+
+impl TabMessage {
+    pub fn new<T: Any + Debug + Send + Sync>(message: T) -> Self {
+        TabMessage(Arc::new(message))
+    }
+
+    /// The message as the tab's message type, `None` when the message is of another type.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        (*self.0).as_any().downcast_ref::<T>()
+    }
+}
+
+impl Debug for TabMessage {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+        self.0.fmt(formatter)
+    }
 }
 
-#[derive(PartialEq, Clone, Debug)]
-pub enum Setting {
-    Language(RefCount<LanguageTag>),
-    Log(logs::LogSetting),
+trait TabPayload: Any + Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub enum TabId {
-    Language,
-    Logs,
+impl<T: Any + Debug + Send + Sync> TabPayload for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The application message addressing the message to the tab of the Preferences window.
+pub fn tab_message<T: Any + Debug + Send + Sync>(
+    id: window::Id,
+    tab_id: TabId,
+    message: T,
+) -> application::Message {
+    application::Message::Preferences(id, Message::Tab(tab_id, TabMessage::new(message)))
+}
+
+const SCROLLABLE_ID: &str = "preferences_content"; // The scrollable of the tab content.
+
+/// A settings tab of the Preferences window. Additional tabs implement this trait, with their own
+/// `TabId` and message type, and are added to `State::try_new()`.
+pub trait PreferenceTab: TabTrait {
+    fn tab_id(&self) -> TabId;
+
+    /// Handle a message addressed to this tab, see `tab_message()`.
+    fn selected(&mut self, message: &TabMessage, string_cache: &StringCache);
+
+    /// Add the changed settings of this tab, when Accept is pressed.
+    fn check_change(&self, string_cache: &StringCache, changed_settings: &mut Vec<Setting>);
+
+    /// Update the localised strings held by the tab, after the language has been changed.
+    fn update_localisation(&mut self, _string_cache: &StringCache) {}
+
+    /// Cancel was pressed.
+    fn cancelled(&mut self) {}
+
+    /// Indicates whether the tab is displayed on first use.
+    fn is_first_use_tab(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any;
 }
 
 pub struct State {
@@ -65,8 +135,10 @@ pub struct State {
 
     // Tabs
     active_tab: TabId,
-    language: language::Tab, // i18n settings
-    logs: logs::Tab, // log settings
+    tabs: Vec<Box<dyn PreferenceTab>>, // All the tabs, in display order.
+    displayed: Vec<TabId>, // The displayed tabs, in display order.
+    search: String, // Only the displayed tabs with a matching setting label are listed.
+    offset: RelativeOffset, // The scroll position of the tab content, see `restore_offset()`.
 }
 
 impl State {
@@ -77,14 +149,25 @@ impl State {
         first_use: bool,
     ) -> Result<Self, ApplicationError> {
         let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let tabs: Vec<Box<dyn PreferenceTab>> = vec![
+            Box::new(language::Tab::try_new(localisation, strings, settings)?), // i18n settings
+            Box::new(logs::Tab::new(strings, settings)), // log settings
+            Box::new(theme::Tab::new(strings.as_ref(), settings)), // theme settings
+            Box::new(backup::Tab::new()), // export and import of the settings
+
+            // Add additional preferences above this comment.
+        ];
+        let displayed = default_tabs(&tabs, first_use);
         Ok(State {
             changed_settings: None,
             first_use,
 
             // Tabs
-            active_tab: TabId::Language,
-            language: language::Tab::try_new(localisation, strings, settings)?,
-            logs: logs::Tab::new(strings, settings),
+            active_tab: initial_tab(settings.last_preferences_tab.as_deref(), &displayed),
+            tabs,
+            displayed,
+            search: String::new(),
+            offset: RelativeOffset::START,
         })
     }
 
@@ -93,11 +176,93 @@ impl State {
     }
 
     pub fn language_update(&self) -> bool {
-        self.language.update()
+        self.language_tab().is_some_and(|tab| tab.update())
     }
 
     pub fn language_changed(&self) -> bool {
-        self.language.changed()
+        self.language_tab().is_some_and(|tab| tab.changed())
+    }
+
+    /// Update the localised strings of the tabs, after the language has been changed.
+    pub fn update_localisation(&mut self, string_cache: &StringCache) {
+        for tab in self.tabs.iter_mut() {
+            tab.update_localisation(string_cache);
+        }
+    }
+
+    /// Indicates whether the window geometries are also restored, when restoring the defaults.
+    pub fn include_windows(&self) -> bool {
+        self.tab(backup::TAB_ID)
+            .and_then(|tab| tab.as_any().downcast_ref::<backup::Tab>())
+            .is_some_and(|tab| tab.include_windows())
+    }
+
+    fn language_tab(&self) -> Option<&language::Tab> {
+        self.tab(language::TAB_ID)?.as_any().downcast_ref::<language::Tab>()
+    }
+
+    fn tab(&self, tab_id: TabId) -> Option<&dyn PreferenceTab> {
+        self.tabs.iter().find(|tab| tab.tab_id() == tab_id).map(|tab| tab.as_ref())
+    }
+
+    /// The displayed tabs having a setting label matching the search text, in display order.
+    fn matching_tabs(&self, string_cache: &StringCache) -> Vec<TabId> {
+        self.displayed
+            .iter()
+            .filter(|tab_id| {
+                self.tab(tab_id).is_some_and(|tab| {
+                    is_match(&tab.searchable_labels(string_cache), self.search.as_str())
+                })
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Set the search text, selecting the first matching tab when the active tab has no matching
+    /// setting label.
+    fn search(&mut self, search: String, string_cache: &StringCache) {
+        self.search = search;
+        let matching = self.matching_tabs(string_cache);
+        if !matching.contains(&self.active_tab) {
+            if let Some(tab_id) = matching.first() {
+                self.active_tab = tab_id;
+            }
+        }
+    }
+
+    /// Scroll the tab content to the stored scroll position, such as after the widget tree has been
+    /// rebuilt by a localisation update.
+    pub fn restore_offset(&self) -> Task<application::Message> {
+        scrollable::snap_to(scrollable::Id::new(SCROLLABLE_ID), self.offset)
+    }
+
+    /// The tab area: the title of the tab, and the scrollable content of the tab.
+    fn tab_view<'a>(
+        &'a self,
+        tab: &'a dyn PreferenceTab,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        column![
+            text(tab.title(string_cache)).size(TAB_HEADER_SIZE),
+            scrollable(tab.content(id, localisation, string_cache))
+                .id(scrollable::Id::new(SCROLLABLE_ID))
+                .on_scroll(move |viewport| {
+                    application::Message::Preferences(
+                        id,
+                        Message::Scrolled(viewport.relative_offset()),
+                    )
+                })
+                .width(Length::Fill)
+                .height(Length::Fill),
+        ]
+        .spacing(20)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Alignment::Center)
+        .padding(TAB_PADDING)
+        .into()
     }
 
     pub fn is_first_use(&self) -> bool {
@@ -106,9 +271,53 @@ impl State {
 
     pub fn end_first_use(&mut self) {
         self.first_use = false;
+        self.displayed = default_tabs(&self.tabs, false);
+    }
+
+    /// Close the tab, selecting the previous tab if it was the active tab. The last remaining tab
+    /// can't be closed.
+    fn close_tab(&mut self, tab_id: TabId) {
+        if self.displayed.len() <= 1 {
+            return;
+        }
+        let Some(index) = self.displayed.iter().position(|tab| *tab == tab_id) else {
+            return;
+        };
+        self.displayed.remove(index);
+        if self.active_tab == tab_id {
+            self.active_tab = self.displayed[index.saturating_sub(1)];
+        }
     }
 }
 
+/// Indicates whether any of the labels contains the search text, ignoring case. An empty search
+/// text matches all the labels.
+fn is_match(labels: &[String], search: &str) -> bool {
+    let search = search.trim().to_lowercase();
+    search.is_empty()
+        || labels
+            .iter()
+            .any(|label| label.to_lowercase().contains(search.as_str()))
+}
+
+/// The last selected tab, or the Language tab when the stored tab is unknown or not displayed,
+/// such as the Logs tab on first use.
+fn initial_tab(stored: Option<&str>, displayed: &[TabId]) -> TabId {
+    stored
+        .and_then(|stored| displayed.iter().find(|tab_id| **tab_id == stored))
+        .copied()
+        .unwrap_or(language::TAB_ID)
+}
+
+/// The tabs displayed when the window is opened, in display order. On first use only the tabs
+/// indicating `is_first_use_tab()` are displayed, thus not the Logs tab.
+fn default_tabs(tabs: &[Box<dyn PreferenceTab>], first_use: bool) -> Vec<TabId> {
+    tabs.iter()
+        .filter(|tab| !first_use || tab.is_first_use_tab())
+        .map(|tab| tab.tab_id())
+        .collect()
+}
+
 impl AnyWindowTrait for State {
     fn as_any(&self) -> &dyn Any {
         self
@@ -125,8 +334,18 @@ impl WindowTrait for State {
     }
 
     fn title<'a>(&'a self, string_cache: &'a StringCache) -> &String {
-        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
-        strings.title()
+        string_cache.title(&StringGroup::Preferences)
+    }
+
+    #[cfg(any(test, feature = "debug_snapshot"))]
+    fn debug_snapshot(&self, string_cache: &StringCache) -> WindowSnapshot {
+        WindowSnapshot {
+            window_type: WindowType::Preferences,
+            enabled: true,
+            title: self.title(string_cache).clone(),
+            is_unsaved: false,
+            is_first_use: self.first_use,
+        }
     }
 
     fn try_update(
@@ -134,29 +353,38 @@ impl WindowTrait for State {
         message: application::Message,
         string_cache: &StringCache,
     ) -> Result<Task<application::Message>, ApplicationError> {
-        let task = Task::none();
+        let mut task = Task::none();
         match message {
             application::Message::Preferences(_id, message) => match message {
                 Message::Cancel => {
-                    self.language.update_changed()
+                    for tab in self.tabs.iter_mut() {
+                        tab.cancelled();
+                    }
+                }
+                Message::TabSelected(tab_id) => {
+                    // The content of the selected tab starts at the top.
+                    self.active_tab = tab_id;
+                    self.offset = RelativeOffset::START;
+                    task = self.restore_offset();
                 }
-                Message::TabSelected(tab_id) => self.active_tab = tab_id,
-                Message::TabClosed(_tab_id) => {} // for now do nothing
-                Message::Language(language) => self.language.selected(language),
-                Message::Log(logs) => self.logs.selected(logs, string_cache),
+                Message::Scrolled(offset) => self.offset = offset,
+                Message::TabClosed(tab_id) => self.close_tab(tab_id),
+                Message::Search(search) => self.search(search, string_cache),
                 Message::Accept => {
-                    let strings = string_cache.get(&StringGroup::Preferences).unwrap();
-                    #[allow(unused_mut)]
                     let mut changed_settings = Vec::<Setting>::new();
-                    self.language.check_change(strings, &mut changed_settings);
-                    self.logs.check_change(&mut changed_settings);
-
-                    // Insert additional settings above.
-
+                    for tab in self.tabs.iter() {
+                        tab.check_change(string_cache, &mut changed_settings);
+                    }
                     if !changed_settings.is_empty() {
                         self.changed_settings = Some(changed_settings);
                     }
                 }
+                Message::Tab(tab_id, message) => {
+                    // Dispatch to the addressed tab.
+                    if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.tab_id() == tab_id) {
+                        tab.selected(&message, string_cache);
+                    }
+                }
             },
             _ => {}
         }
@@ -176,30 +404,30 @@ impl WindowTrait for State {
         let mut content: Vec<Element<application::Message>> =
             Vec::<Element<application::Message>>::new();
 
-        // Preferences - scrollable
-        #[allow(unused_mut)]
-        let mut tabs: Vec<(TabId, TabLabel, Element<application::Message>)> =
-            Vec::<(TabId, TabLabel, Element<application::Message>)>::new();
-
-        // Language
-        tabs.push((
-            TabId::Language,
-            self.language.tab_label(string_cache),
-            self.language.view(id, localisation, string_cache),
-        ));
-
-        // Logs
-        let display = !self.first_use;
-        if display {
-            tabs.push((
-                TabId::Logs,
-                self.logs.tab_label(string_cache),
-                self.logs.view(id, localisation, string_cache),
-            ));
-        }
-
-        // Add additional preferences above this comment.
+        // Search
+        content.push(
+            text_input(strings.string(Index::Search as usize), self.search.as_str())
+                .on_input(move |search| {
+                    application::Message::Preferences(id, Message::Search(search))
+                })
+                .padding(5)
+                .into(),
+        );
 
+        // Preferences - scrollable
+        let mut tabs = self
+            .matching_tabs(string_cache)
+            .iter()
+            .filter_map(|tab_id| self.tab(tab_id))
+            .map(|tab| {
+                (
+                    tab.tab_id(),
+                    tab.tab_label(string_cache),
+                    self.tab_view(tab, id, localisation, string_cache),
+                )
+            })
+            .collect::<Vec<(TabId, TabLabel, Element<application::Message>)>>();
+        let no_results = tabs.is_empty();
         if reverse_lines {
             tabs.reverse();
         }
@@ -210,15 +438,24 @@ impl WindowTrait for State {
         .set_active_tab(&self.active_tab)
         .sidebar_position(sidebar::SidebarPosition::Start)
         .align_tabs(Alignment::Start)
-        //.on_close(move |tab_id| application::Message::Preferences(id, Message::TabClosed(tab_id)))
+        .on_close(move |tab_id| application::Message::Preferences(id, Message::TabClosed(tab_id)))
         //.close_icon_position(sidebar::Position::End)
         .tab_label_padding(0.0);
-        content.push(
-            preferences
-                .width(Length::Fill)
-                .height(Length::Fill)
-                .into(),
-        );
+        if no_results {
+            content.push(
+                column![text(strings.string(Index::NoResults as usize))]
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+            );
+        } else {
+            content.push(
+                preferences
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+            );
+        }
         content.push(" ".into()); // Paragraph separation
 
         // Buttons
@@ -258,6 +495,10 @@ impl WindowTrait for State {
             .into()
     }
 
+    fn is_singleton(&self) -> bool {
+        true
+    }
+
     fn is_global_disable(&self) -> bool {
         true
     }
@@ -267,15 +508,9 @@ pub fn display(
     application: &mut application::State,
     parent: window::Id,
 ) -> Result<Task<application::Message>, ApplicationError> {
-    if !application
+    application
         .string_cache
-        .exists(&StringGroup::Preferences)
-    {
-        application.string_cache.insert(
-            StringGroup::Preferences,
-            Box::new(Strings::try_new(&application.localisation)?),
-        );
-    }
+        .try_ensure(StringGroup::Preferences, &application.localisation)?;
     let state = State::try_new(
         &application.localisation,
         &application.string_cache,
@@ -301,9 +536,41 @@ pub fn try_update(
 
             // Post internal update
             match inner_message {
-                Message::Language(language) => {
-                    match language {
-                        language::Message::LanguageSelected(string) => {
+                Message::TabSelected(tab_id) => {
+                    application.session.settings.last_preferences_tab = Some(tab_id.to_string());
+                }
+                Message::Tab(tab_id, message) if *tab_id == backup::TAB_ID => {
+                    match message.downcast_ref::<backup::Message>() {
+                        Some(backup::Message::Export) => {
+                            let dialog = AsyncFileDialog::new()
+                                .add_filter("RON", &["ron"])
+                                .set_directory(application.environment.data_dir())
+                                .set_file_name(format!("{}.ron", PACKAGE_NAME));
+                            task = Task::perform(dialog.save_file(), move |handle| {
+                                application::Message::ExportSettings(
+                                    id,
+                                    handle.map(|handle| handle.path().to_path_buf()),
+                                )
+                            })
+                        }
+                        Some(backup::Message::RestoreDefaults) => {
+                            task = restore_defaults::display(application, id)?
+                        }
+                        Some(backup::Message::Import) => {
+                            let dialog = AsyncFileDialog::new().add_filter("RON", &["ron"]);
+                            task = Task::perform(dialog.pick_file(), move |handle| {
+                                application::Message::ImportSettings(
+                                    id,
+                                    handle.map(|handle| handle.path().to_path_buf()),
+                                )
+                            })
+                        }
+                        _ => {}
+                    }
+                }
+                Message::Tab(tab_id, message) if *tab_id == language::TAB_ID => {
+                    match message.downcast_ref::<language::Message>() {
+                        Some(language::Message::LanguageSelected(string)) => {
                             let mut _update = false;
                             {
                                 let actual = state.as_any().downcast_ref::<State>().unwrap();
@@ -334,33 +601,25 @@ pub fn try_update(
                                         .try_update(&application.localisation)?;
                                 }
 
-                                // Update localised combo box selection strings
+                                // Update localised combo box selection strings, and keep the scroll
+                                // position of the rebuilt widget tree.
                                 {
                                     let actual =
                                         state.as_any_mut().downcast_mut::<State>().unwrap();
-                                    let strings = application
-                                        .string_cache
-                                        .get(&StringGroup::Preferences)
-                                        .unwrap();
-                                    let actual_strings = strings
-                                        .as_any()
-                                        .downcast_ref::<Strings>()
-                                        .unwrap();
-                                    actual.logs.update(actual_strings);
+                                    actual.update_localisation(&application.string_cache);
+                                    task = actual.restore_offset();
                                 }
 
-                                // Update main windows, usually the dynamic title strings.
+                                // Update windows, usually the dynamic title strings.
                                 {
-                                    let list = application.manager.thread_list();
-                                    for thread_id in list {
-                                        let Some(&mut ref mut state) = application.manager.state_mut(&thread_id) else {
-                                            return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
-                                        };
-                                        state.try_localise(&application.localisation)?;
-                                    }
+                                    let localisation = &application.localisation;
+                                    application.manager.for_each_state_mut(|_, state| {
+                                        state.try_localise(localisation)
+                                    })?;
                                 }
                             }
                         }
+                        None => {}
                     }
                 }
                 Message::Accept => {
@@ -370,74 +629,29 @@ pub fn try_update(
                         _changed_settings = actual.result_vector();
                     }
                     debug!("{:?}", _changed_settings);
-                    let mut logging_update = false;
-
-                    // Handle all the changed settings, where necessary update components that require immediate
-                    // effect.
-                    if _changed_settings.is_some() {
-                        let binding = _changed_settings.unwrap();
-                        let iterator = binding.iter();
-                        for setting in iterator {
-                            match setting {
-                                Setting::Language(language) => {
-                                    application.session.settings.ui.language =
-                                        language.as_str().to_string();
-                                }
-                                Setting::Log(log) => match log {
-                                    logs::LogSetting::LevelDefault(log_level) => {
-                                        application.session.settings.log_levels.default =
-                                            *log_level;
-                                        trace!(
-                                            "Default: {}",
-                                            application.session.settings.log_levels.default
-                                        );
-                                        logging_update = true;
-                                    }
-                                    logs::LogSetting::LevelApplication(log_level) => {
-                                        application.session.settings.log_levels.application =
-                                            *log_level;
-                                        trace!(
-                                            "Application: {}",
-                                            application.session.settings.log_levels.application
-                                        );
-                                        logging_update = true;
-                                    }
-                                    logs::LogSetting::LevelOther(log_level) => {
-                                        application.session.settings.log_levels.other = *log_level;
-                                        trace!(
-                                            "Other: {}",
-                                            application.session.settings.log_levels.other
-                                        );
-                                        logging_update = true;
-                                    }
-                                    logs::LogSetting::LevelIced(log_level) => {
-                                        application.session.settings.log_levels.iced = *log_level;
-                                        trace!(
-                                            "iced: {}",
-                                            application.session.settings.log_levels.iced
-                                        );
-                                        logging_update = true;
-                                    }
-                                    logs::LogSetting::LevelI18n(log_level) => {
-                                        application.session.settings.log_levels.i18n = *log_level;
-                                        trace!(
-                                            "i18n: {}",
-                                            application.session.settings.log_levels.i18n
-                                        );
-                                        logging_update = true;
-                                    }
-                                },
-
-                                #[allow(unreachable_patterns)]
-                                _ => {}
+
+                    // Apply all the changed settings, then perform their side effects once, for
+                    // the components that require immediate effect.
+                    let mut side_effects = Vec::<SideEffect>::new();
+                    for setting in _changed_settings.unwrap_or_default() {
+                        for side_effect in application.session.settings.apply_change(setting) {
+                            if !side_effects.contains(&side_effect) {
+                                side_effects.push(side_effect);
                             }
                         }
                     }
-                    if logging_update {
-                        update_logger(
-                            &mut application.environment.logger,
-                            &application.session.settings.log_levels,
-                        )
+                    for side_effect in side_effects {
+                        match side_effect {
+                            SideEffect::ReLocalise => try_apply_language(application)?,
+                            SideEffect::UpdateLogger => update_logger(
+                                &mut application.environment.logger,
+                                &application.session.settings.log_levels,
+                                &application.environment.log_buffer,
+                            ),
+                            // Takes effect immediately, as `State::theme()` is used for every
+                            // window.
+                            SideEffect::ReTheme => {}
+                        }
                     }
                     task = close(application, id)?
                 }
@@ -449,39 +663,7 @@ pub fn try_update(
                     }
                     if _update {
                         // Reset back to session's language.
-                        {
-                            let tag = match application
-                                .localisation
-                                .language_tag_registry()
-                                .tag(application.session.settings.ui.language.as_str())
-                            {
-                                Err(error) => {
-                                    return Err(ApplicationError::Core(
-                                        CoreError::LanguageTagRegistry(error),
-                                    ))
-                                }
-                                Ok(value) => value,
-                            };
-                            let _ = application.localisation.change_default_language(tag)?;
-                        }
-
-                        // Update all windows localisation strings
-                        {
-                            let _ = application
-                                .string_cache
-                                .try_update(&application.localisation)?;
-                        }
-
-                        // Update main windows, usually the dynamic title strings.
-                        {
-                            let list = application.manager.thread_list();
-                            for thread_id in list {
-                                let Some(&mut ref mut state) = application.manager.state_mut(&thread_id) else {
-                                    return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
-                                };
-                                state.try_localise(&application.localisation)?;
-                            }
-                        }
+                        try_apply_language(application)?;
                     }
                     task = close(application, id)?
                 }
@@ -494,6 +676,97 @@ pub fn try_update(
     Ok(task)
 }
 
+/// Change the localiser's language to the session's language, and update the localised strings of
+/// all the windows.
+fn try_apply_language(application: &mut application::State) -> Result<(), ApplicationError> {
+    let tag = match application
+        .localisation
+        .language_tag_registry()
+        .tag(application.session.settings.ui.language.as_str())
+    {
+        Err(error) => {
+            return Err(ApplicationError::Core(
+                CoreError::LanguageTagRegistry(error),
+            ))
+        }
+        Ok(value) => value,
+    };
+    let _ = application.localisation.change_default_language(tag)?;
+
+    // Update all windows localisation strings
+    let _ = application
+        .string_cache
+        .try_update(&application.localisation)?;
+
+    // Update windows, usually the dynamic title strings.
+    let localisation = &application.localisation;
+    application.manager.for_each_state_mut(|_, state| {
+        state.try_localise(localisation)
+    })?;
+    Ok(())
+}
+
+/// Export the saved settings to the file selected in the file save dialogue.
+pub fn try_export(
+    application: &mut application::State,
+    path: PathBuf,
+) -> Result<Task<application::Message>, ApplicationError> {
+    application.session.export_to(&path)?;
+    info!("Exported the settings to ‘{}’.", path.display());
+    Ok(Task::none())
+}
+
+/// Import the settings from the file selected in the file open dialogue, replacing the settings
+/// and window geometries immediately. The Preferences window is closed, as its tabs display the
+/// replaced settings.
+pub fn try_import(
+    application: &mut application::State,
+    id: window::Id,
+    path: PathBuf,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let session = Session::import_from(&path)?;
+    info!("Importing the settings from ‘{}’.", path.display());
+    application.session.settings = session.settings;
+    application.session.windows = session.windows;
+    try_apply_language(application)?;
+    update_logger(
+        &mut application.environment.logger,
+        &application.session.settings.log_levels,
+        &application.environment.log_buffer,
+    );
+    let tasks = application.apply_settings();
+    Ok(tasks.chain(close(application, id)?))
+}
+
+/// Restore the default settings, confirmed in the restore defaults dialogue, and also the default
+/// window geometries when selected in the Backup tab. The remaining session data is kept. Both the
+/// dialogue and the Preferences window are closed, as its tabs display the replaced settings.
+pub fn try_restore_defaults(
+    application: &mut application::State,
+    id: window::Id,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let Some(parent) = application.manager.parent(&id) else {
+        return Err(CoreError::ExpectedWindowParent(WindowType::RestoreDefaults))?;
+    };
+    let Some(state) = application.manager.state(&parent) else {
+        return Err(CoreError::WindowIdNotFound(parent, "window_states".to_string()))?;
+    };
+    let include_windows = state.as_any().downcast_ref::<State>().unwrap().include_windows();
+    info!("Restoring the default settings.");
+    application.session.settings = Settings::default();
+    if include_windows {
+        application.session.windows.clear(); // Missing geometries use `WINDOW_DEFAULT_DATA`.
+    }
+    try_apply_language(application)?;
+    update_logger(
+        &mut application.environment.logger,
+        &application.session.settings.log_levels,
+        &application.environment.log_buffer,
+    );
+    let tasks = application.apply_settings().chain(application.manager.close_window(id)?);
+    Ok(tasks.chain(close(application, parent)?))
+}
+
 pub fn cancel_and_close(
     application: &mut application::State,
     id: window::Id,
@@ -504,6 +777,9 @@ pub fn cancel_and_close(
     )?)
 }
 
+/// Close the Preferences window. On first use the window is its own thread, thus the thread is
+/// closed, else the focus is returned to the window that opened it, found using
+/// `Manager::parent()`, rather than assuming a particular window.
 pub fn close(
     application: &mut application::State,
     id: window::Id,
@@ -513,9 +789,228 @@ pub fn close(
     };
     let actual = state.as_any_mut().downcast_mut::<State>().unwrap();
     if actual.is_first_use() {
-        Ok(application.close_thread(id)?)
+        return application.close_thread(id);
+    }
+    let Some(parent) = application.manager.parent(&id) else {
+        return Err(CoreError::ExpectedWindowParent(WindowType::Preferences))?;
+    };
+    Ok(application.manager.close_window(id)?.chain(window::gain_focus(parent)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::localisation;
+
+    #[test]
+    fn tab_message_downcasts_to_its_own_type() {
+        let message = TabMessage::new(backup::Message::IncludeWindows(true));
+        assert_eq!(
+            message.downcast_ref::<backup::Message>(),
+            Some(&backup::Message::IncludeWindows(true))
+        );
+        assert!(message.downcast_ref::<theme::Message>().is_none());
+    }
+
+    #[test]
+    fn initial_tab_is_stored_displayed_tab() {
+        let displayed = [language::TAB_ID, theme::TAB_ID, "plugin"];
+        assert_eq!(initial_tab(Some("plugin"), &displayed), "plugin");
+        assert_eq!(initial_tab(Some(logs::TAB_ID), &displayed), language::TAB_ID);
+        assert_eq!(initial_tab(Some("unknown"), &displayed), language::TAB_ID);
+        assert_eq!(initial_tab(None, &displayed), language::TAB_ID);
+    }
+
+    /// A state without tabs, displaying the tab identifiers.
+    fn state(displayed: &[TabId], active_tab: TabId) -> State {
+        State {
+            changed_settings: None,
+            first_use: false,
+            active_tab,
+            tabs: Vec::<Box<dyn PreferenceTab>>::new(),
+            displayed: displayed.to_vec(),
+            search: String::new(),
+            offset: RelativeOffset::START,
+        }
+    }
+
+    #[test]
+    fn close_active_tab_selects_previous_tab() {
+        let mut state = state(&[language::TAB_ID, theme::TAB_ID, backup::TAB_ID], theme::TAB_ID);
+        state.close_tab(theme::TAB_ID);
+        assert_eq!(state.displayed, vec![language::TAB_ID, backup::TAB_ID]);
+        assert_eq!(state.active_tab, language::TAB_ID);
+
+        // Without a previous tab, the next tab is selected.
+        state.close_tab(language::TAB_ID);
+        assert_eq!(state.displayed, vec![backup::TAB_ID]);
+        assert_eq!(state.active_tab, backup::TAB_ID);
+    }
+
+    #[test]
+    fn close_inactive_tab_keeps_active_tab() {
+        let mut state = state(&[language::TAB_ID, theme::TAB_ID, backup::TAB_ID], theme::TAB_ID);
+        state.close_tab(backup::TAB_ID);
+        assert_eq!(state.displayed, vec![language::TAB_ID, theme::TAB_ID]);
+        assert_eq!(state.active_tab, theme::TAB_ID);
+
+        // Closing a tab that isn't displayed does nothing.
+        state.close_tab(logs::TAB_ID);
+        assert_eq!(state.displayed, vec![language::TAB_ID, theme::TAB_ID]);
+    }
+
+    #[test]
+    fn last_tab_is_not_closed() {
+        let mut state = state(&[theme::TAB_ID], theme::TAB_ID);
+        state.close_tab(theme::TAB_ID);
+        assert_eq!(state.displayed, vec![theme::TAB_ID]);
+        assert_eq!(state.active_tab, theme::TAB_ID);
+    }
+
+    #[test]
+    fn titles_follow_changed_language() {
+        use crate::window::default::{menu_bar, Message as DefaultMessage};
+
+        let mut application = application::State::new_for_test();
+        let update = |application: &mut application::State, message| {
+            let _ = application.try_update(message).unwrap();
+        };
+        update(&mut application, application::Message::Initialise);
+        let id = application.manager.first_of_type(WindowType::Default).unwrap();
+        for message in [
+            menu_bar::Message::New(WindowType::Main),
+            menu_bar::Message::LogViewer,
+            menu_bar::Message::About,
+            menu_bar::Message::Preferences,
+        ] {
+            update(
+                &mut application,
+                application::Message::Default(id, DefaultMessage::MenuBar(message)),
+            );
+        }
+        let titles = |application: &application::State| {
+            let mut titles = Vec::<(WindowType, String)>::new();
+            for window_type in WindowType::all() {
+                for id in application.manager.ids_of_type(window_type.clone()) {
+                    let state = application.manager.state(&id).unwrap();
+                    let title = state.title(&application.string_cache).clone();
+                    titles.push((window_type.clone(), title));
+                }
+            }
+            titles
+        };
+        let before = titles(&application);
+        assert_eq!(before.len(), 5);
+        application.session.settings.ui.language = "it".to_string();
+        try_apply_language(&mut application).unwrap();
+
+        // The cache-backed titles are those of a new Italian string cache, while the Main window
+        // refreshes its instance-owned title in `try_localise()`.
+        let italian = localisation("it");
+        for ((window_type, before), (_, title)) in before.iter().zip(titles(&application)) {
+            assert_ne!(&title, before);
+            let string_group = match window_type {
+                WindowType::Default => StringGroup::Default,
+                WindowType::Preferences => StringGroup::Preferences,
+                WindowType::About => StringGroup::About,
+                WindowType::LogViewer => StringGroup::LogViewer,
+                _ => continue,
+            };
+            let strings = crate::localisation::try_strings(&string_group, &italian).unwrap();
+            assert_eq!(&title, strings.string(0));
+        }
+    }
+
+    #[test]
+    fn search_matches_labels() {
+        let labels = vec!["Default log level".to_string(), "Log to file".to_string()];
+        assert!(is_match(&labels, ""));
+        assert!(is_match(&labels, "   "));
+        assert!(is_match(&labels, "file"));
+        assert!(is_match(&labels, " LOG LEVEL "));
+        assert!(!is_match(&labels, "theme"));
+        assert!(!is_match(&[], "log"));
+        assert!(is_match(&[], ""));
+        assert!(is_match(&["Lingua".to_string()], "lingua"));
+    }
+
+
+    #[test]
+    fn stored_tab_falls_back_to_language_on_first_use() {
+        let mut application = application::State::new_for_test();
+        let _ = application.try_update(application::Message::Initialise).unwrap();
+        application
+            .string_cache
+            .try_ensure(StringGroup::Preferences, &application.localisation)
+            .unwrap();
+        let mut settings = application.session.settings.clone();
+        settings.last_preferences_tab = Some(logs::TAB_ID.to_string());
+        let open = |settings: &Settings, first_use| {
+            State::try_new(
+                &application.localisation,
+                &application.string_cache,
+                settings,
+                first_use,
+            )
+            .unwrap()
+        };
+        assert_eq!(open(&settings, false).active_tab, logs::TAB_ID);
+
+        // The Logs tab is hidden on first use.
+        let state = open(&settings, true);
+        assert!(!state.displayed.contains(&logs::TAB_ID));
+        assert_eq!(state.active_tab, language::TAB_ID);
+
+        // A tab that no longer exists.
+        settings.last_preferences_tab = Some("removed".to_string());
+        assert_eq!(open(&settings, false).active_tab, language::TAB_ID);
+    }
+
+
+    #[test]
+    fn scroll_offset_is_kept_until_tab_selected() {
+        let string_cache = StringCache::new();
+        let id = window::Id::unique();
+        let mut state = state(&[language::TAB_ID, theme::TAB_ID], language::TAB_ID);
+        let update = |state: &mut State, message| {
+            let message = application::Message::Preferences(id, message);
+            let _ = state.try_update(message, &string_cache).unwrap();
+        };
+        let offset = RelativeOffset { x: 0.0, y: 0.75 };
+        update(&mut state, Message::Scrolled(offset));
+        assert_eq!(state.offset, offset);
+
+        // Rebuilding the localised strings keeps the stored offset, for `restore_offset()`.
+        state.update_localisation(&string_cache);
+        assert_eq!(state.offset, offset);
+
+        // The content of the selected tab starts at the top.
+        update(&mut state, Message::TabSelected(theme::TAB_ID));
+        assert_eq!(state.offset, RelativeOffset::START);
+    }
+
 
-    } else {
-        Ok(application.manager.close_window(id)?)
+    #[test]
+    fn closing_returns_to_opener() {
+        use crate::window::main;
+
+        let mut application = application::State::new_for_test();
+        let update = |application: &mut application::State, message| {
+            let _ = application.try_update(message).unwrap();
+        };
+        update(&mut application, application::Message::Initialise);
+        let _ = main::display(&mut application, Some("opener.txt".to_string()), false).unwrap();
+        let opener = application.manager.first_of_type(WindowType::Main).unwrap();
+        let preferences = main::Message::MenuBar(main::menu_bar::Message::Preferences);
+        update(&mut application, application::Message::Main(opener, preferences));
+        let id = application.manager.first_of_type(WindowType::Preferences).unwrap();
+        assert_eq!(application.manager.parent(&id), Some(opener));
+        assert_eq!(application.manager.is_enabled(&opener), Some(false));
+
+        let _ = close(&mut application, id).unwrap();
+        update(&mut application, application::Message::WindowClosed(id));
+        assert_eq!(application.manager.is_enabled(&opener), Some(true));
+        assert_eq!(application.manager.ids_of_type(WindowType::Main), vec![opener]);
+        assert_eq!(application.manager.count_by_type(WindowType::Default), 1);
     }
 }
@@ -0,0 +1,204 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+#![allow(clippy::single_match)]
+
+use crate::{
+    application::{
+        self,
+        constants::ACCENT_PRESETS,
+        session::{Setting, Settings},
+        theme::{accent_color, ThemePreference},
+        StringGroup,
+    },
+    core::{
+        localisation::{Localisation, StringCache},
+        traits::{AnyLocalisedTrait, TabTrait},
+    },
+    localisation::preferences::{Index, Strings},
+    window::preferences::{self, PreferenceTab, TabId, TabMessage},
+};
+
+#[allow(unused_imports)]
+use iced::{
+    widget::{button, column, combo_box, row, text},
+    window, Border, Element, Length, Theme,
+};
+
+use std::any::Any;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+pub const TAB_ID: TabId = "theme";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    ThemeSelected(String),
+    AccentSelected([f32; 3]),
+}
+
+pub struct Tab {
+    list: combo_box::State<String>,
+    original: ThemePreference,
+    selected: ThemePreference,
+    original_accent: [f32; 3],
+    selected_accent: [f32; 3],
+}
+
+impl Tab {
+    pub fn new(
+        strings: &dyn AnyLocalisedTrait,
+        settings: &Settings,
+    ) -> Self {
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        Tab {
+            list: combo_box::State::new(actual.theme_list().to_vec()),
+            original: settings.theme,
+            selected: settings.theme,
+            original_accent: settings.accent,
+            selected_accent: settings.accent,
+        }
+    }
+
+}
+
+impl PreferenceTab for Tab {
+    fn tab_id(&self) -> TabId {
+        TAB_ID
+    }
+
+    // Update localised combo box list, after a language change.
+    fn update_localisation(&mut self, string_cache: &StringCache) {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        self.list = combo_box::State::new(actual.theme_list().to_vec());
+    }
+
+    fn selected(&mut self, message: &TabMessage, string_cache: &StringCache) {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        let Some(message) = message.downcast_ref::<Message>() else {
+            return;
+        };
+        match message {
+            Message::ThemeSelected(theme) => {
+                self.selected = *actual.theme_map_to_preference(theme).unwrap();
+            }
+            Message::AccentSelected(accent) => self.selected_accent = *accent,
+        }
+    }
+
+    fn check_change(&self, _string_cache: &StringCache, changed_settings: &mut Vec<Setting>) {
+        if self.original != self.selected {
+            changed_settings.push(Setting::Theme(self.selected));
+        }
+        if self.original_accent != self.selected_accent {
+            changed_settings.push(Setting::Accent(self.selected_accent));
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl TabTrait for Tab {
+    fn title(&self, string_cache: &StringCache) -> String {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        String::from(strings.string(Index::Theme as usize))
+    }
+
+    fn searchable_labels(&self, string_cache: &StringCache) -> Vec<String> {
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        vec![
+            String::from(strings.string(Index::Theme as usize)),
+            String::from(strings.string(Index::ThemeUi as usize)),
+            String::from(strings.string(Index::AccentUi as usize)),
+        ]
+    }
+
+    fn content<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let reverse_words = localisation.layout_data().reverse_words;
+        let strings = string_cache.get(&StringGroup::Preferences).unwrap();
+        let actual = strings.as_any().downcast_ref::<Strings>().unwrap();
+        let mut setting: Vec<Element<application::Message>> = vec![
+            text(strings.string(Index::ThemeUi as usize)).into(),
+            text("").width(Length::Fill).into(),
+            combo_box(
+                &self.list,
+                strings.string(Index::ThemePlaceholder as usize),
+                actual.theme_map_to_string(&self.selected),
+                move |string| {
+                    preferences::tab_message(
+                        id,
+                        TAB_ID,
+                        Message::ThemeSelected(string),
+                    )
+                },
+            )
+            .width(100)
+            .into(),
+        ];
+        if reverse_words {
+            setting.reverse();
+        }
+        let theme = row(setting).width(Length::Fill);
+
+        // Accent colour: the current value, followed by the presets.
+        let mut swatches: Vec<Element<application::Message>> = vec![
+            swatch(id, self.original_accent, self.selected_accent),
+            text(" ").into(),
+        ];
+        for accent in ACCENT_PRESETS {
+            swatches.push(swatch(id, accent, self.selected_accent));
+        }
+        if reverse_words {
+            swatches.reverse();
+        }
+        let mut setting: Vec<Element<application::Message>> = vec![
+            text(strings.string(Index::AccentUi as usize)).into(),
+            text("").width(Length::Fill).into(),
+            row(swatches).spacing(4).into(),
+        ];
+        if reverse_words {
+            setting.reverse();
+        }
+        column![theme, row(setting).width(Length::Fill)]
+            .spacing(10)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+/// A colour swatch button for selecting the accent colour, where the selected accent is outlined.
+fn swatch<'a>(
+    id: window::Id,
+    accent: [f32; 3],
+    selected: [f32; 3],
+) -> Element<'a, application::Message> {
+    let outline = if accent == selected { 2.0 } else { 0.0 };
+    button(text(""))
+        .width(20)
+        .height(20)
+        .style(move |theme: &Theme, _status| button::Style {
+            background: Some(accent_color(accent).into()),
+            border: Border {
+                color: theme.palette().text,
+                width: outline,
+                radius: 4.0.into(),
+            },
+            ..button::Style::default()
+        })
+        .on_press(preferences::tab_message(
+            id,
+            TAB_ID,
+            Message::AccentSelected(accent),
+        ))
+        .into()
+}
@@ -0,0 +1,175 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The window for recoverable errors, where the user can retry the failed operation, unlike the
+//! FatalError window that terminates the application.
+
+#![allow(clippy::single_match)]
+
+use crate::{
+    application::{self, ApplicationError, WindowType, StringGroup},
+    core::{
+        error::CoreError,
+        localisation::{Localisation, StringCache},
+        traits::{AnyWindowTrait, WindowTrait},
+    },
+    localisation::recoverable_error::Index,
+};
+use iced::{
+    widget::{button, column, row, scrollable, text},
+    window, Alignment, Task, Element, Length,
+};
+use std::any::Any;
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Retry,
+    Cancel,
+}
+
+pub struct State {
+    message: RefCount<String>, // The localised error.
+    retry: application::Message, // The message dispatched again on Retry.
+}
+
+impl State {
+    pub fn new(
+        localisation: &Localisation,
+        error: &ApplicationError,
+        retry: application::Message,
+    ) -> Self {
+        let message = match localisation.format_error_with_defaults(error) {
+            Ok(value) => value.0,
+            Err(_) => RefCount::new(error.to_string()),
+        };
+        State { message, retry }
+    }
+}
+
+impl AnyWindowTrait for State {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl WindowTrait for State {
+    fn window_type(&self) -> WindowType {
+        WindowType::RecoverableError
+    }
+
+    fn title<'a>(&'a self, string_cache: &'a StringCache) -> &'a String {
+        string_cache.title(&StringGroup::RecoverableError)
+    }
+
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let align_start = localisation.layout_data().align_words_start;
+        let reverse_words = localisation.layout_data().reverse_words;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let strings = string_cache.get(&StringGroup::RecoverableError).unwrap();
+
+        // Buttons
+        let mut buttons: Vec<Element<application::Message>> = vec![
+            button(text(strings.string(Index::Retry as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::RecoverableError(id, Message::Retry))
+                .into(),
+            button(text(strings.string(Index::Cancel as usize)))
+                .padding([5, 10])
+                .on_press(application::Message::RecoverableError(id, Message::Cancel))
+                .into(),
+        ];
+        if reverse_words {
+            buttons.reverse();
+        }
+
+        let mut content: Vec<Element<application::Message>> = vec![
+            // Message
+            scrollable(
+                column![text(self.message.as_str())]
+                    .width(Length::Fill)
+                    .align_x(align_start),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            " ".into(), // Paragraph separation
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
+        ];
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(2)
+            .into()
+    }
+}
+
+/// Display the error, which disables only the parent window. On Retry the `retry` message is
+/// dispatched again, using `application::Message::Retry`, thus a further failure displays this
+/// window again.
+pub fn display(
+    application: &mut application::State,
+    parent: window::Id,
+    error: ApplicationError,
+    retry: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    warn!("Recoverable error: {}", error);
+    application
+        .string_cache
+        .try_ensure(StringGroup::RecoverableError, &application.localisation)?;
+    let state = State::new(&application.localisation, &error, retry);
+    Ok(application
+        .manager
+        .try_create_window(&mut application.session, Box::new(state), parent)?)
+}
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut tasks = Task::none();
+    match message {
+        application::Message::RecoverableError(id, ref inner_message) => {
+            match inner_message {
+                Message::Cancel => tasks = application.manager.close_window(id)?,
+                Message::Retry => {
+                    let Some(parent) = application.manager.parent(&id) else {
+                        return Err(CoreError::ExpectedWindowParent(WindowType::RecoverableError))?;
+                    };
+                    let Some(state) = application.manager.state(&id) else {
+                        return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+                    };
+                    let actual = state.as_any().downcast_ref::<State>().unwrap();
+                    let retry = application::Message::Retry(parent, Box::new(actual.retry.clone()));
+                    tasks = application.manager.close_window(id)?.chain(Task::done(retry));
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(tasks)
+}
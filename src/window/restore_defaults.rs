@@ -0,0 +1,43 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The dialogue confirming the restoring of the default settings, displayed from the Backup tab of
+//! the Preferences window. The strings are of the Preferences string group.
+
+use crate::{
+    application::{self, ApplicationError, StringGroup, WindowType},
+    localisation::preferences::Index,
+    window::confirm::{self, Text},
+};
+use iced::{window, Task};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+/// The restore defaults dialogue, which disables only the Preferences window.
+pub fn new() -> confirm::State {
+    let group = StringGroup::Preferences;
+    confirm::State::new(
+        WindowType::RestoreDefaults,
+        Text::Cached(group.clone(), Index::RestoreDefaultsTitle as usize),
+    )
+    .statement(Text::Cached(group.clone(), Index::RestoreDefaultsStatement as usize))
+    .button(
+        Text::Cached(group.clone(), Index::Restore as usize),
+        application::Message::RestoreDefaults,
+    )
+    .button(Text::Cached(group, Index::Cancel as usize), application::Message::Close)
+    .padding(20)
+}
+
+pub fn display(
+    application: &mut application::State,
+    parent: window::Id,
+) -> Result<Task<application::Message>, ApplicationError> {
+    application
+        .string_cache
+        .try_ensure(StringGroup::Preferences, &application.localisation)?;
+    Ok(application
+        .manager
+        .try_create_window(&mut application.session, Box::new(new()), parent)?)
+}
@@ -5,28 +5,15 @@ use crate::{
     application::{self, ApplicationError, constants::APPLICATION_NAME_SHORT, StringGroup, WindowType},
     core::{
         error::CoreError,
-        localisation::{Localisation, StringCache},
-        traits::{AnyWindowTrait, SaveDataTrait, WindowTrait},
+        localisation::Localisation,
+        traits::SaveDataTrait,
     },
-    localisation::unsaved_data::{Index, Strings},
-    window::main,
+    localisation::unsaved_data::Index,
+    window::{confirm::{self, Text}, main},
 };
 use i18n::utility::PlaceholderValue;
-use iced::{
-    widget::{button, column, row, text},
-    window, Alignment, Task, Element, Length,
-};
-use std::{
-    any::Any,
-    collections::HashMap,
-};
-
-#[cfg(not(feature = "sync"))]
-use std::rc::Rc as RefCount;
-
-#[cfg(feature = "sync")]
-#[cfg(target_has_atomic = "ptr")]
-use std::sync::Arc as RefCount;
+use iced::{window, Task};
+use std::collections::HashMap;
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
@@ -38,124 +25,62 @@ pub enum Message {
     Cancel,
 }
 
-pub struct State {
-    title: RefCount<String>,
-    message: RefCount<String>,
-}
-
-impl State {
-    pub fn try_new(
-        localisation: &Localisation,
-        name: &str,
-    ) -> Result<State, ApplicationError> {
-        let title = {
-            let mut values = HashMap::<String, PlaceholderValue>::new();
-            values.insert(
-                "application".to_string(),
-                PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
-            );
-            let localised = localisation.literal_with_defaults("application", "unsaved_data")?;
-            values.insert(
-                "window".to_string(),
-                PlaceholderValue::Localised(localised.0, localised.1),
-            );
-            values.insert(
-                "name".to_string(),
-                PlaceholderValue::String(name.to_string()),
-            );
-            localisation.format_with_defaults("application", "window_title_name_format", &values)?
-        }.0;
-        let message = {
-            let mut values = HashMap::<String, PlaceholderValue>::new();
-            values.insert(
-                "name".to_string(),
-                PlaceholderValue::String(name.to_string()),
-            );
-            localisation.format_with_defaults("application", "unsaved_data_statement", &values)?
-        }.0;
-        Ok(State {title, message})
+/// The unsaved data dialogue for the named file, which disables only its parent window.
+pub fn try_new(
+    localisation: &Localisation,
+    name: &str,
+) -> Result<confirm::State, ApplicationError> {
+    let (title, statement) = localise(localisation, name)?;
+    let name = name.to_string();
+    let group = StringGroup::UnsavedData;
+    let mut state = confirm::State::new(WindowType::UnsavedData, title)
+        .button(Text::Cached(group.clone(), Index::Save as usize), |id| {
+            application::Message::UnsavedData(id, Message::Save)
+        })
+        .button(Text::Cached(group.clone(), Index::Discard as usize), |id| {
+            application::Message::UnsavedData(id, Message::Discard)
+        })
+        .button(Text::Cached(group, Index::Cancel as usize), |id| {
+            application::Message::UnsavedData(id, Message::Cancel)
+        })
+        .relocalise(Box::new(move |localisation| localise(localisation, name.as_str())));
+    if let Some(statement) = statement {
+        state = state.statement(statement);
     }
+    Ok(state)
 }
 
-impl AnyWindowTrait for State {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-}
-
-impl WindowTrait for State {
-    fn window_type(&self) -> WindowType {
-        WindowType::UnsavedData
-    }
-
-    fn title<'a>(&'a self, _string_cache: &'a StringCache) -> &String {
-        &self.title
-    }
-
-    fn view<'a>(
-        &'a self,
-        id: window::Id,
-        localisation: &Localisation,
-        string_cache: &'a StringCache,
-    ) -> Element<application::Message> {
-        let reverse_words = localisation.layout_data().reverse_words;
-        let reverse_lines = localisation.layout_data().reverse_lines;
-        let strings = string_cache.get(&StringGroup::UnsavedData).unwrap();
-        let mut content: Vec<Element<application::Message>> =
-            Vec::<Element<application::Message>>::new();
-
-        // Message
-        content.push(
-            column![text(self.message.as_str())]
-                .width(Length::Fill)
-                .align_x(Alignment::Center)
-                .into(),
+/// The title and statement, both containing the name of the file.
+fn localise(
+    localisation: &Localisation,
+    name: &str,
+) -> Result<(Text, Option<Text>), ApplicationError> {
+    let title = {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert(
+            "application".to_string(),
+            PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
         );
-        content.push(text(" ").height(Length::Fill).into()); // Paragraph separation
-
-        // Buttons
-        let mut buttons: Vec<Element<application::Message>> =
-            Vec::<Element<application::Message>>::new();
-        buttons.push(
-            button(text(strings.string(Index::Save as usize).as_str()))
-                .padding([5, 10])
-                .on_press(application::Message::UnsavedData(id, Message::Save))
-                .into(),
+        let localised = localisation.literal_with_defaults("application", "unsaved_data")?;
+        values.insert(
+            "window".to_string(),
+            PlaceholderValue::Localised(localised.0, localised.1),
         );
-        buttons.push(
-            button(text(strings.string(Index::Discard as usize).as_str()))
-                .padding([5, 10])
-                .on_press(application::Message::UnsavedData(id, Message::Discard))
-                .into(),
+        values.insert(
+            "name".to_string(),
+            PlaceholderValue::String(name.to_string()),
         );
-        buttons.push(
-            button(text(strings.string(Index::Cancel as usize).as_str()))
-                .padding([5, 10])
-                .on_press(application::Message::UnsavedData(id, Message::Cancel))
-                .into(),
+        localisation.format_with_defaults("application", "window_title_name_format", &values)?
+    }.0;
+    let statement = {
+        let mut values = HashMap::<String, PlaceholderValue>::new();
+        values.insert(
+            "name".to_string(),
+            PlaceholderValue::String(name.to_string()),
         );
-        if reverse_words {
-            buttons.reverse();
-        }
-        content.push(
-            column![row(buttons).spacing(10)]
-                .width(Length::Fill)
-                .align_x(Alignment::Center)
-                .into(),
-        );
-        if reverse_lines {
-            content.reverse();
-        }
-        column(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(2)
-            .into()
-    }
+        localisation.format_with_defaults("application", "unsaved_data_statement", &values)?
+    }.0;
+    Ok((Text::Owned(title), Some(Text::Owned(statement))))
 }
 
 pub fn display(
@@ -163,16 +88,10 @@ pub fn display(
     parent: window::Id,
     name: &str, // Reason for &str instead of &PathBuff, is to also support database connections
 ) -> Result<Task<application::Message>, ApplicationError> {
-    if !application
+    application
         .string_cache
-        .exists(&StringGroup::UnsavedData)
-    {
-        application.string_cache.insert(
-            StringGroup::UnsavedData,
-            Box::new(Strings::try_new(&application.localisation)?),
-        );
-    }
-    let state = State::try_new(&application.localisation, name)?;
+        .try_ensure(StringGroup::UnsavedData, &application.localisation)?;
+    let state = try_new(&application.localisation, name)?;
     Ok(application
         .manager
         .try_create_window(&mut application.session, Box::new(state), parent)?)
@@ -0,0 +1,244 @@
+// This file is part of `iced_af` crate. For the terms of use, please see the file
+// called LICENSE-BSD-3-Clause at the top level of the `iced_af` crate.
+
+//! The unsaved data dialogue for closing multiple windows having unsaved data, such as for the
+//! Close All menu item. A single window having unsaved data uses the `unsaved_data` dialogue.
+
+#![allow(clippy::single_match)]
+
+use crate::{
+    application::{self, ApplicationError, constants::APPLICATION_NAME_SHORT, StringGroup, WindowType},
+    core::{
+        error::CoreError,
+        localisation::{Localisation, StringCache},
+        traits::{AnyWindowTrait, SaveDataTrait, WindowTrait},
+    },
+    localisation::unsaved_data::Index,
+    window::main,
+};
+use i18n::utility::PlaceholderValue;
+use iced::{
+    widget::{button, column, row, scrollable, text},
+    window, Alignment, Task, Element, Length,
+};
+use std::{
+    any::Any,
+    collections::HashMap,
+};
+
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as RefCount;
+
+#[cfg(feature = "sync")]
+#[cfg(target_has_atomic = "ptr")]
+use std::sync::Arc as RefCount;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    SaveAll,
+    DiscardAll,
+    Cancel,
+}
+
+pub struct State {
+    title: RefCount<String>,
+    statement: RefCount<String>, // Pluralised for the number of windows.
+    unsaved: Vec<(window::Id, String)>, // The windows having unsaved data, with their names.
+}
+
+impl State {
+    pub fn try_new(
+        localisation: &Localisation,
+        unsaved: Vec<(window::Id, String)>,
+    ) -> Result<State, ApplicationError> {
+        let (title, statement) = localise(localisation, unsaved.len())?;
+        Ok(State {
+            title,
+            statement,
+            unsaved,
+        })
+    }
+
+    /// The windows having unsaved data, which the user is deciding upon.
+    pub fn windows(&self) -> Vec<window::Id> {
+        self.unsaved.iter().map(|(id, _)| *id).collect()
+    }
+}
+
+impl AnyWindowTrait for State {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl WindowTrait for State {
+    fn window_type(&self) -> WindowType {
+        WindowType::UnsavedDataBatch
+    }
+
+    fn title<'a>(&'a self, _string_cache: &'a StringCache) -> &'a String {
+        &self.title
+    }
+
+    fn view<'a>(
+        &'a self,
+        id: window::Id,
+        localisation: &Localisation,
+        string_cache: &'a StringCache,
+    ) -> Element<'a, application::Message> {
+        let reverse_words = localisation.layout_data().reverse_words;
+        let reverse_lines = localisation.layout_data().reverse_lines;
+        let strings = string_cache.get(&StringGroup::UnsavedData).unwrap();
+        let mut content: Vec<Element<application::Message>> =
+            Vec::<Element<application::Message>>::new();
+
+        // Message
+        content.push(
+            column![text(self.statement.as_str())]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
+        );
+        let names = self
+            .unsaved
+            .iter()
+            .map(|(_, name)| text(name.as_str()).into())
+            .collect::<Vec<Element<application::Message>>>();
+        content.push(
+            scrollable(column(names).width(Length::Fill).padding([0, 10]))
+                .height(Length::Fill)
+                .into(),
+        );
+
+        // Buttons
+        let mut buttons: Vec<Element<application::Message>> = vec![
+            button(text(strings.string(Index::SaveAll as usize).as_str()))
+                .padding([5, 10])
+                .on_press(application::Message::UnsavedDataBatch(id, Message::SaveAll))
+                .into(),
+            button(text(strings.string(Index::DiscardAll as usize).as_str()))
+                .padding([5, 10])
+                .on_press(application::Message::UnsavedDataBatch(id, Message::DiscardAll))
+                .into(),
+            button(text(strings.string(Index::Cancel as usize).as_str()))
+                .padding([5, 10])
+                .on_press(application::Message::UnsavedDataBatch(id, Message::Cancel))
+                .into(),
+        ];
+        if reverse_words {
+            buttons.reverse();
+        }
+        content.push(
+            column![row(buttons).spacing(10)]
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
+        );
+        if reverse_lines {
+            content.reverse();
+        }
+        column(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(2)
+            .into()
+    }
+
+    fn is_global_disable(&self) -> bool {
+        true
+    }
+
+    fn try_localise(
+        &mut self,
+        localisation: &Localisation,
+    ) -> Result<(), ApplicationError> {
+        (self.title, self.statement) = localise(localisation, self.unsaved.len())?;
+        Ok(())
+    }
+}
+
+/// Display the dialogue for the windows having unsaved data. The first window is used as the
+/// parent window.
+pub fn display(
+    application: &mut application::State,
+    unsaved: Vec<(window::Id, String)>,
+) -> Result<Task<application::Message>, ApplicationError> {
+    application
+        .string_cache
+        .try_ensure(StringGroup::UnsavedData, &application.localisation)?;
+    let parent = unsaved[0].0;
+    let state = State::try_new(&application.localisation, unsaved)?;
+    Ok(application
+        .manager
+        .try_create_window(&mut application.session, Box::new(state), parent)?)
+}
+
+pub fn try_update(
+    application: &mut application::State,
+    message: application::Message,
+) -> Result<Task<application::Message>, ApplicationError> {
+    let mut tasks = Task::none();
+    match message {
+        application::Message::UnsavedDataBatch(id, ref inner_message) => {
+            match inner_message {
+                Message::Cancel => tasks = application.manager.close_window(id)?,
+                Message::SaveAll | Message::DiscardAll => {
+                    if *inner_message == Message::SaveAll {
+                        let Some(state) = application.manager.state(&id) else {
+                            return Err(CoreError::WindowIdNotFound(id, "window_states".to_string()))?;
+                        };
+                        let actual = state.as_any().downcast_ref::<State>().unwrap();
+                        let unsaved = actual.unsaved.clone();
+                        for (unsaved_id, name) in unsaved {
+                            let Some(state) = application.manager.state_mut(&unsaved_id) else {
+                                return Err(CoreError::WindowIdNotFound(
+                                    unsaved_id,
+                                    "window_states".to_string(),
+                                ))?;
+                            };
+                            if let Some(actual) = state.as_any_mut().downcast_mut::<main::State>() {
+                                debug!("Saving {}.", name);
+                                actual.try_save()?;
+                            }
+                        }
+                    }
+
+                    // Proceed with closing all the threads, this dialogue closes with its parent.
+                    for thread_id in application.manager.ids_of_type(WindowType::Main) {
+                        tasks = tasks.chain(application.close_thread(thread_id)?);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(tasks)
+}
+
+/// Returns the title, and the statement pluralised for the number of windows.
+fn localise(
+    localisation: &Localisation,
+    count: usize,
+) -> Result<(RefCount<String>, RefCount<String>), CoreError> {
+    let mut values = HashMap::<String, PlaceholderValue>::new();
+    values.insert(
+        "application".to_string(),
+        PlaceholderValue::String(APPLICATION_NAME_SHORT.to_string()),
+    );
+    let localised = localisation.literal_with_defaults("application", "unsaved_data")?;
+    values.insert(
+        "window".to_string(),
+        PlaceholderValue::Localised(localised.0, localised.1),
+    );
+    let title = localisation.format_with_defaults("application", "window_title_format", &values)?.0;
+    let statement = localisation
+        .format_plural("application", "unsaved_windows", count, &HashMap::new())?.0;
+    Ok((title, statement))
+}